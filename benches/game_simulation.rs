@@ -21,7 +21,7 @@ fn benchmark_multiple_games(c: &mut Criterion) {
     c.bench_function("100_games", |b| {
         b.iter(|| {
             for seed in 0..100 {
-                run_game(black_box(&deck), black_box(seed), black_box(&db));
+                run_game(black_box(&deck), black_box(seed), black_box(&db), black_box(false));
             }
         })
     });
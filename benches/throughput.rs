@@ -0,0 +1,92 @@
+//! Throughput regression benchmarks.
+//!
+//! The card database and deck are embedded at compile time so these run from any CWD.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use mtg_reanimator::card::{Card, CardDatabase};
+use mtg_reanimator::game::mana::{can_afford_cost, tap_lands_for_cost};
+use mtg_reanimator::game::state::GameState;
+use mtg_reanimator::game::zones::{Library, Permanent};
+use mtg_reanimator::rng::GameRng;
+use mtg_reanimator::simulation::deck::parse_deck_list;
+use mtg_reanimator::simulation::engine::run_game;
+
+const CARDS_JSON: &str = include_str!("../cards.json");
+const DECK_TXT: &str = include_str!("../deck.txt");
+const GAMES_PER_ITER: u64 = 5000;
+
+fn load_fixture() -> (CardDatabase, Vec<Card>) {
+    let db = CardDatabase::from_json(CARDS_JSON).expect("Failed to load embedded cards");
+    let deck = parse_deck_list(DECK_TXT, &db).expect("Failed to parse embedded deck");
+    (db, deck)
+}
+
+/// Four untapped lands that can pay for Superior Spider-Man ({2}{U}{B})
+fn representative_board(db: &CardDatabase) -> GameState {
+    let mut state = GameState::new();
+    state.turn = 4;
+    for name in ["Watery Grave", "Underground Mortuary", "Forest", "Swamp"] {
+        let land = db.get_card(name).expect("Land should exist");
+        state.battlefield.add_permanent(Permanent::new(land, 1));
+    }
+    state
+}
+
+fn benchmark_games_per_second(c: &mut Criterion) {
+    let (db, deck) = load_fixture();
+
+    let mut group = c.benchmark_group("throughput");
+    group.throughput(Throughput::Elements(GAMES_PER_ITER));
+    group.sample_size(10);
+    group.bench_function("5000_games_fixed_seed", |b| {
+        b.iter(|| {
+            for seed in 0..GAMES_PER_ITER {
+                black_box(run_game(black_box(&deck), seed, &db, false));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn benchmark_mana_payment(c: &mut Criterion) {
+    let (db, _) = load_fixture();
+    let state = representative_board(&db);
+    let spider_man = match db.get_card("Superior Spider-Man").expect("Spider-Man should exist") {
+        Card::Creature(creature) => creature,
+        _ => unreachable!("Superior Spider-Man is a creature"),
+    };
+    let cost = spider_man.base.mana_cost.clone();
+
+    c.bench_function("can_afford_cost_spider_man", |b| {
+        b.iter(|| can_afford_cost(black_box(&cost), black_box(&state), Some(&spider_man)))
+    });
+
+    c.bench_function("tap_lands_for_cost_spider_man", |b| {
+        b.iter_batched(
+            || state.clone(),
+            |mut board| tap_lands_for_cost(black_box(&cost), &mut board, Some(&spider_man)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn benchmark_library_shuffle(c: &mut Criterion) {
+    let (db, deck) = load_fixture();
+    let mut rng = GameRng::new(Some(42));
+
+    let mut library = Library::new();
+    for card in deck.iter().skip(7) {
+        library.add_card(card.clone());
+    }
+    c.bench_function("library_shuffle_53_cards", |b| b.iter(|| library.shuffle(&mut rng)));
+
+    let mut uniform = Library::new();
+    uniform.set_skip_redundant_shuffles(true);
+    for _ in 0..20 {
+        uniform.add_card(db.get_card("Swamp").expect("Swamp should exist"));
+    }
+    c.bench_function("library_shuffle_uniform_skipped", |b| b.iter(|| uniform.shuffle(&mut rng)));
+}
+
+criterion_group!(benches, benchmark_games_per_second, benchmark_mana_payment, benchmark_library_shuffle);
+criterion_main!(benches);
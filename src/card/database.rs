@@ -1,4 +1,6 @@
-use crate::card::types::Card;
+use crate::card::types::{Card, ManaCost, ManaCostError};
+#[cfg(test)]
+use crate::card::types::{BaseCard, CreatureCard, LandCard, ManaColor, SpellCard};
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -10,21 +12,50 @@ pub enum CardDatabaseError {
     JsonError(#[from] serde_json::Error),
     #[error("Card not found: {0}")]
     CardNotFound(String),
+    #[error("Could not read card database '{path}': {source}")]
+    ReadError { path: String, source: std::io::Error },
+    #[error("Invalid card database '{path}': {source}")]
+    InvalidFile { path: String, source: serde_json::Error },
+    #[error("Invalid cost for '{card}': {source}")]
+    InvalidCost { card: String, source: ManaCostError },
+    #[error("Card '{0}' has both \"cost\" and \"mana_cost\"")]
+    ConflictingCost(String),
 }
 
+/// The cards.json the binary was built with, used when no file is found at runtime
+pub const EMBEDDED_CARDS: &str = include_str!("../../cards.json");
+
 /// Card database that loads cards from JSON
 pub struct CardDatabase {
     cards: HashMap<String, Card>,
 }
 
 impl CardDatabase {
-    /// Load cards from a JSON file
+    /// Load cards from a JSON file (errors name the path)
     pub fn from_file(path: &str) -> Result<Self, CardDatabaseError> {
-        let content = std::fs::read_to_string(path)?;
-        let cards_vec: Vec<Card> = serde_json::from_str(&content)?;
+        let content = std::fs::read_to_string(path)
+            .map_err(|source| CardDatabaseError::ReadError { path: path.to_string(), source })?;
+        Self::from_json(&content).map_err(|e| match e {
+            CardDatabaseError::JsonError(source) => CardDatabaseError::InvalidFile { path: path.to_string(), source },
+            other => other,
+        })
+    }
+
+    /// Load the cards.json embedded at build time
+    pub fn embedded() -> Self {
+        Self::from_json(EMBEDDED_CARDS).expect("embedded cards.json is valid")
+    }
+
+    /// Load cards from a JSON string (same format as cards.json). A card may give its
+    /// cost as a string, `"cost": "{2}{U}{B}"`, instead of a `mana_cost` object; its
+    /// `mana_value` then defaults to the cost's.
+    pub fn from_json(content: &str) -> Result<Self, CardDatabaseError> {
+        let values: Vec<serde_json::Value> = serde_json::from_str(content)?;
 
         let mut cards = HashMap::new();
-        for card in cards_vec {
+        for mut value in values {
+            Self::expand_cost_string(&mut value)?;
+            let card: Card = serde_json::from_value(value)?;
             let name = card.name().to_string();
             cards.insert(name, card);
         }
@@ -32,6 +63,31 @@ impl CardDatabase {
         Ok(CardDatabase { cards })
     }
 
+    /// Build a database from cards already in memory (a later card replaces an earlier one with the same name)
+    pub fn from_cards(cards: impl IntoIterator<Item = Card>) -> Self {
+        let cards = cards.into_iter().map(|card| (card.name().to_string(), card)).collect();
+        CardDatabase { cards }
+    }
+
+    /// Replace a `"cost"` string with the structured `mana_cost` (and `mana_value`)
+    fn expand_cost_string(value: &mut serde_json::Value) -> Result<(), CardDatabaseError> {
+        let Some(card) = value.as_object_mut() else {
+            return Ok(());
+        };
+        let Some(serde_json::Value::String(text)) = card.remove("cost") else {
+            return Ok(());
+        };
+        let name = card.get("name").and_then(|n| n.as_str()).unwrap_or("?").to_string();
+        if card.contains_key("mana_cost") {
+            return Err(CardDatabaseError::ConflictingCost(name));
+        }
+
+        let cost = ManaCost::parse(&text).map_err(|source| CardDatabaseError::InvalidCost { card: name, source })?;
+        card.entry("mana_value").or_insert_with(|| cost.mana_value().into());
+        card.insert("mana_cost".to_string(), serde_json::to_value(&cost)?);
+        Ok(())
+    }
+
     /// Get a card by name
     pub fn get_card(&self, name: &str) -> Result<Card, CardDatabaseError> {
         self.cards
@@ -45,7 +101,77 @@ impl CardDatabase {
         self.cards.len()
     }
 
+    /// All cards in the database, in no particular order
+    pub fn cards(&self) -> impl Iterator<Item = &Card> {
+        self.cards.values()
+    }
 
+    /// Serialize every card, sorted by name, in the cards.json format (loads back with `from_json`)
+    pub fn to_json(&self) -> String {
+        let mut cards: Vec<&Card> = self.cards.values().collect();
+        cards.sort_by(|a, b| a.name().cmp(b.name()));
+        serde_json::to_string_pretty(&cards).expect("card types always serialize")
+    }
+}
+
+#[cfg(test)]
+impl CardDatabase {
+    /// A handful of cards copied from cards.json, built in code so unit tests
+    /// don't depend on the working directory
+    pub fn fixture() -> Self {
+        fn base(name: &str, cost: &str) -> BaseCard {
+            let mana_cost = ManaCost::parse(cost).expect("fixture costs are valid");
+            BaseCard { name: name.to_string(), mana_value: mana_cost.mana_value(), mana_cost }
+        }
+        fn creature(name: &str, cost: &str, power: u32, toughness: u32, types: &[&str], abilities: &[&str]) -> CreatureCard {
+            CreatureCard {
+                base: base(name, cost),
+                abilities: abilities.iter().map(|a| a.to_string()).collect(),
+                ..CreatureCard::test(name, power, toughness, types)
+            }
+        }
+
+        let spider_man = CreatureCard {
+            is_legendary: true,
+            ..creature("Superior Spider-Man", "{2}{U}{B}", 4, 4, &["Spider", "Human", "Hero"], &["mind_swap_copy"])
+        };
+        let kiora = CreatureCard {
+            is_legendary: true,
+            ..creature("Kiora, the Rising Tide", "{2}{U}", 3, 2, &["Merfolk", "Noble"],
+                &["etb_draw_2_discard_2", "threshold_create_octopus"])
+        };
+        let ardyn = CreatureCard {
+            is_legendary: true,
+            hardcast_ok: false,
+            ..creature("Ardyn, the Usurper", "{5}{B}{B}{B}", 4, 4, &["Elder", "Human", "Noble"],
+                &["gives_demons_haste", "gives_demons_menace", "gives_demons_lifelink", "starscourge"])
+        };
+        let overlord = CreatureCard {
+            impending_cost: Some(ManaCost::parse("{1}{B}").unwrap()),
+            impending_counters: Some(5),
+            ..creature("Overlord of the Balemurk", "{3}{B}{B}", 5, 5, &["Avatar", "Horror"],
+                &["impending_5", "etb_or_attack_mill_4_return"])
+        };
+        let cache_grab = SpellCard {
+            base: base("Cache Grab", "{1}{G}"),
+            abilities: vec!["mill_4_return_permanent".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        };
+
+        Self::from_cards([
+            LandCard::basic("Forest", ManaColor::Green).into(),
+            LandCard::basic("Island", ManaColor::Blue).into(),
+            LandCard::basic("Swamp", ManaColor::Black).into(),
+            creature("Terror of the Peaks", "{3}{R}{R}", 5, 4, &["Dragon"], &["flying", "etb_damage_trigger"]).into(),
+            creature("Town Greeter", "{1}{G}", 1, 1, &["Human", "Citizen"], &["etb_mill_4_return_land"]).into(),
+            spider_man.into(),
+            kiora.into(),
+            ardyn.into(),
+            overlord.into(),
+            Card::Instant(cache_grab),
+        ])
+    }
 }
 
 #[cfg(test)]
@@ -71,5 +197,51 @@ mod tests {
         let result = db.get_card("Nonexistent Card");
         assert!(result.is_err());
     }
-}
 
+    #[test]
+    fn test_bad_path_error_names_the_path() {
+        assert_eq!(CardDatabase::from_file("./cards.json").unwrap().card_count(), CardDatabase::embedded().card_count());
+
+        let err = CardDatabase::from_file("no/such/cards.json").err().expect("missing file should fail");
+        assert!(matches!(err, CardDatabaseError::ReadError { .. }));
+        assert!(err.to_string().contains("'no/such/cards.json'"), "{}", err);
+    }
+
+    #[test]
+    fn test_cost_string_replaces_mana_cost_object() {
+        let db = CardDatabase::from_json(r#"[
+            { "card_type": "creature", "name": "Test Spider", "cost": "{2}{U}{B}",
+              "power": 4, "toughness": 4, "creature_types": [], "abilities": [] }
+        ]"#).expect("cost string should load");
+        let card = db.get_card("Test Spider").unwrap();
+        assert_eq!(card.mana_value(), 4);
+        let Card::Creature(creature) = card else { panic!("not a creature") };
+        assert_eq!((creature.base.mana_cost.blue, creature.base.mana_cost.black, creature.base.mana_cost.generic), (1, 1, 2));
+
+        let err = CardDatabase::from_json(r#"[
+            { "card_type": "sorcery", "name": "Bad Cost", "cost": "{2}{Z}", "abilities": [] }
+        ]"#).err().expect("bad symbol should fail");
+        assert!(matches!(err, CardDatabaseError::InvalidCost { ref card, .. } if card == "Bad Cost"), "{}", err);
+    }
+
+    #[test]
+    fn test_to_json_round_trips() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let json = db.to_json();
+
+        let reloaded = CardDatabase::from_json(&json).expect("dump should parse");
+        assert_eq!(reloaded.card_count(), db.card_count());
+        assert_eq!(reloaded.to_json(), json);
+    }
+
+    #[test]
+    fn test_fixture_matches_embedded_cards() {
+        let embedded = CardDatabase::embedded();
+        let fixture = CardDatabase::fixture();
+        assert_eq!(fixture.card_count(), 10);
+        for card in fixture.cards() {
+            let real = embedded.get_card(card.name()).unwrap();
+            assert_eq!(serde_json::to_value(card).unwrap(), serde_json::to_value(&real).unwrap(), "{}", card.name());
+        }
+    }
+}
@@ -2,5 +2,5 @@ pub mod database;
 pub mod types;
 
 pub use database::{CardDatabase, CardDatabaseError};
-pub use types::{Card, CardType, ColorFlags, CreatureCard, LandCard, LandSubtype, ManaCost, ManaColor};
+pub use types::{Card, CardType, ColorFlags, CreatureCard, DeckColorIdentity, LandCard, LandSubtype, ManaCost, ManaCostError, ManaColor};
 
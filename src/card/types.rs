@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
 
 /// Mana colors in Magic: The Gathering
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -45,7 +47,7 @@ impl ManaColor {
 }
 
 /// Bitflag representation of mana colors for fast operations
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct ColorFlags(pub u8);
 
 impl ColorFlags {
@@ -131,6 +133,51 @@ impl ColorFlags {
     }
 }
 
+/// Colors a deck actually plays: the colored pips of its spells that its lands can make
+/// without relying on any-color lands (Multiversal Passage, Starting Town). A color only
+/// those lands make - Terror's red here, which the deck reanimates rather than casts - is
+/// left out, so color fixing doesn't chase it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeckColorIdentity(pub ColorFlags);
+
+impl DeckColorIdentity {
+    /// Blue-black-green, the reanimator deck's colors
+    pub const UBG: DeckColorIdentity =
+        DeckColorIdentity(ColorFlags(ColorFlags::BLUE | ColorFlags::BLACK | ColorFlags::GREEN));
+
+    pub fn from_deck(deck: &[Card]) -> Self {
+        let mut spell_colors = ColorFlags::new();
+        let mut land_colors = ColorFlags::new();
+        for card in deck {
+            match card {
+                Card::Land(land) => {
+                    let colored = land.colors.iter().filter(|c| **c != ManaColor::Colorless).count();
+                    if colored < 5 {
+                        land.colors.iter().for_each(|c| land_colors.insert(*c));
+                    }
+                }
+                _ => spell_colors.0 |= card.mana_cost().colors().0,
+            }
+        }
+        DeckColorIdentity(ColorFlags(spell_colors.0 & land_colors.0 & !ColorFlags::COLORLESS))
+    }
+
+    pub fn contains(&self, color: ManaColor) -> bool {
+        self.0.contains(color)
+    }
+
+    /// Identity colors not in `available`
+    pub fn missing(&self, available: ColorFlags) -> ColorFlags {
+        ColorFlags(self.0 .0 & !available.0)
+    }
+}
+
+impl Default for DeckColorIdentity {
+    fn default() -> Self {
+        DeckColorIdentity::UBG
+    }
+}
+
 /// Mana cost for a card
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ManaCost {
@@ -150,10 +197,103 @@ pub struct ManaCost {
     pub generic: u32,
 }
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ManaCostError {
+    #[error("Mana cost '{0}' is not a sequence of {{...}} symbols")]
+    Malformed(String),
+    #[error("Unknown mana symbol '{{{symbol}}}' in '{cost}'")]
+    UnknownSymbol { cost: String, symbol: String },
+}
+
+impl ManaCost {
+    /// Parse a cost string such as `"{2}{U}{B}"`.
+    ///
+    /// The payment code only knows fixed pips, so some symbols are approximated:
+    /// hybrid `{B/G}` needs its first color, and Phyrexian `{B/P}` (payable with
+    /// 2 life) and snow `{S}` (no snow sources are modeled) count as generic.
+    pub fn parse(text: &str) -> Result<ManaCost, ManaCostError> {
+        let malformed = || ManaCostError::Malformed(text.to_string());
+        let mut cost = ManaCost::default();
+        let mut rest = text.trim();
+        if rest.is_empty() {
+            return Err(malformed());
+        }
+
+        while !rest.is_empty() {
+            let inner = rest.strip_prefix('{').ok_or_else(malformed)?;
+            let end = inner.find('}').ok_or_else(malformed)?;
+            let symbol = &inner[..end];
+            rest = &inner[end + 1..];
+
+            let unknown = || ManaCostError::UnknownSymbol { cost: text.to_string(), symbol: symbol.to_string() };
+            if let Ok(generic) = symbol.parse::<u32>() {
+                cost.generic += generic;
+                continue;
+            }
+            let pip = match symbol.split_once('/') {
+                // Phyrexian: pay the color or 2 life
+                Some((first, "P")) if Self::pip_color(first).is_some() => None,
+                // Hybrid: either color, approximated by the first
+                Some((first, second)) if Self::pip_color(second).is_some() => Some(first),
+                Some(_) => return Err(unknown()),
+                None if symbol == "S" => None,
+                None => Some(symbol),
+            };
+            match pip {
+                None => cost.generic += 1,
+                Some(pip) => match Self::pip_color(pip).ok_or_else(unknown)? {
+                    ManaColor::White => cost.white += 1,
+                    ManaColor::Blue => cost.blue += 1,
+                    ManaColor::Black => cost.black += 1,
+                    ManaColor::Red => cost.red += 1,
+                    ManaColor::Green => cost.green += 1,
+                    ManaColor::Colorless => cost.colorless += 1,
+                },
+            }
+        }
+        Ok(cost)
+    }
+
+    fn pip_color(symbol: &str) -> Option<ManaColor> {
+        match symbol {
+            "W" => Some(ManaColor::White),
+            "U" => Some(ManaColor::Blue),
+            "B" => Some(ManaColor::Black),
+            "R" => Some(ManaColor::Red),
+            "G" => Some(ManaColor::Green),
+            "C" => Some(ManaColor::Colorless),
+            _ => None,
+        }
+    }
+
+    /// Total mana value of the cost
+    pub fn mana_value(&self) -> u32 {
+        self.white + self.blue + self.black + self.red + self.green + self.colorless + self.generic
+    }
+
+    /// Colors with at least one pip in the cost
+    pub fn colors(&self) -> ColorFlags {
+        let mut colors = ColorFlags::new();
+        for (pips, color) in [
+            (self.white, ManaColor::White),
+            (self.blue, ManaColor::Blue),
+            (self.black, ManaColor::Black),
+            (self.red, ManaColor::Red),
+            (self.green, ManaColor::Green),
+            (self.colorless, ManaColor::Colorless),
+        ] {
+            if pips > 0 {
+                colors.insert(color);
+            }
+        }
+        colors
+    }
+}
+
 
 
 /// Card types in Magic
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CardType {
     Land,
@@ -197,6 +337,9 @@ pub struct LandCard {
     pub has_surveil: bool,
     #[serde(default)]
     pub surveil_amount: u32,
+    /// Life gained when it enters (gain-lands, which always enter tapped)
+    #[serde(default)]
+    pub etb_gain_life: u32,
 }
 
 /// Creature card
@@ -214,6 +357,19 @@ pub struct CreatureCard {
     pub impending_cost: Option<ManaCost>,
     #[serde(default)]
     pub impending_counters: Option<u32>,
+    /// False for creatures that should only ever enter via reanimation
+    #[serde(default = "default_true")]
+    pub hardcast_ok: bool,
+    /// Protection from the opponent's interaction: they need an extra counterspell to get
+    /// past ward, and can't touch a hexproof creature at all
+    #[serde(default)]
+    pub ward: bool,
+    #[serde(default)]
+    pub hexproof: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Spell card (Instant, Sorcery, Enchantment)
@@ -222,6 +378,12 @@ pub struct SpellCard {
     #[serde(flatten)]
     pub base: BaseCard,
     pub abilities: Vec<String>,
+    /// Flashback-style spells can be cast from the graveyard, then are exiled
+    #[serde(default)]
+    pub castable_from_graveyard: bool,
+    /// Delve: each graveyard card exiled while casting pays for one generic mana
+    #[serde(default)]
+    pub delve: bool,
 }
 
 /// Saga card
@@ -256,6 +418,17 @@ impl Card {
         }
     }
 
+    pub fn mana_cost(&self) -> &ManaCost {
+        match self {
+            Card::Land(c) => &c.base.mana_cost,
+            Card::Creature(c) => &c.base.mana_cost,
+            Card::Instant(c) => &c.base.mana_cost,
+            Card::Sorcery(c) => &c.base.mana_cost,
+            Card::Enchantment(c) => &c.base.mana_cost,
+            Card::Saga(c) => &c.base.mana_cost,
+        }
+    }
+
     pub fn mana_value(&self) -> u32 {
         match self {
             Card::Land(c) => c.base.mana_value,
@@ -266,5 +439,170 @@ impl Card {
             Card::Saga(c) => c.base.mana_value,
         }
     }
+
+    /// Ability strings for cards that have them (lands and sagas have none)
+    pub fn abilities(&self) -> &[String] {
+        match self {
+            Card::Creature(c) => &c.abilities,
+            Card::Instant(c) | Card::Sorcery(c) | Card::Enchantment(c) => &c.abilities,
+            Card::Land(_) | Card::Saga(_) => &[],
+        }
+    }
+
+    /// Whether the card may be cast from hand (reanimation-only creatures may not)
+    pub fn is_hardcastable(&self) -> bool {
+        match self {
+            Card::Creature(c) => c.hardcast_ok,
+            _ => true,
+        }
+    }
+
+    pub fn is_castable_from_graveyard(&self) -> bool {
+        match self {
+            Card::Instant(c) | Card::Sorcery(c) => c.castable_from_graveyard,
+            _ => false,
+        }
+    }
+
+    pub fn card_type(&self) -> CardType {
+        match self {
+            Card::Land(_) => CardType::Land,
+            Card::Creature(_) => CardType::Creature,
+            Card::Instant(_) => CardType::Instant,
+            Card::Sorcery(_) => CardType::Sorcery,
+            Card::Enchantment(_) => CardType::Enchantment,
+            Card::Saga(_) => CardType::Saga,
+        }
+    }
 }
 
+
+impl fmt::Display for CardType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardType::Land => write!(f, "Land"),
+            CardType::Creature => write!(f, "Creature"),
+            CardType::Instant => write!(f, "Instant"),
+            CardType::Sorcery => write!(f, "Sorcery"),
+            CardType::Enchantment => write!(f, "Enchantment"),
+            CardType::Saga => write!(f, "Saga"),
+        }
+    }
+}
+
+/// Name with a type tag, e.g. "Terror of the Peaks [Creature]" (use `name()` for the bare name)
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.name(), self.card_type())
+    }
+}
+
+impl From<LandCard> for Card {
+    fn from(land: LandCard) -> Self {
+        Card::Land(land)
+    }
+}
+
+impl From<CreatureCard> for Card {
+    fn from(creature: CreatureCard) -> Self {
+        Card::Creature(creature)
+    }
+}
+
+#[cfg(test)]
+impl BaseCard {
+    /// Free card with just a name, for tests
+    pub fn test(name: &str) -> Self {
+        BaseCard {
+            name: name.to_string(),
+            mana_cost: ManaCost::default(),
+            mana_value: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+impl CreatureCard {
+    /// Vanilla non-legendary creature, for tests
+    pub fn test(name: &str, power: u32, toughness: u32, creature_types: &[&str]) -> Self {
+        CreatureCard {
+            base: BaseCard::test(name),
+            power,
+            toughness,
+            is_legendary: false,
+            creature_types: creature_types.iter().map(|t| t.to_string()).collect(),
+            abilities: vec![],
+            impending_cost: None,
+            impending_counters: None,
+            hardcast_ok: true,
+            ward: false,
+            hexproof: false,
+        }
+    }
+}
+
+#[cfg(test)]
+impl LandCard {
+    /// Untapped basic land tapping for one color, for tests
+    pub fn basic(name: &str, color: ManaColor) -> Self {
+        LandCard {
+            base: BaseCard::test(name),
+            subtype: LandSubtype::Basic,
+            enters_tapped: false,
+            colors: vec![color],
+            has_surveil: false,
+            surveil_amount: 0,
+            etb_gain_life: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_display_includes_name_and_type() {
+        let card = Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &["Dragon"]));
+        assert_eq!(format!("{}", card), "Terror of the Peaks [Creature]");
+        assert_eq!(card.name(), "Terror of the Peaks");
+
+        let land = Card::from(LandCard::basic("Forest", ManaColor::Green));
+        assert_eq!(land.to_string(), "Forest [Land]");
+    }
+
+    fn parsed(text: &str) -> [u32; 7] {
+        let c = ManaCost::parse(text).unwrap();
+        [c.white, c.blue, c.black, c.red, c.green, c.colorless, c.generic]
+    }
+
+    #[test]
+    fn test_parse_generic_and_colored_pips() {
+        assert_eq!(parsed("{2}{U}{B}"), [0, 1, 1, 0, 0, 0, 2]);
+        assert_eq!(parsed("{10}{W}{R}{G}{C}"), [1, 0, 0, 1, 1, 1, 10]);
+        assert_eq!(ManaCost::parse("{2}{U}{B}").unwrap().mana_value(), 4);
+    }
+
+    #[test]
+    fn test_parse_hybrid_uses_first_color() {
+        assert_eq!(parsed("{1}{B/G}{B/G}"), [0, 0, 2, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_parse_phyrexian_and_snow_count_as_generic() {
+        assert_eq!(parsed("{B/P}{1}"), [0, 0, 0, 0, 0, 0, 2]);
+        assert_eq!(parsed("{S}{S}{G}"), [0, 0, 0, 0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_costs() {
+        assert_eq!(ManaCost::parse("2UB").unwrap_err(), ManaCostError::Malformed("2UB".to_string()));
+        assert_eq!(ManaCost::parse("{2}{U").unwrap_err(), ManaCostError::Malformed("{2}{U".to_string()));
+        assert_eq!(ManaCost::parse("").unwrap_err(), ManaCostError::Malformed(String::new()));
+        let err = ManaCost::parse("{2}{Q}").unwrap_err();
+        assert_eq!(err, ManaCostError::UnknownSymbol { cost: "{2}{Q}".to_string(), symbol: "Q".to_string() });
+        assert_eq!(err.to_string(), "Unknown mana symbol '{Q}' in '{2}{Q}'");
+        assert!(ManaCost::parse("{2/B}").is_err());
+        assert!(ManaCost::parse("{X/P}").is_err());
+    }
+}
@@ -1,7 +1,65 @@
-use crate::card::{Card, CardDatabase, CardType, LandSubtype, ManaColor, ManaCost};
-use crate::game::state::GameState;
+use crate::card::{Card, CardDatabase, CardType, ColorFlags, CreatureCard, LandSubtype, ManaColor};
+use crate::game::state::{GameState, SurveilPolicy};
 use crate::game::zones::{CounterType, Permanent};
 use crate::simulation::decisions::DecisionEngine;
+use crate::vlog;
+use std::collections::BTreeMap;
+
+/// Ability strings the engine acts on (a few, like Ardyn's, are resolved by card name)
+const HANDLED_ABILITIES: &[&str] = &[
+    "creature_mana_tap_bonus_green", "death_ping", "etb_damage_trigger", "etb_discard_tutor_creature",
+    "etb_earthbend_1", "etb_earthbend_2", "etb_mass_reanimate",
+    "etb_mill_4_return_artifact_creature_land", "etb_mill_4_return_land", "etb_or_attack_mill_4_return",
+    "etb_search_basic_land_tapped", "exile_graveyard_reanimate_token_5_5", "extra_land_drop", "gives_demons_haste",
+    "gives_demons_lifelink", "graveyard_leave_lifegain", "impending_5", "mill_4_return_permanent",
+    "mind_swap_copy", "on_second_spell_draw", "reanimate_top_creature", "return_best_from_graveyard_to_hand",
+    "search_land_or_creature_with_evidence", "starscourge",
+    "tap_for_green", "tap_plus_permanent_for_any_color",
+];
+
+/// Abilities we recognize but deliberately don't simulate in a goldfish game
+const UNMODELED_ABILITIES: &[&str] = &[
+    "cant_be_countered", "combat_counters", "etb_pump_all", "exile_from_graveyard", "flying",
+    "gives_demons_menace", "haste", "offspring", "pump_spell", "threshold_create_octopus", "trample",
+];
+
+/// Abilities taking a count suffix, e.g. "surveil_2"
+const COUNTED_ABILITY_PREFIXES: &[&str] = &["mill_opponent_", "surveil_", "upkeep_mill_"];
+
+/// Whether the engine recognizes an ability string (a typo would otherwise silently do nothing)
+pub fn is_known_ability(ability: &str) -> bool {
+    HANDLED_ABILITIES.contains(&ability)
+        || UNMODELED_ABILITIES.contains(&ability)
+        || COUNTED_ABILITY_PREFIXES.iter().any(|prefix| {
+            ability.strip_prefix(prefix).is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        || parse_loot_ability(ability).is_some()
+}
+
+/// Cards drawn and discarded by a loot ETB like "etb_draw_2_discard_2"
+pub fn parse_loot_ability(ability: &str) -> Option<(u32, u32)> {
+    let (draw, discard) = ability.strip_prefix("etb_draw_")?.split_once("_discard_")?;
+    Some((draw.parse().ok()?, discard.parse().ok()?))
+}
+
+/// The loot ETB on `card`, if it has one
+fn loot_ability(card: &Card) -> Option<(u32, u32)> {
+    card.abilities().iter().find_map(|ability| parse_loot_ability(ability))
+}
+
+/// Unrecognized ability strings in the database, each with the (sorted) cards that list it
+pub fn unknown_abilities(db: &CardDatabase) -> BTreeMap<String, Vec<String>> {
+    let mut unknown: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for card in db.cards() {
+        for ability in card.abilities().iter().filter(|a| !is_known_ability(a)) {
+            unknown.entry(ability.clone()).or_default().push(card.name().to_string());
+        }
+    }
+    for names in unknown.values_mut() {
+        names.sort();
+    }
+    unknown
+}
 
 /// Intern card names to static strings for zero-allocation copying
 /// This uses a static lookup for known card names
@@ -81,6 +139,10 @@ pub fn play_land(state: &mut GameState, card: &Card, verbose: bool) -> Result<()
         }
         _ => {} // Basic, Surveil use enters_tapped from card definition
     }
+    // Gain-lands always enter tapped
+    if land.etb_gain_life > 0 {
+        enters_tapped = true;
+    }
 
     let mut permanent = Permanent::new(card.clone(), state.turn);
     permanent.tapped = enters_tapped;
@@ -89,7 +151,7 @@ pub fn play_land(state: &mut GameState, card: &Card, verbose: bool) -> Result<()
     if land.base.name == "Cavern of Souls" {
         let chosen_type = choose_cavern_type(state);
         if verbose {
-            println!("    (Cavern set to: {})", chosen_type);
+            vlog!(3, "    (Cavern set to: {})", chosen_type);
         }
         permanent.chosen_type = Some(chosen_type);
     }
@@ -98,18 +160,31 @@ pub fn play_land(state: &mut GameState, card: &Card, verbose: bool) -> Result<()
     if land.base.name == "Multiversal Passage" {
         let chosen_color = choose_passage_color(state);
         if verbose {
-            println!("    (Passage set to: {})", chosen_color);
+            vlog!(3, "    (Passage set to: {})", chosen_color);
         }
         permanent.chosen_basic_type = Some(chosen_color);
     }
 
+    if land.etb_gain_life > 0 {
+        state.gain_life(land.etb_gain_life);
+        if verbose {
+            vlog!(2, "    Gained {} life (life: {})", land.etb_gain_life, state.life);
+        }
+    }
+
     // Handle surveil lands
     if land.has_surveil && land.surveil_amount > 0 {
-        resolve_surveil(state, land.surveil_amount as usize, verbose);
+        let policy = state.surveil_policy;
+        resolve_surveil(state, land.surveil_amount as usize, policy, verbose);
+    }
+
+    // A land that enters untapped adds to this turn's available mana
+    if !enters_tapped {
+        state.mana_available_this_turn += 1;
     }
 
     state.battlefield.add_permanent(permanent);
-    state.land_played_this_turn = true;
+    state.lands_played_this_turn += 1;
 
     Ok(())
 }
@@ -177,78 +252,42 @@ fn choose_cavern_type(state: &GameState) -> String {
     "Human".to_string()
 }
 
-/// Helper to get mana cost from any card
-fn card_mana_cost(card: &Card) -> &ManaCost {
-    match card {
-        Card::Land(c) => &c.base.mana_cost,
-        Card::Creature(c) => &c.base.mana_cost,
-        Card::Instant(c) => &c.base.mana_cost,
-        Card::Sorcery(c) => &c.base.mana_cost,
-        Card::Enchantment(c) => &c.base.mana_cost,
-        Card::Saga(c) => &c.base.mana_cost,
-    }
-}
+/// Colors the Passage fills when a spell in hand needs them, most wanted first
+const PASSAGE_NEED_ORDER: [ManaColor; 5] =
+    [ManaColor::Green, ManaColor::Blue, ManaColor::Black, ManaColor::White, ManaColor::Red];
+
+/// Colors the Passage fills when nothing in hand is short, blue first for Spider-Man and Kiora
+const PASSAGE_DEFAULT_ORDER: [ManaColor; 5] =
+    [ManaColor::Blue, ManaColor::Black, ManaColor::Green, ManaColor::White, ManaColor::Red];
 
 /// Choose basic land type for Multiversal Passage
-/// Priority: Fill missing colors for castable spells
+/// Priority: Fill missing colors for castable spells, then the deck's other missing colors
 fn choose_passage_color(state: &GameState) -> String {
     // Check what colors we currently have access to from untapped lands
-    let mut has_blue = false;
-    let mut has_black = false;
-    let mut has_green = false;
-
+    let mut available = ColorFlags::new();
     for perm in state.battlefield.permanents() {
         if perm.tapped {
             continue;
         }
         if let Card::Land(land) = &perm.card {
-            for color in &land.colors {
-                match color {
-                    ManaColor::Blue => has_blue = true,
-                    ManaColor::Black => has_black = true,
-                    ManaColor::Green => has_green = true,
-                    _ => {}
-                }
-            }
+            land.colors.iter().for_each(|c| available.insert(*c));
         }
     }
 
-    // Check what colors we need for spells in hand
-    let mut needs_blue = false;
-    let mut needs_black = false;
-    let mut needs_green = false;
-
+    // Check what colors we need for spells in hand (off-identity pips, like Terror's red, aren't chased)
+    let mut needed = ColorFlags::new();
     for card in state.hand.cards() {
-        let cost = card_mana_cost(card);
-        if cost.blue > 0 {
-            needs_blue = true;
-        }
-        if cost.black > 0 {
-            needs_black = true;
-        }
-        if cost.green > 0 {
-            needs_green = true;
-        }
-    }
-
-    // Priority: Fill missing colors for castable spells
-    if needs_green && !has_green {
-        return "G".to_string();
-    } else if needs_blue && !has_blue {
-        return "U".to_string();
-    } else if needs_black && !has_black {
-        return "B".to_string();
-    } else if !has_blue {
-        // Default: prioritize blue for Spider-Man and Kiora
-        return "U".to_string();
-    } else if !has_black {
-        return "B".to_string();
-    } else if !has_green {
-        return "G".to_string();
+        needed.0 |= card.mana_cost().colors().0;
     }
 
-    // Fallback
-    "U".to_string()
+    let missing = state.color_identity.missing(available);
+    let color = PASSAGE_NEED_ORDER
+        .iter()
+        .find(|c| needed.contains(**c) && missing.contains(**c))
+        .or_else(|| PASSAGE_DEFAULT_ORDER.iter().find(|c| missing.contains(**c)))
+        .copied()
+        .unwrap_or(ManaColor::Blue);
+    color.to_char().to_string()
 }
 
 /// Cast a creature, handling impending logic
@@ -271,17 +310,77 @@ pub fn cast_creature(
     }
 
     state.battlefield.add_permanent(permanent);
+    state.record_spell_cast();
+    apply_legend_rule(state, false);
     Ok(())
 }
 
-/// Cast a spell and resolve its effects
+/// Legend rule: when two legendary permanents share a name, keep the newest
+/// (last added) and put the others into the graveyard. Spider-Man copies are
+/// skipped since a copy's legendary status comes from the copied card.
+pub fn apply_legend_rule(state: &mut GameState, verbose: bool) {
+    let permanents = state.battlefield.permanents();
+    let is_legend = |p: &Permanent| p.is_copy_of.is_none() && matches!(&p.card, Card::Creature(c) if c.is_legendary);
+    let older: Vec<usize> = (0..permanents.len())
+        .filter(|&idx| {
+            is_legend(&permanents[idx])
+                && permanents[idx + 1..].iter().any(|p| is_legend(p) && p.card.name() == permanents[idx].card.name())
+        })
+        .collect();
+
+    let mut died = Vec::new();
+    for &idx in older.iter().rev() {
+        if let Some(perm) = state.battlefield.remove_permanent(idx) {
+            if verbose {
+                vlog!(2, "    Legend rule: {} goes to the graveyard", perm.card.name());
+            }
+            state.add_to_graveyard(perm.card.clone());
+            died.push(perm);
+        }
+    }
+    resolve_creature_deaths(state, &died, verbose);
+}
+
+/// "Whenever a creature dies" triggers for creatures just put into the graveyard from the
+/// battlefield: each `death_ping` permanent deals 1 damage to the opponent per death. The
+/// dead are passed in because a pinger dying alongside them still sees those deaths.
+pub fn resolve_creature_deaths(state: &mut GameState, died: &[Permanent], verbose: bool) {
+    let deaths = died.iter().filter(|p| matches!(p.card, Card::Creature(_))).count() as i32;
+    let is_pinger = |p: &Permanent| p.card.abilities().iter().any(|a| a == "death_ping");
+    let pingers = (state.battlefield.permanents().iter().filter(|p| is_pinger(p)).count()
+        + died.iter().filter(|p| is_pinger(p)).count()) as i32;
+    if deaths == 0 || pingers == 0 {
+        return;
+    }
+
+    state.opponent_life -= deaths * pingers;
+    if verbose {
+        vlog!(1, "  Death triggers dealt {} damage ({} creature(s) died, {} payoff(s))",
+            deaths * pingers, deaths, pingers);
+    }
+}
+
+/// Where an instant or sorcery goes once it resolves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellDestination {
+    /// The graveyard (exile under Rest in Peace)
+    Graveyard,
+    /// Exile, for a flashback-style spell cast from the graveyard
+    Exile,
+}
+
+/// Cast a spell and resolve its effects. A resolved instant or sorcery goes to `destination`.
 pub fn cast_spell(
     state: &mut GameState,
     card: &Card,
+    destination: SpellDestination,
     _db: &CardDatabase,
     verbose: bool,
     rng: &mut crate::rng::GameRng,
 ) -> Result<(), String> {
+    if !matches!(card, Card::Land(_) | Card::Creature(_)) {
+        state.record_spell_cast();
+    }
     match card {
         Card::Instant(spell) | Card::Sorcery(spell) => {
             // Process instant/sorcery abilities
@@ -297,7 +396,7 @@ pub fn cast_spell(
 
                         if verbose {
                             let names: Vec<&str> = milled_cards.iter().map(|c| c.name()).collect();
-                            println!("    Mill 4: {}", names.join(", "));
+                            vlog!(2, "    Mill 4: {}", names.join(", "));
                         }
 
                         // Filter to permanents only (not instant/sorcery)
@@ -317,27 +416,26 @@ pub fn cast_spell(
                         for card in milled_cards {
                             if Some(card.name().to_string()) == selected_name {
                                 if verbose {
-                                    println!("    -> Returned to hand: {}", card.name());
+                                    vlog!(2, "    -> Returned to hand: {}", card.name());
                                 }
                                 state.hand.add_card(card);
                                 // Clear selected_name so we only return one copy
                                 selected_name = None;
                             } else {
-                                state.graveyard.add_card(card);
+                                state.mill_to_graveyard(card);
                             }
                         }
                     }
                     "search_land_or_creature_with_evidence" => {
                         // Analyze the Pollen: evidence 8 (total mana value), search for creature/land
-                        // NEVER exile: Terror, Bringer (combo pieces), lands (MV 0, don't help)
-                        let never_exile = ["Terror of the Peaks", "Bringer of the Last Gift"];
-
-                        // Collect exilable cards with their indices and info
+                        // NEVER exile: Terror, Bringer (combo pieces), lands (MV 0, don't help),
+                        // or what Spider-Man needs for its Ardyn line
+                        let reserved = DecisionEngine::spider_man_copy_reserve(state.graveyard.cards());
                         let exilable_cards: Vec<(usize, String, i32, &Card)> = state.graveyard.cards()
                             .iter()
                             .enumerate()
-                            .filter(|(_, c)| {
-                                !matches!(c, Card::Land(_)) && !never_exile.contains(&c.name())
+                            .filter(|(i, c)| {
+                                !matches!(c, Card::Land(_)) && !DecisionEngine::is_combo_piece(c) && !reserved.contains(i)
                             })
                             .map(|(i, c)| (i, c.name().to_string(), c.mana_value() as i32, c))
                             .collect();
@@ -379,19 +477,15 @@ pub fn cast_spell(
                                 evidence_mv += mv;
                             }
 
-                            // Sort indices in reverse order so we can remove from highest to lowest
-                            to_exile.sort_by(|a, b| b.0.cmp(&a.0));
-
                             let exiled_names: Vec<String> = to_exile.iter().map(|(_, n)| n.clone()).collect();
+                            let exile_indices: Vec<usize> = to_exile.iter().map(|(idx, _)| *idx).collect();
 
-                            for (idx, _) in &to_exile {
-                                if let Some(card) = state.graveyard.remove_card(*idx) {
-                                    state.add_to_exile(card);
-                                }
+                            for card in state.graveyard.remove_cards(&exile_indices) {
+                                state.add_to_exile(card);
                             }
 
                             if verbose {
-                                println!("    Evidence collected ({} MV exiled: {})",
+                                vlog!(2, "    Evidence collected ({} MV exiled: {})",
                                     evidence_mv, exiled_names.join(", "));
                             }
 
@@ -402,49 +496,18 @@ pub fn cast_spell(
                             let has_bringer_in_gy = state.graveyard.cards().iter()
                                 .any(|c| c.name() == "Bringer of the Last Gift");
 
-                            let mut found_idx: Option<usize> = None;
+                            let library = &mut state.library;
+                            let found = (!has_spider_man && has_bringer_in_gy)
+                                .then(|| library.search_and_take(|c| c.name() == "Superior Spider-Man", rng))
+                                .flatten()
+                                .or_else(|| library.search_and_take(|c| c.name() == "Kiora, the Rising Tide", rng))
+                                .or_else(|| library.search_and_take(|c| matches!(c, Card::Land(_)), rng));
 
-                            // Search for Spider-Man if we need it
-                            if !has_spider_man && has_bringer_in_gy {
-                                for (i, card) in state.library.cards().iter().enumerate() {
-                                    if card.name() == "Superior Spider-Man" {
-                                        found_idx = Some(i);
-                                        break;
-                                    }
-                                }
-                            }
-
-                            // Search for Kiora
-                            if found_idx.is_none() {
-                                for (i, card) in state.library.cards().iter().enumerate() {
-                                    if card.name() == "Kiora, the Rising Tide" {
-                                        found_idx = Some(i);
-                                        break;
-                                    }
-                                }
-                            }
-
-                            // Search for a land
-                            if found_idx.is_none() {
-                                for (i, card) in state.library.cards().iter().enumerate() {
-                                    if matches!(card, Card::Land(_)) {
-                                        found_idx = Some(i);
-                                        break;
-                                    }
-                                }
-                            }
-
-                            if let Some(idx) = found_idx {
-                                let library_cards = state.library.cards_mut();
-                                if idx < library_cards.len() {
-                                    let target = library_cards.remove(idx);
-                                    if verbose {
-                                        println!("    -> Searched for: {}", target.name());
-                                    }
-                                    state.hand.add_card(target);
-                                    // Shuffle library with deterministic RNG
-                                    state.library.shuffle(rng);
+                            if let Some(target) = found {
+                                if verbose {
+                                    vlog!(2, "    -> Searched for: {}", target.name());
                                 }
+                                state.hand.add_card(target);
                             }
                         } else {
                             // No evidence - just search for basic land
@@ -452,43 +515,54 @@ pub fn cast_spell(
                                 .map(|c| c.mana_value())
                                 .sum();
                             if verbose {
-                                println!("    No evidence (graveyard MV: {}/8)", graveyard_mv);
+                                vlog!(2, "    No evidence (graveyard MV: {}/8)", graveyard_mv);
                             }
 
                             // Find a basic land in library
-                            let mut found_idx: Option<usize> = None;
-                            for (i, card) in state.library.cards().iter().enumerate() {
-                                if let Card::Land(land) = card {
-                                    if land.subtype == LandSubtype::Basic {
-                                        found_idx = Some(i);
-                                        break;
-                                    }
-                                }
-                            }
-
-                            if let Some(idx) = found_idx {
-                                let library_cards = state.library.cards_mut();
-                                if idx < library_cards.len() {
-                                    let target = library_cards.remove(idx);
-                                    if verbose {
-                                        println!("    -> Searched for basic land: {}", target.name());
-                                    }
-                                    state.hand.add_card(target);
-                                    // Shuffle library with deterministic RNG
-                                    state.library.shuffle(rng);
+                            let is_basic = |c: &Card| matches!(c, Card::Land(land) if land.subtype == LandSubtype::Basic);
+                            if let Some(target) = state.library.search_and_take(is_basic, rng) {
+                                if verbose {
+                                    vlog!(2, "    -> Searched for basic land: {}", target.name());
                                 }
+                                state.hand.add_card(target);
                             } else {
                                 if verbose {
-                                    println!("    -> No basic land found in library");
+                                    vlog!(2, "    -> No basic land found in library");
                                 }
                             }
                         }
                     }
+                    other if other.starts_with("mill_opponent_") => {
+                        resolve_mill_opponent(state, other, verbose);
+                    }
+                    "exile_graveyard_reanimate_token_5_5" => {
+                        resolve_exile_reanimate_token(state, &spell.base.name, verbose);
+                    }
+                    "reanimate_top_creature" => {
+                        resolve_reanimate_top_creature(state, rng, verbose);
+                    }
+                    "return_best_from_graveyard_to_hand" => {
+                        resolve_regrowth(state, verbose);
+                    }
+                    other if other.starts_with("surveil_") => {
+                        if let Some(count) = other.strip_prefix("surveil_").and_then(|n| n.parse::<usize>().ok()) {
+                            let policy = state.surveil_policy;
+                            resolve_surveil(state, count, policy, verbose);
+                        }
+                    }
                     _ => {}
                 }
             }
-            // Instant/Sorcery goes to graveyard after resolution
-            state.graveyard.add_card(card.clone());
+            // Instant/Sorcery goes to its destination after resolution
+            match destination {
+                SpellDestination::Graveyard => state.add_to_graveyard(card.clone()),
+                SpellDestination::Exile => {
+                    if verbose {
+                        vlog!(2, "    {} exiled (cast from graveyard)", card.name());
+                    }
+                    state.add_to_exile(card.clone());
+                }
+            }
             Ok(())
         }
         Card::Enchantment(spell) => {
@@ -509,27 +583,34 @@ pub fn cast_spell(
 
                         if verbose {
                             let names: Vec<&str> = milled_cards.iter().map(|c| c.name()).collect();
-                            println!("    Mill 4: {}", names.join(", "));
+                            vlog!(2, "    Mill 4: {}", names.join(", "));
                         }
 
                         // Choose which card to return (prioritize Spider-Man, then Kiora, then lands)
                         if let Some(idx) = DecisionEngine::choose_mill_return(&milled_cards, CardType::Creature) {
                             let card_to_return = milled_cards.remove(idx);
                             if verbose {
-                                println!("    -> Returned to hand: {}", card_to_return.name());
+                                vlog!(2, "    -> Returned to hand: {}", card_to_return.name());
                             }
                             state.hand.add_card(card_to_return);
                         }
 
                         // Rest go to graveyard
                         for card in milled_cards {
-                            state.graveyard.add_card(card);
+                            state.mill_to_graveyard(card);
                         }
                     }
                     "graveyard_leave_lifegain" => {
                         // Dredger's Insight: gain life when leaving graveyard
                         // This is a triggered ability, handled elsewhere
                     }
+                    "extra_land_drop" => {
+                        // Exploration-style: one more land this turn (recounted each turn)
+                        state.extra_land_drops += 1;
+                    }
+                    other if other.starts_with("mill_opponent_") => {
+                        resolve_mill_opponent(state, other, verbose);
+                    }
                     _ => {}
                 }
             }
@@ -543,7 +624,7 @@ pub fn cast_spell(
             state.battlefield.add_permanent(permanent);
             
             // Resolve Chapter I immediately
-            resolve_saga_chapter(state, &saga_name, 1, verbose);
+            resolve_saga_chapter(state, &saga_name, 1, rng, verbose);
             
             Ok(())
         }
@@ -551,6 +632,23 @@ pub fn cast_spell(
     }
 }
 
+/// Resolve "whenever you cast a spell" triggers; call after each spell is cast.
+/// Each `on_second_spell_draw` permanent draws a card as the second spell of the turn is cast.
+pub fn resolve_spell_cast_triggers(state: &mut GameState, verbose: bool) {
+    if state.spells_cast_this_turn != 2 {
+        return;
+    }
+    let drawers: Vec<String> = state.battlefield.permanents().iter()
+        .filter(|p| p.card.abilities().iter().any(|a| a == "on_second_spell_draw"))
+        .map(|p| p.card.name().to_string())
+        .collect();
+    for name in drawers {
+        if state.draw_card_from(&name) && verbose {
+            vlog!(2, "    {} (second spell this turn): drew a card", name);
+        }
+    }
+}
+
 /// Process enter-the-battlefield triggers for a creature (with verbose output)
 pub fn process_etb_triggers_verbose(
     state: &mut GameState,
@@ -559,6 +657,8 @@ pub fn process_etb_triggers_verbose(
     verbose: bool,
     rng: &mut crate::rng::GameRng,
 ) -> Result<(), String> {
+    let _timer = crate::profile::section(crate::profile::Section::Etb);
+
     // Extract abilities before borrowing permanent mutably
     let abilities = match &permanent.card {
         Card::Creature(c) => c.abilities.clone(),
@@ -578,7 +678,7 @@ pub fn process_etb_triggers_verbose(
 
                 if verbose {
                     let mill_names: Vec<String> = milled_cards.iter().map(|c| c.name().to_string()).collect();
-                    println!("    Mill 4: {}", mill_names.join(", "));
+                    vlog!(2, "    Mill 4: {}", mill_names.join(", "));
                 }
 
                 // Find the best land to return
@@ -616,17 +716,20 @@ pub fn process_etb_triggers_verbose(
                 for (idx, card) in milled_cards.into_iter().enumerate() {
                     if Some(idx) == best_land_idx {
                         if verbose {
-                            println!("    -> Returned to hand: {}", card.name());
+                            vlog!(2, "    -> Returned to hand: {}", card.name());
                         }
                         state.hand.add_card(card);
                     } else {
-                        state.graveyard.add_card(card);
+                        state.mill_to_graveyard(card);
                     }
                 }
             }
-            "etb_draw_2_discard_2" => {
-                // Kiora: draw 2, discard 2 - use the proper priority logic
-                resolve_kiora_etb(state, verbose);
+            other if other.starts_with("etb_draw_") => {
+                // Kiora and other looters: draw N, discard M - use the proper priority logic
+                if let Some((draw, discard)) = parse_loot_ability(other) {
+                    let name = permanent.card.name().to_string();
+                    resolve_loot(state, &name, draw, discard, verbose);
+                }
             }
             "etb_discard_tutor_creature" => {
                 // Formidable Speaker: may discard a card to tutor a creature
@@ -644,91 +747,14 @@ pub fn process_etb_triggers_verbose(
             "etb_mass_reanimate" => {
                 // Bringer of the Last Gift: mass reanimate
                 // Return all creature cards from graveyard to battlefield
-                let graveyard_cards = state.graveyard.cards().to_vec();
-                for card in graveyard_cards {
-                    if matches!(card, Card::Creature(_)) {
-                        let perm = Permanent::new(card.clone(), state.turn);
-                        state.battlefield.add_permanent(perm);
-                    }
+                for card in state.graveyard.take_creatures() {
+                    let perm = Permanent::new(card, state.turn);
+                    state.battlefield.add_permanent(perm);
                 }
-                // Clear graveyard of creatures
-                state.graveyard.clear_creatures();
             }
             "etb_or_attack_mill_4_return" => {
                 // Overlord of the Balemurk: mill 4, may return non-Avatar creature or land
-                // BUT we usually DON'T want to return creatures - we want them in graveyard for reanimate!
-                let milled = state.library.mill(4);
-
-                if verbose {
-                    let mill_names: Vec<String> = milled.iter().map(|c| c.name().to_string()).collect();
-                    println!("    Mill 4: {}", mill_names.join(", "));
-                }
-
-                // Check game state for selection logic
-                let has_bringer_in_gy = state.graveyard.cards().iter()
-                    .any(|c| c.name() == "Bringer of the Last Gift");
-                let has_spider_in_hand = state.hand.cards().iter()
-                    .any(|c| c.name() == "Superior Spider-Man");
-                let has_bringer_in_hand = state.hand.cards().iter()
-                    .any(|c| c.name() == "Bringer of the Last Gift");
-                let land_count = state.battlefield.permanents().iter()
-                    .filter(|p| matches!(p.card, Card::Land(_)))
-                    .count();
-
-                let mut selected_idx: Option<usize> = None;
-
-                // Priority 1: Spider-Man if we need it for the combo
-                if has_bringer_in_gy && !has_spider_in_hand {
-                    for (idx, card) in milled.iter().enumerate() {
-                        if card.name() == "Superior Spider-Man" {
-                            selected_idx = Some(idx);
-                            if verbose {
-                                println!("    Overlord returns Superior Spider-Man (combo piece!)");
-                            }
-                            break;
-                        }
-                    }
-                }
-
-                // Priority 2: Kiora if Bringer is stuck in hand
-                if selected_idx.is_none() && has_bringer_in_hand {
-                    for (idx, card) in milled.iter().enumerate() {
-                        if card.name() == "Kiora, the Rising Tide" {
-                            selected_idx = Some(idx);
-                            if verbose {
-                                println!("    Overlord returns Kiora (need to discard Bringer from hand)");
-                            }
-                            break;
-                        }
-                    }
-                }
-
-                // Priority 3: Town Greeter if early game
-                if selected_idx.is_none() && land_count < 4 {
-                    for (idx, card) in milled.iter().enumerate() {
-                        if card.name() == "Town Greeter" {
-                            selected_idx = Some(idx);
-                            if verbose {
-                                println!("    Overlord returns Town Greeter (cheap enabler)");
-                            }
-                            break;
-                        }
-                    }
-                }
-
-                // Otherwise: DON'T return anything! Leave creatures in graveyard for reanimation
-                if selected_idx.is_none() && verbose {
-                    println!("    Overlord returns nothing (keeping creatures for reanimate)");
-                }
-
-                // Add cards to graveyard or hand
-                for (idx, card) in milled.into_iter().enumerate() {
-                    if Some(idx) == selected_idx {
-                        state.hand.add_card(card);
-                    } else {
-                        state.graveyard.add_card(card);
-                    }
-                }
+                resolve_overlord_etb(state, verbose);
             }
             "mind_swap_copy" => {
                 // Superior Spider-Man: copy creature from graveyard
@@ -742,7 +768,7 @@ pub fn process_etb_triggers_verbose(
 
                 if let Some(idx) = bringer_idx {
                     if verbose {
-                        println!("    *** COMBO! Superior Spider-Man copies Bringer of the Last Gift! ***");
+                        vlog!(1, "    *** COMBO! Superior Spider-Man copies Bringer of the Last Gift! ***");
                     }
 
                     // Copy Bringer! (Spider-Man stays 4/4 but gains Bringer's types and triggers ETB)
@@ -763,14 +789,13 @@ pub fn process_etb_triggers_verbose(
                 let ardyn_idx = state.graveyard.cards().iter()
                     .position(|c| c.name() == "Ardyn, the Usurper");
 
-                let other_creatures_count = state.graveyard.cards().iter()
-                    .filter(|c| matches!(c, Card::Creature(_)) && c.name() != "Ardyn, the Usurper")
+                let other_creatures_count = state.graveyard.creatures()
+                    .filter(|c| c.base.name != "Ardyn, the Usurper")
                     .count();
 
-                if ardyn_idx.is_some() && other_creatures_count >= 1 {
-                    let idx = ardyn_idx.unwrap();
+                if let Some(idx) = ardyn_idx.filter(|_| other_creatures_count >= 1) {
                     if verbose {
-                        println!("    *** Spider-Man copies Ardyn, the Usurper! ({} creatures for Starscourge) ***", other_creatures_count);
+                        vlog!(1, "    *** Spider-Man copies Ardyn, the Usurper! ({} creatures for Starscourge) ***", other_creatures_count);
                     }
 
                     // Copy Ardyn (Spider-Man stays 4/4 but gains Demon type for haste and triggers Starscourge)
@@ -794,51 +819,38 @@ pub fn process_etb_triggers_verbose(
 
                 if spider_man_in_hand >= 1 {
                     // We have another Spider-Man - copy a mill creature to dig for Bringer
-                    // Priority: Overlord of the Balemurk > Kiora > Town Greeter
+                    // Priority: Overlord of the Balemurk > a looter (Kiora) > Town Greeter
                     let mill_creature = state.graveyard.cards().iter()
                         .position(|c| c.name() == "Overlord of the Balemurk")
                         .or_else(|| state.graveyard.cards().iter()
-                            .position(|c| c.name() == "Kiora, the Rising Tide"))
+                            .position(|c| matches!(c, Card::Creature(_)) && loot_ability(c).is_some()))
                         .or_else(|| state.graveyard.cards().iter()
                             .position(|c| c.name() == "Town Greeter"));
 
                     if let Some(idx) = mill_creature {
                         let creature_name = state.graveyard.cards()[idx].name().to_string();
                         if verbose {
-                            println!("    Spider-Man copies {} to dig for Bringer (have another Spider-Man in hand)", creature_name);
+                            vlog!(2, "    Spider-Man copies {} to dig for Bringer (have another Spider-Man in hand)", creature_name);
                         }
 
                         // Copy the mill creature (Spider-Man stays 4/4 but triggers the copied creature's ETB)
                         permanent.is_copy_of = Some(intern_card_name(&creature_name));
 
-                        // Exile the copied card
+                        // Exile the copied card, then trigger its ETB
                         if let Some(creature) = state.graveyard.remove_card(idx) {
-                            state.exile.add_card(creature);
-                        }
-
-                        // Trigger the copied creature's ETB
-                        match creature_name.as_str() {
-                            "Overlord of the Balemurk" => {
-                                // Mill 4, return a permanent
-                                resolve_overlord_etb(state, verbose);
-                            }
-                            "Kiora, the Rising Tide" => {
-                                // Draw 2, discard 2
-                                resolve_kiora_etb(state, verbose);
-                            }
-                            "Town Greeter" => {
-                                // Mill 4, return a land
-                                resolve_town_greeter_etb(state, verbose);
-                            }
-                            _ => {}
+                            state.exile.add_card(creature.clone());
+                            resolve_reanimated_etb(state, &creature, rng, verbose);
                         }
                     } else if verbose {
-                        println!("    Spider-Man enters as a 4/4 (no good copy target, but have another Spider-Man)");
+                        vlog!(2, "    Spider-Man enters as a 4/4 (no good copy target, but have another Spider-Man)");
                     }
                 } else if verbose {
-                    println!("    Spider-Man enters as a 4/4 (no good copy target)");
+                    vlog!(2, "    Spider-Man enters as a 4/4 (no good copy target)");
                 }
             }
+            other if other.starts_with("mill_opponent_") => {
+                resolve_mill_opponent(state, other, verbose);
+            }
             _ => {} // Other abilities handled elsewhere
         }
     }
@@ -873,7 +885,7 @@ pub fn resolve_bringer_etb(state: &mut GameState, rng: &mut crate::rng::GameRng,
         // Skip impending creatures (have time counters)
         if perm.get_counter(CounterType::Time) > 0 {
             if verbose {
-                println!("    Impending survives: {} ({} counters)",
+                vlog!(2, "    Impending survives: {} ({} counters)",
                     perm.card.name(), perm.get_counter(CounterType::Time));
             }
             continue;
@@ -885,59 +897,56 @@ pub fn resolve_bringer_etb(state: &mut GameState, rng: &mut crate::rng::GameRng,
         let names: Vec<String> = to_sacrifice.iter()
             .map(|&idx| state.battlefield.permanents()[idx].card.name().to_string())
             .collect();
-        println!("    Sacrifice: {}", names.join(", "));
+        vlog!(2, "    Sacrifice: {}", names.join(", "));
     }
 
     // Remove sacrificed creatures and add to graveyard (in reverse order to preserve indices)
+    let mut sacrificed = Vec::new();
     for &idx in to_sacrifice.iter().rev() {
         if let Some(perm) = state.battlefield.remove_permanent(idx) {
-            state.graveyard.add_card(perm.card);
+            state.add_to_graveyard(perm.card.clone());
+            sacrificed.push(perm);
         }
     }
+    resolve_creature_deaths(state, &sacrificed, verbose);
 
     // Step 2: Return ALL creature cards from graveyard to battlefield
-    let creatures_to_reanimate: Vec<Card> = state.graveyard.cards()
-        .iter()
-        .filter(|c| matches!(c, Card::Creature(_)))
-        .cloned()
-        .collect();
+    // (replacement effects exile some or all of them instead)
+    let creatures = state.graveyard.take_creatures();
+    // Spider-Man chooses what to copy as it enters, while these are still graveyard cards
+    let terror_in_graveyard = creatures.iter().any(|c| c.name() == "Terror of the Peaks");
+    let (exiled_instead, creatures_to_reanimate): (Vec<Card>, Vec<Card>) = creatures
+        .into_iter()
+        .partition(|c| state.replacement_effects.exiles_reanimated(c));
 
     if verbose && !creatures_to_reanimate.is_empty() {
         let names: Vec<String> = creatures_to_reanimate.iter()
             .map(|c| c.name().to_string())
             .collect();
-        println!("    Reanimate: {}", names.join(", "));
+        vlog!(2, "    Reanimate: {}", names.join(", "));
+    }
+    if verbose && !exiled_instead.is_empty() {
+        let names: Vec<&str> = exiled_instead.iter().map(|c| c.name()).collect();
+        vlog!(2, "    Exiled instead of reanimated: {}", names.join(", "));
     }
+    state.reanimated_count = Some(creatures_to_reanimate.len() as u32);
 
-    // Handle Superior Spider-Man's copy choice BEFORE clearing graveyard
     // When reanimated, Spider-Man should copy Terror of the Peaks (not Bringer)
     // because Bringer already triggered the reanimate
     let spider_man_being_reanimated = creatures_to_reanimate.iter()
         .any(|c| c.name() == "Superior Spider-Man");
 
     let spider_man_copy_target: Option<&'static str> = if spider_man_being_reanimated {
-        // Look for Terror of the Peaks in graveyard to copy
-        // Note: Terror might also be in creatures_to_reanimate, but Spider-Man
-        // copies from graveyard, so we check if Terror is there
-        let terror_in_graveyard = state.graveyard.cards().iter()
-            .any(|c| c.name() == "Terror of the Peaks");
-
+        // The copied Terror enters alongside it (or is exiled by a replacement effect),
+        // so there is no card left in the graveyard to exile for the copy
         if terror_in_graveyard {
             if verbose {
-                println!("    Superior Spider-Man (reanimated) copies Terror of the Peaks!");
-            }
-            // Remove Terror from graveyard and exile it
-            if let Some(idx) = state.graveyard.cards().iter()
-                .position(|c| c.name() == "Terror of the Peaks")
-            {
-                if let Some(terror) = state.graveyard.remove_card(idx) {
-                    state.exile.add_card(terror);
-                }
+                vlog!(2, "    Superior Spider-Man (reanimated) copies Terror of the Peaks!");
             }
             Some("Terror of the Peaks")
         } else {
             if verbose {
-                println!("    Superior Spider-Man (reanimated) enters as a 4/4 (no Terror to copy)");
+                vlog!(2, "    Superior Spider-Man (reanimated) enters as a 4/4 (no Terror to copy)");
             }
             None
         }
@@ -945,10 +954,12 @@ pub fn resolve_bringer_etb(state: &mut GameState, rng: &mut crate::rng::GameRng,
         None
     };
 
-    // Remove remaining creatures from graveyard
-    state.graveyard.clear_creatures();
+    for creature in exiled_instead {
+        state.exile.add_card(creature);
+    }
 
     // Add to battlefield
+    let terrors_before = count_terrors(state);
     for creature in &creatures_to_reanimate {
         let mut perm = Permanent::new(creature.clone(), state.turn);
 
@@ -961,81 +972,259 @@ pub fn resolve_bringer_etb(state: &mut GameState, rng: &mut crate::rng::GameRng,
 
         state.battlefield.add_permanent(perm);
     }
+    // Duplicate legends all entered (and still trigger Terror), but only one stays
+    apply_legend_rule(state, verbose);
 
     // Step 3: Resolve ETBs for reanimated creatures
     for creature in &creatures_to_reanimate {
-        match creature.name() {
-            "Kiora, the Rising Tide" => {
-                resolve_kiora_etb(state, verbose);
-            }
-            "Town Greeter" => {
-                resolve_town_greeter_etb(state, verbose);
-            }
-            "Overlord of the Balemurk" => {
-                resolve_overlord_etb(state, verbose);
-            }
-            "Formidable Speaker" => {
-                resolve_formidable_speaker_etb(state, rng, verbose);
-            }
-            _ => {}
-        }
+        resolve_reanimated_etb(state, creature, rng, verbose);
     }
 
     // Step 4: Resolve Terror triggers for each creature that entered
     // Note: If Spider-Man copied Terror, it now counts as a Terror for triggers!
-    resolve_terror_triggers(state, &creatures_to_reanimate, verbose);
+    resolve_terror_triggers(state, &creatures_to_reanimate, terrors_before, verbose);
 
 }
 
-/// Resolve Terror of the Peaks triggers for creatures entering the battlefield
-///
-/// EXACT LOGIC FROM TYPESCRIPT resolveTerrorTriggers:
-/// - Count Terrors on battlefield
-/// - Each Terror triggers for each OTHER creature entering (not itself)
-/// - Deal damage equal to creature's power for each Terror
-fn resolve_terror_triggers(state: &mut GameState, entering: &[Card], verbose: bool) {
-    // Count how many Terrors are on the battlefield
-    let terror_count = state.battlefield.permanents().iter()
+/// Resolve the ETB of a creature returned from the graveyard (or copied by Spider-Man)
+fn resolve_reanimated_etb(state: &mut GameState, creature: &Card, rng: &mut crate::rng::GameRng, verbose: bool) {
+    if let Some((draw, discard)) = loot_ability(creature) {
+        resolve_loot(state, creature.name(), draw, discard, verbose);
+        return;
+    }
+    match creature.name() {
+        "Town Greeter" => {
+            resolve_town_greeter_etb(state, verbose);
+        }
+        "Overlord of the Balemurk" => {
+            resolve_overlord_etb(state, verbose);
+        }
+        "Formidable Speaker" => {
+            resolve_formidable_speaker_etb(state, rng, verbose);
+        }
+        _ => {}
+    }
+}
+
+/// Return the topmost creature of the graveyard to the battlefield (graveyard order
+/// matters here, unlike `choose_reanimation_target`)
+fn resolve_reanimate_top_creature(state: &mut GameState, rng: &mut crate::rng::GameRng, verbose: bool) {
+    let Some(creature) = state.graveyard.take_top_creature() else {
+        if verbose {
+            vlog!(2, "    -> No creature in graveyard to return");
+        }
+        return;
+    };
+    if state.replacement_effects.exiles_reanimated(&creature) {
+        if verbose {
+            vlog!(2, "    -> {} is exiled instead of returning", creature.name());
+        }
+        state.exile.add_card(creature);
+        return;
+    }
+
+    if verbose {
+        vlog!(2, "    -> Returned {} from the top of the graveyard", creature.name());
+    }
+    let terrors_before = count_terrors(state);
+    state.battlefield.add_permanent(Permanent::new(creature.clone(), state.turn));
+    resolve_reanimated_etb(state, &creature, rng, verbose);
+    resolve_terror_triggers(state, std::slice::from_ref(&creature), terrors_before, verbose);
+}
+
+/// Terrors of the Peaks on the battlefield, including Spider-Man copies of one
+fn count_terrors(state: &GameState) -> u32 {
+    state.battlefield.permanents().iter()
         .filter(|p| {
             p.card.name() == "Terror of the Peaks"
-                || p.is_copy_of.as_deref() == Some("Terror of the Peaks")
+                || p.is_copy_of == Some("Terror of the Peaks")
+        })
+        .count() as u32
+}
+
+/// Terror damage for creatures entering together, shared by the resolver and
+/// `calculate_combo_damage` so the estimate can't drift from what actually happens.
+/// A non-Terror triggers every Terror on the battlefield afterwards (`terrors_after`);
+/// an entering Terror triggers only the Terrors that were already there (`terrors_before`),
+/// not itself or the Terrors entering alongside it.
+fn terror_trigger_damage<'a>(
+    entering: impl Iterator<Item = &'a CreatureCard>,
+    terrors_before: u32,
+    terrors_after: u32,
+) -> u32 {
+    entering
+        .map(|c| {
+            let terrors = if c.base.name == "Terror of the Peaks" { terrors_before } else { terrors_after };
+            c.power * terrors
         })
-        .count() as i32;
+        .sum()
+}
+
+/// Return the most useful graveyard card to hand, leaving Bringer and Terror for reanimation
+fn resolve_regrowth(state: &mut GameState, verbose: bool) {
+    let Some(card) = DecisionEngine::choose_return_to_hand(state.graveyard.cards())
+        .and_then(|idx| state.graveyard.remove_card(idx))
+    else {
+        if verbose {
+            vlog!(2, "    -> Nothing in graveyard worth returning");
+        }
+        return;
+    };
+
+    if verbose {
+        vlog!(2, "    -> Returned {} from graveyard to hand", card.name());
+    }
+    state.hand.add_card(card);
+}
 
+/// Resolve Terror of the Peaks triggers for creatures that just entered the battlefield
+/// (`terrors_before` is the Terror count before they were added)
+fn resolve_terror_triggers(state: &mut GameState, entering: &[Card], terrors_before: u32, verbose: bool) {
+    let terror_count = count_terrors(state);
     if terror_count == 0 {
         return;
     }
 
-    // Each Terror triggers for each OTHER creature entering
-    // (Terror doesn't trigger for itself)
-    let mut total_damage = 0i32;
+    let creatures = entering.iter().filter_map(|c| match c {
+        Card::Creature(c) => Some(c),
+        _ => None,
+    });
+    let total_damage = terror_trigger_damage(creatures, terrors_before, terror_count) as i32;
+
+    state.opponent_life -= total_damage;
+
+    if verbose && total_damage > 0 {
+        vlog!(1, "  Terror triggers dealt {} damage! ({} Terror(s), {} creatures entered)",
+            total_damage, terror_count, entering.len());
+    }
+}
 
-    for creature in entering {
-        if creature.name() == "Terror of the Peaks" {
-            continue; // Doesn't trigger for itself
+/// Pick the graveyard creature most worth exiling for a 5/5 token copy
+/// Priority: high power creatures, especially reanimation targets like Bringer
+/// (first one wins ties)
+pub fn choose_reanimation_target(state: &GameState) -> Option<String> {
+    best_token_target(state, |_| true)
+}
+
+/// Pick the creature for Ardyn's Starscourge. It triggers every combat, so unlike a
+/// one-shot spell it spares the only Bringer while anything else can be exiled: a token
+/// copy doesn't reanimate, and a later Spider-Man still needs Bringer to copy.
+pub fn choose_starscourge_target(state: &GameState) -> Option<String> {
+    let bringers = state.graveyard.creatures().filter(|c| c.base.name == "Bringer of the Last Gift").count();
+    if bringers == 1 {
+        if let Some(name) = best_token_target(state, |c| c.base.name != "Bringer of the Last Gift") {
+            return Some(name);
         }
+    }
+    choose_reanimation_target(state)
+}
+
+fn best_token_target(state: &GameState, allowed: impl Fn(&CreatureCard) -> bool) -> Option<String> {
+    state.graveyard.cards().iter()
+        .filter(|c| DecisionEngine::is_reanimation_target(c))
+        .filter_map(|c| match c {
+            Card::Creature(c) => Some(c),
+            _ => None,
+        })
+        .filter(|c| allowed(c))
+        .map(|c| {
+            // Prioritize Bringer of the Last Gift and Terror of the Peaks
+            let priority_boost = if c.base.name == "Bringer of the Last Gift" {
+                100
+            } else if c.base.name == "Terror of the Peaks" {
+                50
+            } else {
+                0
+            };
+            (c.power + priority_boost, c.base.name.clone())
+        })
+        .filter(|(effective_power, _)| *effective_power > 0)
+        .min_by_key(|(effective_power, _)| std::cmp::Reverse(*effective_power))
+        .map(|(_, name)| name)
+}
 
-        if let Card::Creature(c) = creature {
-            // Each Terror deals damage equal to the creature's power
-            total_damage += c.power as i32 * terror_count;
+/// Put a 5/5 token copy of an exiled creature onto the battlefield and
+/// resolve Terror of the Peaks triggers for it. `source` labels the token
+/// and the log lines (e.g. "Starscourge")
+pub fn create_reanimation_token(
+    state: &mut GameState,
+    copied_name: &str,
+    source: &str,
+    creature_types: Vec<String>,
+    verbose: bool,
+) {
+    let token = Card::Creature(crate::card::CreatureCard {
+        base: crate::card::types::BaseCard {
+            name: format!("{} ({} Token)", copied_name, source),
+            mana_cost: Default::default(),
+            mana_value: 0,
+        },
+        power: 5,
+        toughness: 5,
+        is_legendary: false,
+        creature_types,
+        abilities: vec![],
+        impending_cost: None,
+        impending_counters: None,
+        hardcast_ok: true,
+        ward: false,
+        hexproof: false,
+    });
+
+    let mut perm = Permanent::new(token, state.turn);
+    perm.is_copy_of = Some(intern_card_name(copied_name));
+
+    // Count Terrors BEFORE adding the token - Terror of the Peaks triggers on "another creature"
+    // so the token cannot trigger from itself entering
+    let terror_count = count_terrors(state) as i32;
+
+    state.battlefield.add_permanent(perm);
+
+    // Trigger Terror of the Peaks if on battlefield (for the 5/5 token entering)
+    if terror_count > 0 {
+        let terror_damage = 5 * terror_count; // Token is 5/5
+        state.opponent_life -= terror_damage;
+        if verbose {
+            vlog!(1, "[Terror] {} damage from {} token entering (5 power x {} Terror(s))",
+                terror_damage, source, terror_count);
         }
     }
+}
 
-    state.opponent_life -= total_damage;
+/// Exile the best graveyard creature and make a 5/5 token copy that keeps its creature types
+fn resolve_exile_reanimate_token(state: &mut GameState, spell_name: &str, verbose: bool) {
+    let Some(creature_name) = choose_reanimation_target(state) else {
+        if verbose {
+            vlog!(2, "    -> No creature in graveyard to exile");
+        }
+        return;
+    };
 
-    if verbose && total_damage > 0 {
-        println!("  Terror triggers dealt {} damage! ({} Terror(s), {} creatures entered)",
-            total_damage, terror_count, entering.len());
+    let Some(card) = state.graveyard.remove_card_by_name(&creature_name) else {
+        return;
+    };
+    let creature_types = match &card {
+        Card::Creature(c) => c.creature_types.clone(),
+        _ => vec![],
+    };
+    state.add_to_exile(card);
+
+    if verbose {
+        vlog!(2, "    -> Exiled {} from graveyard, created a 5/5 token copy", creature_name);
     }
+    create_reanimation_token(state, &creature_name, spell_name, creature_types, verbose);
 }
 
 /// Resolve surveil mechanic: look at top N cards and decide which go to graveyard
 ///
 /// EXACT LOGIC FROM TYPESCRIPT:
-/// - Check hasKioraInHand INSIDE the loop (it can change)
+/// - Ask `policy` INSIDE the loop (the default checks for Kiora in hand, which can change)
 /// - Only remove from library if putting in graveyard
 /// - If keeping on top, do NOT touch the library - leave card in place
-pub fn resolve_surveil(state: &mut GameState, count: usize, verbose: bool) {
+///
+/// Only touches library, hand and graveyard, so it serves both surveil lands
+/// and `surveil_N` spells
+pub fn resolve_surveil(state: &mut GameState, count: usize, policy: SurveilPolicy, verbose: bool) {
     let mut to_graveyard: Vec<String> = Vec::new();
     let mut to_top: Vec<String> = Vec::new();
 
@@ -1050,20 +1239,10 @@ pub fn resolve_surveil(state: &mut GameState, count: usize, verbose: bool) {
             let card_name = top_card.name().to_string();
 
             // Decision: keep on top or put in graveyard?
-            // Graveyard: Bringer, Terror, Overlord (want to reanimate these)
-            // Also put Kiora if we already have one (for reanimation value)
-            // Top: Spider-Man (MUST stay in hand!), lands, mill spells
-            let has_kiora_in_hand = state.hand.cards().iter().any(|c| c.name() == "Kiora, the Rising Tide");
-            let put_in_graveyard = card_name == "Bringer of the Last Gift"
-                || card_name == "Terror of the Peaks"
-                || card_name == "Overlord of the Balemurk"
-                || (card_name == "Kiora, the Rising Tide" && has_kiora_in_hand)
-                || card_name == "Town Greeter"; // Cheap 1/1, better to reanimate than draw
-
-            if put_in_graveyard {
+            if policy(top_card, state) {
                 // Remove from library and add to graveyard
                 if let Some(card) = state.library.draw() {
-                    state.graveyard.add_card(card);
+                    state.mill_to_graveyard(card);
                     to_graveyard.push(card_name);
                 }
             } else {
@@ -1075,10 +1254,10 @@ pub fn resolve_surveil(state: &mut GameState, count: usize, verbose: bool) {
 
     if verbose && (!to_graveyard.is_empty() || !to_top.is_empty()) {
         if !to_graveyard.is_empty() {
-            println!("    Surveil -> graveyard: {}", to_graveyard.join(", "));
+            vlog!(2, "    Surveil -> graveyard: {}", to_graveyard.join(", "));
         }
         if !to_top.is_empty() {
-            println!("    Surveil -> kept on top: {}", to_top.join(", "));
+            vlog!(2, "    Surveil -> kept on top: {}", to_top.join(", "));
         }
     }
 }
@@ -1090,12 +1269,10 @@ pub fn resolve_overlord_etb(state: &mut GameState, verbose: bool) {
 
     if verbose {
         let mill_names: Vec<String> = milled.iter().map(|c| c.name().to_string()).collect();
-        println!("    Mill 4: {}", mill_names.join(", "));
+        vlog!(2, "    Mill 4: {}", mill_names.join(", "));
     }
 
     // Check game state for selection logic
-    let has_bringer_in_gy = state.graveyard.cards().iter()
-        .any(|c| c.name() == "Bringer of the Last Gift");
     let has_spider_in_hand = state.hand.cards().iter()
         .any(|c| c.name() == "Superior Spider-Man");
     let has_bringer_in_hand = state.hand.cards().iter()
@@ -1106,13 +1283,13 @@ pub fn resolve_overlord_etb(state: &mut GameState, verbose: bool) {
 
     let mut selected_idx: Option<usize> = None;
 
-    // Priority 1: Spider-Man if we need it for the combo
-    if has_bringer_in_gy && !has_spider_in_hand {
+    // Priority 1: Spider-Man if we don't have one - never mill away the one we need
+    if !has_spider_in_hand {
         for (idx, card) in milled.iter().enumerate() {
             if card.name() == "Superior Spider-Man" {
                 selected_idx = Some(idx);
                 if verbose {
-                    println!("    Overlord returns Superior Spider-Man (combo piece!)");
+                    vlog!(2, "    Overlord returns Superior Spider-Man (combo piece!)");
                 }
                 break;
             }
@@ -1125,7 +1302,7 @@ pub fn resolve_overlord_etb(state: &mut GameState, verbose: bool) {
             if card.name() == "Kiora, the Rising Tide" {
                 selected_idx = Some(idx);
                 if verbose {
-                    println!("    Overlord returns Kiora (need to discard Bringer from hand)");
+                    vlog!(2, "    Overlord returns Kiora (need to discard Bringer from hand)");
                 }
                 break;
             }
@@ -1138,7 +1315,7 @@ pub fn resolve_overlord_etb(state: &mut GameState, verbose: bool) {
             if card.name() == "Town Greeter" {
                 selected_idx = Some(idx);
                 if verbose {
-                    println!("    Overlord returns Town Greeter (cheap enabler)");
+                    vlog!(2, "    Overlord returns Town Greeter (cheap enabler)");
                 }
                 break;
             }
@@ -1147,7 +1324,7 @@ pub fn resolve_overlord_etb(state: &mut GameState, verbose: bool) {
 
     // Otherwise: DON'T return anything! Leave creatures in graveyard for reanimation
     if selected_idx.is_none() && verbose {
-        println!("    Overlord returns nothing (keeping creatures for reanimate)");
+        vlog!(2, "    Overlord returns nothing (keeping creatures for reanimate)");
     }
 
     // Add cards to graveyard or hand
@@ -1155,7 +1332,7 @@ pub fn resolve_overlord_etb(state: &mut GameState, verbose: bool) {
         if Some(idx) == selected_idx {
             state.hand.add_card(card);
         } else {
-            state.graveyard.add_card(card);
+            state.mill_to_graveyard(card);
         }
     }
 }
@@ -1171,7 +1348,7 @@ pub fn resolve_town_greeter_etb(state: &mut GameState, verbose: bool) {
 
     if verbose {
         let mill_names: Vec<String> = milled_cards.iter().map(|c| c.name().to_string()).collect();
-        println!("    Mill 4: {}", mill_names.join(", "));
+        vlog!(2, "    Mill 4: {}", mill_names.join(", "));
     }
 
     // Find the best land to return
@@ -1209,31 +1386,36 @@ pub fn resolve_town_greeter_etb(state: &mut GameState, verbose: bool) {
     for (idx, card) in milled_cards.into_iter().enumerate() {
         if Some(idx) == best_land_idx {
             if verbose {
-                println!("    -> Returned to hand: {}", card.name());
+                vlog!(2, "    -> Returned to hand: {}", card.name());
             }
             state.hand.add_card(card);
         } else {
-            state.graveyard.add_card(card);
+            state.mill_to_graveyard(card);
         }
     }
 }
 
 /// Resolve Kiora's ETB ability: draw 2, discard 2
+pub fn resolve_kiora_etb(state: &mut GameState, verbose: bool) {
+    resolve_loot(state, "Kiora, the Rising Tide", 2, 2, verbose);
+}
+
+/// Draw `draw` cards, counted under `source`, then discard `discard`
 ///
-/// EXACT LOGIC FROM TYPESCRIPT:
-/// - Draw 2 cards first
-/// - Then discard 2 cards with 5-priority system:
+/// EXACT LOGIC FROM TYPESCRIPT (Kiora's draw 2, discard 2):
+/// - Draw the cards first
+/// - Then discard with 5-priority system:
 ///   1. Bringer of the Last Gift
 ///   2. Terror of the Peaks
 ///   3. Ardyn, the Usurper (8 mana - want to reanimate, not cast)
 ///   4. Excess lands (only if > 2 lands in hand)
 ///   5. Last card in hand
 /// - Each discard iteration searches for the best card independently
-pub fn resolve_kiora_etb(state: &mut GameState, verbose: bool) {
-    // Draw 2, discard 2
+pub fn resolve_loot(state: &mut GameState, source: &str, draw: u32, discard: u32, verbose: bool) {
     let hand_before = state.hand.size();
-    state.draw_card();
-    state.draw_card();
+    for _ in 0..draw {
+        state.draw_card_from(source);
+    }
 
     // Collect drawn cards for logging
     let drawn: Vec<String> = state.hand.cards()
@@ -1243,12 +1425,12 @@ pub fn resolve_kiora_etb(state: &mut GameState, verbose: bool) {
         .collect();
 
     if verbose {
-        println!("    Kiora ETB: drew {}", drawn.join(", "));
+        vlog!(2, "    {} ETB: drew {}", source, drawn.join(", "));
     }
 
-    // Discard 2 - prioritize discarding Bringer/Terror
+    // Discard - prioritize discarding Bringer/Terror
     let mut discarded: Vec<String> = Vec::new();
-    for _ in 0..2 {
+    for _ in 0..discard {
         if state.hand.size() == 0 {
             break;
         }
@@ -1300,14 +1482,14 @@ pub fn resolve_kiora_etb(state: &mut GameState, verbose: bool) {
         if let Some(idx) = to_discard_idx {
             if let Some(card) = state.hand.remove_card(idx) {
                 let card_name = card.name().to_string();
-                state.graveyard.add_card(card);
+                state.add_to_graveyard(card);
                 discarded.push(card_name);
             }
         }
     }
 
     if verbose {
-        println!("    Kiora ETB: discarded {}", discarded.join(", "));
+        vlog!(2, "    {} ETB: discarded {}", source, discarded.join(", "));
     }
 }
 
@@ -1550,31 +1732,25 @@ pub fn resolve_formidable_speaker_etb(state: &mut GameState, rng: &mut crate::rn
         if let Some(idx) = discard_idx {
             if let Some(card) = state.hand.remove_card(idx) {
                 let discarded_name = card.name().to_string();
-                state.graveyard.add_card(card);
+                state.add_to_graveyard(card);
 
                 // Search library for the tutor target
-                let tutor_idx = state.library.cards().iter()
-                    .position(|c| c.name() == tutor);
-
-                if let Some(lib_idx) = tutor_idx {
-                    // Remove card from library using cards_mut
-                    let tutored = state.library.cards_mut().remove(lib_idx);
+                if let Some(tutored) = state.library.search_and_take(|c| c.name() == tutor, rng) {
                     let tutored_name = tutored.name().to_string();
                     state.hand.add_card(tutored);
-                    state.library.shuffle(rng);
 
                     if verbose {
-                        println!("    Formidable Speaker ETB: discarded {}, tutored {}",
+                        vlog!(2, "    Formidable Speaker ETB: discarded {}, tutored {}",
                             discarded_name, tutored_name);
                     }
                 } else if verbose {
-                    println!("    Formidable Speaker ETB: discarded {}, but {} not found in library",
+                    vlog!(2, "    Formidable Speaker ETB: discarded {}, but {} not found in library",
                         discarded_name, tutor);
                 }
             }
         }
     } else if verbose {
-        println!("    Formidable Speaker ETB: chose not to discard");
+        vlog!(2, "    Formidable Speaker ETB: chose not to discard");
     }
 }
 
@@ -1582,7 +1758,7 @@ pub fn resolve_formidable_speaker_etb(state: &mut GameState, rng: &mut crate::rn
 fn has_ardyn_on_battlefield(state: &GameState) -> bool {
     state.battlefield.permanents().iter().any(|p| {
         p.card.name() == "Ardyn, the Usurper"
-            || p.is_copy_of.as_deref() == Some("Ardyn, the Usurper")
+            || p.is_copy_of == Some("Ardyn, the Usurper")
     })
 }
 
@@ -1594,137 +1770,120 @@ fn is_creature_demon(card: &Card) -> bool {
     }
 }
 
+/// Combo damage if cast now, split by source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComboDamage {
+    /// Terror of the Peaks triggers
+    pub terror: u32,
+    /// Combat damage this turn
+    pub combat: u32,
+    /// Terrors on the battlefield after the combo: each extra creature returned
+    /// would add its power times this much
+    pub terrors: u32,
+}
+
+impl ComboDamage {
+    pub fn total(&self) -> u32 {
+        self.terror + self.combat
+    }
+}
+
 /// Calculate total damage from the combo if cast now
+pub fn calculate_combo_damage(state: &GameState) -> u32 {
+    combo_damage_breakdown(state).total()
+}
+
+/// Combo damage if cast now, by source, following what `resolve_bringer_etb` and combat do
 ///
 /// Damage sources:
 /// 1. Terror triggers from creatures entering (both from battlefield and graveyard)
-/// 2. Combat damage from creatures already on battlefield (no summoning sickness)
-/// 3. Combat damage from Demons with haste (if Ardyn is on battlefield)
-pub fn calculate_combo_damage(state: &GameState) -> u32 {
-    // Check if Ardyn is on battlefield (Demons get haste)
-    let ardyn_on_battlefield = has_ardyn_on_battlefield(state);
+/// 2. Combat damage from creatures already on battlefield (no summoning sickness) that Bringer doesn't sacrifice
+/// 3. Combat damage from Demons with haste (if Ardyn is on battlefield after the combo)
+///
+/// Opponent blockers, death pings and Starscourge tokens aren't counted.
+pub fn combo_damage_breakdown(state: &GameState) -> ComboDamage {
+    let is_terror = |p: &Permanent| p.card.name() == "Terror of the Peaks" || p.is_copy_of == Some("Terror of the Peaks");
+
+    // Spider-Man only copies Bringer (exiling the copied card) with a Bringer to copy.
+    // Without one nothing is sacrificed or reanimated: only current attackers deal damage.
+    let Some(copied) = state.graveyard.cards().iter().position(|c| c.name() == "Bringer of the Last Gift") else {
+        let ardyn_on_battlefield = has_ardyn_on_battlefield(state);
+        let combat = state.battlefield.permanents().iter()
+            .filter(|p| p.get_counter(CounterType::Time) == 0)
+            .filter(|p| state.turn > p.turn_entered || (ardyn_on_battlefield && is_creature_demon(&p.card)))
+            .filter_map(|p| match &p.card {
+                Card::Creature(c) => Some(c.power),
+                _ => None,
+            })
+            .sum();
+        return ComboDamage { terror: 0, combat, terrors: count_terrors(state) };
+    };
 
-    // Creatures that would be reanimated from graveyard
-    let creatures_in_graveyard: Vec<&Card> = state
-        .graveyard
-        .cards()
-        .iter()
-        .filter(|c| matches!(c, Card::Creature(_)))
+    // Bringer sacrifices every other creature (impending ones aren't creatures yet); they
+    // return with the graveyard creatures unless Rest in Peace exiled them on the way
+    let (sacrificed, survivors): (Vec<&Permanent>, Vec<&Permanent>) = state.battlefield.permanents().iter()
+        .filter(|p| matches!(p.card, Card::Creature(_)))
+        .partition(|p| p.get_counter(CounterType::Time) == 0);
+    let mut returning: Vec<&Card> = state.graveyard.cards().iter().enumerate()
+        .filter(|&(idx, c)| idx != copied && matches!(c, Card::Creature(_)))
+        .map(|(_, c)| c)
         .collect();
-
-    // Spider-Man copies Bringer (power 6), and Bringer (the copied one) is exiled
-    const BRINGER_POWER: u32 = 6;
-
-    // Count Terrors that will be on battlefield after combo
-    let terrors_in_graveyard = creatures_in_graveyard
-        .iter()
-        .filter(|c| c.name() == "Terror of the Peaks")
-        .count() as u32;
-
-    let terrors_on_battlefield = state
-        .battlefield
-        .permanents()
-        .iter()
-        .filter(|p| {
-            p.card.name() == "Terror of the Peaks" || p.is_copy_of.as_deref() == Some("Terror of the Peaks")
-        })
-        .count() as u32;
-
-    // Calculate Terror trigger damage (IMMEDIATE)
-    // When Spider-Man enters as a copy of Bringer, creatures are reanimated
-    // Each Terror triggers for each creature entering (except itself)
-    //
-    // IMPORTANT: Spider-Man entering does NOT trigger Terrors because Terror is
-    // still in the graveyard at that point. Terrors only trigger for the creatures
-    // that enter simultaneously with them during the mass reanimate.
-
-    let mut terror_damage = 0u32;
-
-    // Terrors already on battlefield trigger for EACH creature entering (including Spider-Man)
-    if terrors_on_battlefield > 0 {
-        terror_damage += BRINGER_POWER * terrors_on_battlefield;
-        for creature in &creatures_in_graveyard {
-            if let Card::Creature(c) = creature {
-                terror_damage += c.power * terrors_on_battlefield;
-            }
-        }
+    if !state.replacement_effects.exiles_to_graveyard() {
+        returning.extend(sacrificed.iter().map(|p| &p.card));
     }
+    // A returning Spider-Man copies Terror if one was among the creatures (even one
+    // exiled instead of entering)
+    let terror_returns = returning.iter().any(|c| c.name() == "Terror of the Peaks");
+    returning.retain(|c| !state.replacement_effects.exiles_reanimated(c));
 
-    // Terrors from graveyard trigger for creatures entering AT THE SAME TIME (during mass reanimate)
-    // They DON'T trigger for Spider-Man (Spider-Man entered BEFORE the mass reanimate)
-    // They trigger for all other creatures entering simultaneously, but NOT for themselves
-    if terrors_in_graveyard > 0 {
-        // Each creature from graveyard triggers Terrors from graveyard (except Terror doesn't trigger for itself)
-        for creature in &creatures_in_graveyard {
-            if let Card::Creature(c) = creature {
-                if c.base.name == "Terror of the Peaks" {
-                    // A Terror entering triggers all OTHER Terrors (from graveyard only - battlefield Terrors already triggered above)
-                    terror_damage += c.power * (terrors_in_graveyard - 1);
-                } else {
-                    terror_damage += c.power * terrors_in_graveyard;
-                }
-            }
-        }
-    }
-
-    // Combat damage from creatures that can attack THIS turn (already on battlefield, no summoning sickness)
-    // These creatures will attack after we cast the combo in main phase 1
-    // Exception: Demons have haste if Ardyn is on battlefield
-    let current_combat_power: u32 = state
-        .battlefield
-        .permanents()
-        .iter()
-        .filter(|p| {
-            if !matches!(p.card, Card::Creature(_)) {
-                return false;
-            }
-            // Check for impending counters
-            if let Some(counter_count) = p.counters.get(&crate::game::zones::CounterType::Time) {
-                if *counter_count > 0 {
-                    return false;
-                }
-            }
-            // Check summoning sickness
-            let has_summoning_sickness = state.turn <= p.turn_entered;
-            if has_summoning_sickness {
-                // Demons get haste from Ardyn
-                if ardyn_on_battlefield && is_creature_demon(&p.card) {
-                    return true; // Can attack despite summoning sickness
-                }
-                return false;
-            }
-            true
-        })
-        .map(|p| {
-            if let Card::Creature(c) = &p.card {
-                c.power
-            } else {
-                0
-            }
-        })
-        .sum();
-
-    // Calculate combat damage from reanimated Demons (if Ardyn gives them haste)
-    let reanimated_demon_combat_power: u32 = if ardyn_on_battlefield {
-        // Bringer of the Last Gift is a Demon and would be reanimated
-        // Spider-Man (as a copy of Bringer) is NOT a Demon
-        // Any Demons in graveyard would get haste from Ardyn after reanimate
-        creatures_in_graveyard
-            .iter()
-            .filter(|c| is_creature_demon(c))
-            .map(|c| {
-                if let Card::Creature(creature) = c {
-                    creature.power
-                } else {
-                    0
-                }
+    // Terrors already there trigger for everything; the rest only for non-Terrors
+    // entering alongside them (see terror_trigger_damage)
+    //
+    // IMPORTANT: Spider-Man entering does NOT trigger Terrors: a Terror still in the
+    // graveyard isn't there yet, and one on the battlefield doesn't trigger for it either
+    // (casting resolves no Terror triggers) before Bringer sacrifices it.
+    let terrors_before = survivors.iter().filter(|p| is_terror(p)).count() as u32;
+    let terrors_after = terrors_before
+        + returning.iter()
+            .filter(|c| c.name() == "Terror of the Peaks" || (terror_returns && c.name() == "Superior Spider-Man"))
+            .count() as u32;
+    let returning_creatures = || returning.iter().filter_map(|c| match c {
+        Card::Creature(c) => Some(c),
+        _ => None,
+    });
+    let terror_damage = terror_trigger_damage(returning_creatures(), terrors_before, terrors_after);
+
+    // Everything that could attack this turn was sacrificed, so only Demons with Ardyn's
+    // haste attack: the cast Spider-Man (a Bringer copy) and returning Demons
+    let ardyn_after = survivors.iter().any(|p| p.card.name() == "Ardyn, the Usurper" || p.is_copy_of == Some("Ardyn, the Usurper"))
+        || returning.iter().any(|c| c.name() == "Ardyn, the Usurper");
+    let combat = if ardyn_after {
+        let spider_man_power = state.hand.cards().iter()
+            .find_map(|c| match c {
+                Card::Creature(c) if c.base.name == "Superior Spider-Man" => Some(c.power),
+                _ => None,
             })
-            .sum()
+            .unwrap_or(0);
+        spider_man_power
+            + returning_creatures()
+                .filter(|c| c.creature_types.iter().any(|t| t == "Demon"))
+                .map(|c| c.power)
+                .sum::<u32>()
     } else {
         0
     };
 
-    terror_damage + current_combat_power + reanimated_demon_combat_power
+    ComboDamage { terror: terror_damage, combat, terrors: terrors_after }
+}
+
+/// Resolve a `mill_opponent_N` ability: the opponent mills N cards
+fn resolve_mill_opponent(state: &mut GameState, ability: &str, verbose: bool) {
+    if let Some(count) = ability.strip_prefix("mill_opponent_").and_then(|n| n.parse::<u32>().ok()) {
+        let milled = state.mill_opponent(count);
+        if verbose {
+            vlog!(2, "    Opponent mills {} ({} cards left in library)", milled, state.opponent_library_size);
+        }
+    }
 }
 
 /// Check if casting the combo NOW would be lethal
@@ -1733,39 +1892,80 @@ pub fn is_combo_lethal(state: &GameState) -> bool {
     expected_damage >= state.opponent_life as u32
 }
 
+/// Combo damage sampled over the cards one more mill could put in the graveyard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComboDamageDistribution {
+    /// Combo damage in each sample
+    pub samples: Vec<u32>,
+}
+
+impl ComboDamageDistribution {
+    /// Fraction of samples dealing at least `life` damage
+    pub fn p_lethal(&self, life: i32) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let lethal = self.samples.iter().filter(|&&damage| damage as i64 >= life as i64).count();
+        lethal as f64 / self.samples.len() as f64
+    }
+}
+
+/// Combo damage after milling `mill` more cards, Monte-Carlo sampled over `samples`
+/// shuffles of the library (the state itself is left untouched)
+pub fn combo_damage_distribution(
+    state: &GameState,
+    mill: usize,
+    samples: usize,
+    rng: &mut crate::rng::GameRng,
+) -> ComboDamageDistribution {
+    let mut trial = state.clone();
+    let mut library = state.library.cards().to_vec();
+    let samples = (0..samples)
+        .map(|_| {
+            rng.shuffle(&mut library);
+            trial.graveyard = state.graveyard.clone();
+            for card in library.iter().take(mill) {
+                trial.graveyard.add_card(card.clone());
+            }
+            calculate_combo_damage(&trial)
+        })
+        .collect();
+    ComboDamageDistribution { samples }
+}
+
 
 /// Resolve a saga chapter ability
-pub fn resolve_saga_chapter(state: &mut GameState, saga_name: &str, chapter: u32, verbose: bool) {
+pub fn resolve_saga_chapter(state: &mut GameState, saga_name: &str, chapter: u32, rng: &mut crate::rng::GameRng, verbose: bool) {
     if saga_name == "Awaken the Honored Dead" {
         match chapter {
             1 => {
                 // Chapter I: Destroy target permanent (skip for goldfishing)
                 if verbose {
-                    println!("    Awaken Chapter I: Destroy target permanent (skipped - no opponent)");
+                    vlog!(2, "    Awaken Chapter I: Destroy target permanent (skipped - no opponent)");
                 }
             }
             2 => {
                 // Chapter II: Mill 3
                 if verbose {
-                    println!("    Awaken Chapter II: Mill 3");
+                    vlog!(2, "    Awaken Chapter II: Mill 3");
                 }
                 let mut milled = Vec::new();
                 for _ in 0..3 {
                     if let Some(card) = state.library.cards_mut().pop() {
                         if verbose {
-                            println!("      -> Milled: {}", card.name());
+                            vlog!(2, "      -> Milled: {}", card.name());
                         }
                         milled.push(card);
                     }
                 }
                 for card in milled {
-                    state.graveyard.add_card(card);
+                    state.mill_to_graveyard(card);
                 }
             }
             3 => {
                 // Chapter III: Return creature from graveyard OR search for creature/land
                 if verbose {
-                    println!("    Awaken Chapter III: Return creature or search");
+                    vlog!(2, "    Awaken Chapter III: Return creature or search");
                 }
                 
                 // Check if there's a creature in graveyard to return
@@ -1776,58 +1976,34 @@ pub fn resolve_saga_chapter(state: &mut GameState, saga_name: &str, chapter: u32
                     // Return creature to hand
                     if let Some(creature) = state.graveyard.remove_card(idx) {
                         if verbose {
-                            println!("      -> Returned {} from graveyard to hand", creature.name());
+                            vlog!(2, "      -> Returned {} from graveyard to hand", creature.name());
                         }
                         state.hand.add_card(creature);
                     }
                 } else {
                     // Search library for creature or land
                     if verbose {
-                        println!("      -> No creature in graveyard, searching library");
+                        vlog!(2, "      -> No creature in graveyard, searching library");
                     }
                     
                     // Priority: Spider-Man > Kiora > Formidable > Land
-                    let mut target_idx = None;
-                    
-                    // Look for Spider-Man first
-                    if target_idx.is_none() {
-                        target_idx = state.library.cards().iter()
-                            .position(|c| c.name() == "Superior Spider-Man");
-                    }
-                    
-                    // Then Kiora
-                    if target_idx.is_none() {
-                        target_idx = state.library.cards().iter()
-                            .position(|c| c.name() == "Kiora, the Rising Tide");
-                    }
-                    
-                    // Then Formidable Speaker
-                    if target_idx.is_none() {
-                        target_idx = state.library.cards().iter()
-                            .position(|c| c.name() == "Formidable Speaker");
-                    }
-                    
-                    // Finally any land
-                    if target_idx.is_none() {
-                        target_idx = state.library.cards().iter()
-                            .position(|c| matches!(c, Card::Land(_)));
-                    }
-                    
-                    if let Some(idx) = target_idx {
-                        let card = state.library.cards_mut().remove(idx);
+                    let library = &mut state.library;
+                    let found = library.search_and_take(|c| c.name() == "Superior Spider-Man", rng)
+                        .or_else(|| library.search_and_take(|c| c.name() == "Kiora, the Rising Tide", rng))
+                        .or_else(|| library.search_and_take(|c| c.name() == "Formidable Speaker", rng))
+                        .or_else(|| library.search_and_take(|c| matches!(c, Card::Land(_)), rng));
+
+                    if let Some(card) = found {
                         if verbose {
-                            println!("      -> Found and added to hand: {}", card.name());
+                            vlog!(2, "      -> Found and added to hand: {}", card.name());
                         }
                         state.hand.add_card(card);
-                        
-                        // Shuffle library (no RNG needed for goldfishing)
-                        // In a real game, would shuffle here
                     }
                 }
             }
             _ => {
                 if verbose {
-                    println!("    Unknown chapter {} for {}", chapter, saga_name);
+                    vlog!(2, "    Unknown chapter {} for {}", chapter, saga_name);
                 }
             }
         }
@@ -1837,8 +2013,8 @@ pub fn resolve_saga_chapter(state: &mut GameState, saga_name: &str, chapter: u32
 #[cfg(test)]
 mod combo_damage_tests {
     use super::*;
-    use crate::card::types::BaseCard;
-    use crate::card::{CreatureCard, ManaCost};
+    use crate::card::{CreatureCard, DeckColorIdentity};
+    use crate::game::state::ReplacementEffect;
     use crate::game::zones::Permanent;
 
     #[test]
@@ -1848,51 +2024,53 @@ mod combo_damage_tests {
         assert_eq!(damage, 0);
     }
 
+    #[test]
+    fn test_combo_damage_distribution_after_one_more_mill() {
+        let mut state = GameState::new();
+        state.opponent_life = 20;
+        state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        state.graveyard.add_card(Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &[])));
+        for _ in 0..30 {
+            state.library.add_card(Card::from(CreatureCard::test("Craterhoof Behemoth", 5, 5, &[])));
+        }
+        for _ in 0..10 {
+            state.library.add_card(Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &[])));
+        }
+        assert!(!is_combo_lethal(&state));
+
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let distribution = combo_damage_distribution(&state, 4, 200, &mut rng);
+        assert_eq!(distribution.samples.len(), 200);
+        assert!(distribution.p_lethal(state.opponent_life) > 0.9, "{}", distribution.p_lethal(state.opponent_life));
+        assert_eq!(state.graveyard.size(), 2, "the real graveyard is left untouched");
+
+        // Nothing left to mill: the estimate is the point estimate every time
+        state.library.clear();
+        let distribution = combo_damage_distribution(&state, 4, 10, &mut rng);
+        assert!(distribution.samples.iter().all(|&damage| damage == calculate_combo_damage(&state)));
+        assert_eq!(distribution.p_lethal(state.opponent_life), 0.0);
+    }
+
     #[test]
     fn test_calculate_combo_damage_with_terror_on_battlefield() {
         let mut state = GameState::new();
         state.opponent_life = 20;
 
         // Add Terror to battlefield
-        let terror = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Terror of the Peaks".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 4,
-            },
-            power: 3,
-            toughness: 3,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let terror = Card::from(CreatureCard::test("Terror of the Peaks", 3, 3, &[]));
 
         let permanent = Permanent::new(terror, 1);
         state.battlefield.add_permanent(permanent);
 
-        // Add Bringer to graveyard
-        let bringer = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 6,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
-
-        state.graveyard.add_card(bringer);
+        // Add two Bringers to graveyard: Spider-Man exiles the one it copies, the other returns
+        for _ in 0..2 {
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        }
 
         let damage = calculate_combo_damage(&state);
-        // Terror on battlefield triggers for Spider-Man (6 power) + Bringer (6 power) = 12 damage
-        assert_eq!(damage, 12);
+        // Bringer sacrifices the Terror, which returns and triggers for Bringer (6 power);
+        // Spider-Man entering triggers nothing
+        assert_eq!(damage, 6);
     }
 
     #[test]
@@ -1901,40 +2079,14 @@ mod combo_damage_tests {
         state.opponent_life = 20;
 
         // Add Terror to graveyard
-        let terror = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Terror of the Peaks".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 4,
-            },
-            power: 3,
-            toughness: 3,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let terror = Card::from(CreatureCard::test("Terror of the Peaks", 3, 3, &[]));
 
         state.graveyard.add_card(terror);
 
-        // Add Bringer to graveyard
-        let bringer = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 6,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
-
-        state.graveyard.add_card(bringer);
+        // Add two Bringers to graveyard: Spider-Man exiles the one it copies, the other returns
+        for _ in 0..2 {
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        }
 
         let damage = calculate_combo_damage(&state);
         // Terrors from graveyard trigger for creatures entering AT THE SAME TIME
@@ -1950,20 +2102,7 @@ mod combo_damage_tests {
         state.turn = 3; // Avoid summoning sickness
 
         // Add a creature to battlefield that can attack
-        let creature = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Test Creature".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 2,
-            },
-            power: 4,
-            toughness: 2,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let creature = Card::from(CreatureCard::test("Test Creature", 4, 2, &[]));
 
         let permanent = Permanent::new(creature, 1); // Entered on turn 1, now turn 3
         state.battlefield.add_permanent(permanent);
@@ -1971,6 +2110,10 @@ mod combo_damage_tests {
         let damage = calculate_combo_damage(&state);
         // Combat damage from creature with no summoning sickness
         assert_eq!(damage, 4);
+
+        // With a Bringer to copy, the attacker is sacrificed and returns summoning sick
+        state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        assert_eq!(calculate_combo_damage(&state), 0);
     }
 
     #[test]
@@ -1979,20 +2122,7 @@ mod combo_damage_tests {
         state.turn = 2;
 
         // Add a creature to battlefield that just entered (summoning sickness)
-        let creature = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Test Creature".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 2,
-            },
-            power: 4,
-            toughness: 2,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let creature = Card::from(CreatureCard::test("Test Creature", 4, 2, &[]));
 
         let permanent = Permanent::new(creature, 2); // Entered on turn 2, now turn 2
         state.battlefield.add_permanent(permanent);
@@ -2005,48 +2135,22 @@ mod combo_damage_tests {
     #[test]
     fn test_is_combo_lethal_true() {
         let mut state = GameState::new();
-        state.opponent_life = 10;
+        state.opponent_life = 6;
 
         // Add Terror to battlefield
-        let terror = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Terror of the Peaks".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 4,
-            },
-            power: 3,
-            toughness: 3,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let terror = Card::from(CreatureCard::test("Terror of the Peaks", 3, 3, &[]));
 
         let permanent = Permanent::new(terror, 1);
         state.battlefield.add_permanent(permanent);
 
-        // Add Bringer to graveyard
-        let bringer = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 6,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
-
-        state.graveyard.add_card(bringer);
+        // Add two Bringers to graveyard: Spider-Man exiles the one it copies, the other returns
+        for _ in 0..2 {
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        }
 
-        // Damage = 12 (Terror triggers for Spider-Man 6 + Bringer 6)
-        // Opponent life = 10
-        // 12 >= 10 = true
+        // Damage = 6 (the sacrificed Terror returns and triggers for Bringer 6)
+        // Opponent life = 6
+        // 6 >= 6 = true
         assert!(is_combo_lethal(&state));
     }
 
@@ -2056,45 +2160,19 @@ mod combo_damage_tests {
         state.opponent_life = 20;
 
         // Add Terror to battlefield
-        let terror = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Terror of the Peaks".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 4,
-            },
-            power: 3,
-            toughness: 3,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let terror = Card::from(CreatureCard::test("Terror of the Peaks", 3, 3, &[]));
 
         let permanent = Permanent::new(terror, 1);
         state.battlefield.add_permanent(permanent);
 
-        // Add Bringer to graveyard
-        let bringer = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 6,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
-
-        state.graveyard.add_card(bringer);
+        // Add two Bringers to graveyard: Spider-Man exiles the one it copies, the other returns
+        for _ in 0..2 {
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        }
 
-        // Damage = 12 (Terror triggers for Spider-Man 6 + Bringer 6)
+        // Damage = 6 (the sacrificed Terror returns and triggers for Bringer 6)
         // Opponent life = 20
-        // 12 >= 20 = false
+        // 6 >= 20 = false
         assert!(!is_combo_lethal(&state));
     }
 
@@ -2105,47 +2183,352 @@ mod combo_damage_tests {
 
         // Add 2 Terrors to battlefield
         for _ in 0..2 {
-            let terror = Card::Creature(CreatureCard {
-                base: BaseCard {
-                    name: "Terror of the Peaks".to_string(),
-                    mana_cost: ManaCost::default(),
-                    mana_value: 4,
-                },
-                power: 3,
-                toughness: 3,
-                is_legendary: false,
-                creature_types: vec![],
-                abilities: vec![],
-                impending_cost: None,
-                impending_counters: None,
-            });
+            let terror = Card::from(CreatureCard::test("Terror of the Peaks", 3, 3, &[]));
 
             let permanent = Permanent::new(terror, 1);
             state.battlefield.add_permanent(permanent);
         }
 
-        // Add Bringer to graveyard
-        let bringer = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: ManaCost::default(),
-                mana_value: 6,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
+        // Add two Bringers to graveyard: Spider-Man exiles the one it copies, the other returns
+        for _ in 0..2 {
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+        }
+
+        let damage = calculate_combo_damage(&state);
+        // Both Terrors are sacrificed and return with Bringer: each triggers for Bringer (6)
+        // 2 Terrors = 12 damage
+        assert_eq!(damage, 12);
+    }
+
+    #[test]
+    fn test_battlefield_and_graveyard_terrors_stack() {
+        let board = || {
+            let mut state = GameState::new();
+            state.turn = 4;
+            state.opponent_life = 40;
+            // Summoning sick, so no combat damage
+            let terror = Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &[]));
+            state.battlefield.add_permanent(Permanent::new(terror, 4));
+            state.graveyard.add_card(Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &[])));
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+            state.graveyard.add_card(Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &[])));
+            state
+        };
+
+        // Bringer sacrifices the battlefield Terror, which returns with the graveyard one:
+        // both trigger for Bringer (6 x 2) and neither is left to trigger for the other
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let mut state = board();
+        let mut spider_man = Permanent::new(Card::from(CreatureCard::test("Superior Spider-Man", 4, 4, &[])), 4);
+        spider_man.is_copy_of = Some("Bringer of the Last Gift");
+        state.battlefield.add_permanent(spider_man);
+        // Spider-Man exiles the Bringer it copies
+        let copied = state.graveyard.remove_card_by_name("Bringer of the Last Gift");
+        state.exile.add_card(copied.unwrap());
+        resolve_bringer_etb(&mut state, &mut rng, false);
+        assert_eq!(state.opponent_life, 40 - 12);
+        assert_eq!(count_terrors(&state), 2);
+
+        // A Terror returning on its own does trigger the two already there (5 x 2)
+        state.graveyard.add_card(Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &[])));
+        resolve_reanimate_top_creature(&mut state, &mut rng, false);
+        assert_eq!(state.opponent_life, 40 - 12 - 10);
+
+        // The estimate matches the resolver: Spider-Man entering triggers nothing
+        assert_eq!(calculate_combo_damage(&board()), 12);
+    }
+
+    #[test]
+    fn test_death_ping_triggers_for_each_bringer_sacrifice() {
+        use crate::card::types::{BaseCard, ManaCost, SpellCard};
+
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 4;
+        state.opponent_life = 20;
+        let payoff = Card::Enchantment(SpellCard {
+            base: BaseCard { name: "Test Blood Artist".to_string(), mana_cost: ManaCost::default(), mana_value: 2 },
+            abilities: vec!["death_ping".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
         });
+        state.battlefield.add_permanent(Permanent::new(payoff, 3));
+        for name in ["Grizzly Bears", "Hill Giant", "Gray Ogre"] {
+            state.battlefield.add_permanent(Permanent::new(Card::from(CreatureCard::test(name, 2, 2, &[])), 3));
+        }
+        let mut spider_man = Permanent::new(Card::from(CreatureCard::test("Superior Spider-Man", 4, 4, &[])), 4);
+        spider_man.is_copy_of = Some("Bringer of the Last Gift");
+        state.battlefield.add_permanent(spider_man);
+
+        // No Terror: the three sacrificed creatures' pings are the only damage
+        resolve_bringer_etb(&mut state, &mut rng, false);
+        assert_eq!(state.opponent_life, 17);
+        assert_eq!(state.reanimated_count, Some(3));
+    }
 
-        state.graveyard.add_card(bringer);
+    #[test]
+    fn test_bringer_records_reanimated_count() {
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 4;
+        for name in ["Grizzly Bears", "Hill Giant", "Gray Ogre", "Craw Wurm"] {
+            state.graveyard.add_card(Card::from(CreatureCard::test(name, 2, 2, &[])));
+        }
+        let mut spider_man = Permanent::new(Card::from(CreatureCard::test("Superior Spider-Man", 4, 4, &[])), 4);
+        spider_man.is_copy_of = Some("Bringer of the Last Gift");
+        state.battlefield.add_permanent(spider_man);
 
-        let damage = calculate_combo_damage(&state);
-        // Each Terror triggers for Spider-Man (6) + Bringer (6) = 12 per Terror
-        // 2 Terrors = 24 damage
-        assert_eq!(damage, 24);
+        resolve_bringer_etb(&mut state, &mut rng, false);
+        assert_eq!(state.reanimated_count, Some(4));
+
+        state.reset_turn_state();
+        assert_eq!(state.reanimated_count, None);
+    }
+
+    #[test]
+    fn test_exile_for_value_keeps_spider_man_a_lethal_copy() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let pollen = db.get_card("Analyze the Pollen").unwrap();
+        let names = |state: &GameState| -> Vec<String> {
+            state.graveyard.cards().iter().map(|c| c.name().to_string()).collect()
+        };
+
+        // Only Bringer's 8 mana value would reach the evidence: none is collected
+        let mut state = GameState::new();
+        for name in ["Bringer of the Last Gift", "Cache Grab", "Town Greeter"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+        cast_spell(&mut state, &pollen, SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+        assert_eq!(state.exile.size(), 0);
+        assert_eq!(names(&state)[..3], ["Bringer of the Last Gift", "Cache Grab", "Town Greeter"]);
+
+        // Without Bringer, Ardyn and its last Starscourge fodder stay too
+        let mut state = GameState::new();
+        for name in ["Ardyn, the Usurper", "Town Greeter", "Awaken the Honored Dead", "Dredger's Insight", "Cache Grab"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+        cast_spell(&mut state, &pollen, SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+        assert!(names(&state).starts_with(&["Ardyn, the Usurper".to_string(), "Town Greeter".to_string()]), "{:?}", names(&state));
+
+        // Starscourge takes the Town Greeter over the only Bringer; a one-shot token spell still takes Bringer
+        let mut state = GameState::new();
+        for name in ["Bringer of the Last Gift", "Town Greeter"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+        assert_eq!(choose_starscourge_target(&state).as_deref(), Some("Town Greeter"));
+        assert_eq!(choose_reanimation_target(&state).as_deref(), Some("Bringer of the Last Gift"));
+        state.graveyard.take_creatures();
+        state.graveyard.add_card(db.get_card("Bringer of the Last Gift").unwrap());
+        assert_eq!(choose_starscourge_target(&state).as_deref(), Some("Bringer of the Last Gift"));
+    }
+
+    #[test]
+    fn test_reanimation_exiled_by_replacement_deals_no_terror_damage() {
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 4;
+        state.replacement_effects.add(ReplacementEffect::ReanimationExiles);
+        state.graveyard.add_card(Card::from(CreatureCard::test("Terror of the Peaks", 5, 4, &["Dragon"])));
+        state.graveyard.add_card(Card::from(CreatureCard::test("Grizzly Bears", 2, 2, &[])));
+        let mut spider_man = Permanent::new(Card::from(CreatureCard::test("Superior Spider-Man", 4, 4, &[])), 4);
+        spider_man.is_copy_of = Some("Bringer of the Last Gift");
+        state.battlefield.add_permanent(spider_man);
+
+        resolve_bringer_etb(&mut state, &mut rng, false);
+        assert_eq!(state.reanimated_count, Some(0));
+        assert_eq!(state.battlefield.permanents().len(), 1);
+        assert_eq!(state.exile.size(), 2);
+        assert_eq!(state.graveyard.size(), 0);
+        assert_eq!(state.opponent_life, 20);
+    }
+
+    #[test]
+    fn test_no_mill_choice_bins_the_last_spider_man() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let spider_man_binned = |resolve: &dyn Fn(&mut GameState)| {
+            let mut state = GameState::new();
+            for name in ["Forest", "Superior Spider-Man", "Forest", "Forest", "Forest", "Forest"] {
+                state.library.add_card(db.get_card(name).unwrap());
+            }
+            resolve(&mut state);
+            let binned = state.graveyard.cards().iter().any(|c| c.name() == "Superior Spider-Man");
+            assert_eq!(binned, state.spider_men_binned > 0);
+            binned
+        };
+        let db = &db;
+        let cast = |name: &str| {
+            let card = db.get_card(name).unwrap();
+            move |state: &mut GameState| {
+                cast_spell(state, &card, SpellDestination::Graveyard, db, false, &mut crate::rng::GameRng::new(Some(1))).unwrap();
+            }
+        };
+
+        assert!(!spider_man_binned(&|state| resolve_overlord_etb(state, false)));
+        assert!(!spider_man_binned(&cast("Cache Grab")));
+        assert!(!spider_man_binned(&cast("Dredger's Insight")));
+        assert!(!spider_man_binned(&|state| resolve_surveil(state, 2, DecisionEngine::surveil_to_graveyard, false)));
+        // Town Greeter can only return a land, so it is the one path that has to bin it
+        assert!(spider_man_binned(&|state| resolve_town_greeter_etb(state, false)));
+    }
+
+    #[test]
+    fn test_passage_fills_white_for_a_wub_deck() {
+        let db = CardDatabase::from_json(r#"[
+            {"name": "Plains", "card_type": "land", "mana_value": 0, "subtype": "basic", "enters_tapped": false, "colors": ["W"]},
+            {"name": "Island", "card_type": "land", "mana_value": 0, "subtype": "basic", "enters_tapped": false, "colors": ["U"]},
+            {"name": "Swamp", "card_type": "land", "mana_value": 0, "subtype": "basic", "enters_tapped": false, "colors": ["B"]},
+            {"name": "Multiversal Passage", "card_type": "land", "mana_value": 0, "subtype": "utility", "enters_tapped": false,
+             "colors": ["W", "U", "B", "R", "G"]},
+            {"name": "Esper Charm", "card_type": "instant", "cost": "{W}{U}{B}", "abilities": []}
+        ]"#).unwrap();
+        let deck: Vec<Card> = ["Plains", "Island", "Swamp", "Multiversal Passage", "Esper Charm"]
+            .iter()
+            .map(|name| db.get_card(name).unwrap())
+            .collect();
+
+        let mut state = GameState::new();
+        state.color_identity = DeckColorIdentity::from_deck(&deck);
+        assert_eq!(state.color_identity.0 .0, ColorFlags::WHITE | ColorFlags::BLUE | ColorFlags::BLACK);
+        for name in ["Island", "Swamp"] {
+            state.battlefield.add_permanent(Permanent::new(db.get_card(name).unwrap(), 0));
+        }
+        state.hand.add_card(db.get_card("Esper Charm").unwrap());
+
+        play_land(&mut state, &db.get_card("Multiversal Passage").unwrap(), false).unwrap();
+        let passage = state.battlefield.permanents().last().unwrap();
+        assert_eq!(passage.chosen_basic_type.as_deref(), Some("W"));
+    }
+
+    #[test]
+    fn test_reanimator_deck_identity_leaves_out_reanimated_red() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        assert_eq!(DeckColorIdentity::from_deck(&deck), DeckColorIdentity::UBG);
     }
 }
 
+#[cfg(test)]
+mod ability_tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_abilities_flagged_once() {
+        let db = CardDatabase::from_json(r#"[
+            {"name": "Typo Dragon", "card_type": "creature", "mana_cost": {"red": 1}, "mana_value": 1,
+             "power": 1, "toughness": 1, "creature_types": [], "abilities": ["flying", "etb_mill4", "surveil_2"]},
+            {"name": "Typo Drake", "card_type": "creature", "mana_cost": {"blue": 1}, "mana_value": 1,
+             "power": 1, "toughness": 1, "creature_types": [], "abilities": ["etb_mill4", "surveil_x"]}
+        ]"#).unwrap();
+
+        let unknown = unknown_abilities(&db);
+        assert_eq!(unknown.len(), 2);
+        assert_eq!(unknown["etb_mill4"], vec!["Typo Dragon".to_string(), "Typo Drake".to_string()]);
+        assert_eq!(unknown["surveil_x"], vec!["Typo Drake".to_string()]);
+    }
+
+    #[test]
+    fn test_second_spell_of_the_turn_draws_a_card() {
+        let db = CardDatabase::from_json(r#"[
+            {"name": "Spell Scholar", "card_type": "creature", "cost": "{1}{U}",
+             "power": 1, "toughness": 1, "creature_types": [], "abilities": ["on_second_spell_draw"]},
+            {"name": "Opt", "card_type": "instant", "cost": "{U}", "abilities": []},
+            {"name": "Island", "card_type": "land", "mana_value": 0, "subtype": "basic", "enters_tapped": false, "colors": ["U"]}
+        ]"#).unwrap();
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let mut state = GameState::new();
+        for _ in 0..5 {
+            state.library.add_card(db.get_card("Island").unwrap());
+        }
+        state.battlefield.add_permanent(Permanent::new(db.get_card("Spell Scholar").unwrap(), 0));
+
+        // Only the second spell of the turn draws
+        let opt = db.get_card("Opt").unwrap();
+        for expected_hand in [0, 1, 1] {
+            cast_spell(&mut state, &opt, SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+            resolve_spell_cast_triggers(&mut state, false);
+            assert_eq!(state.hand.size(), expected_hand);
+        }
+        assert_eq!(state.spells_cast_this_turn, 3);
+
+        state.reset_turn_state();
+        assert_eq!(state.spells_cast_this_turn, 0);
+    }
+
+    #[test]
+    fn test_kiora_etb_records_two_draws_under_its_name() {
+        let db = CardDatabase::fixture();
+        let mut state = GameState::new();
+        for name in ["Forest", "Island", "Swamp", "Cache Grab"] {
+            state.library.add_card(db.get_card(name).unwrap());
+        }
+        state.draw_card();
+        resolve_kiora_etb(&mut state, false);
+
+        assert_eq!(state.draws_by_source.get("Kiora, the Rising Tide"), Some(&2));
+        assert_eq!(state.draws_by_source.get(crate::game::state::DRAW_STEP), Some(&1));
+        assert_eq!(state.draws_by_source.len(), 2);
+    }
+
+    #[test]
+    fn test_loot_three_discard_one_discards_bringer_first() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+        for name in ["Forest", "Island", "Bringer of the Last Gift"] {
+            state.library.add_card(db.get_card(name).unwrap());
+        }
+        state.hand.add_card(db.get_card("Cache Grab").unwrap());
+
+        resolve_loot(&mut state, "Test Looter", 3, 1, false);
+
+        assert_eq!(state.hand.size(), 3);
+        assert!(state.hand.cards().iter().all(|c| c.name() != "Bringer of the Last Gift"));
+        let graveyard: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(graveyard, ["Bringer of the Last Gift"]);
+        assert_eq!(state.draws_by_source.get("Test Looter"), Some(&3));
+
+        assert_eq!(parse_loot_ability("etb_draw_3_discard_2"), Some((3, 2)));
+        assert!(is_known_ability("etb_draw_3_discard_2"));
+        assert!(!is_known_ability("etb_draw_x_discard_2"));
+    }
+
+    #[test]
+    fn test_variant_looter_loots_when_reanimated_or_copied() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let looter = || {
+            let mut looter = CreatureCard::test("Test Looter", 2, 2, &[]);
+            looter.abilities = vec!["etb_draw_3_discard_2".to_string()];
+            Card::from(looter)
+        };
+        let board = || {
+            let mut state = GameState::new();
+            for _ in 0..6 {
+                state.library.add_card(db.get_card("Island").unwrap());
+            }
+            state.graveyard.add_card(looter());
+            state
+        };
+
+        // Returned from the graveyard
+        let mut state = board();
+        resolve_reanimate_top_creature(&mut state, &mut rng, false);
+        assert_eq!(state.draws_by_source.get("Test Looter"), Some(&3));
+        assert_eq!(state.hand.size(), 1);
+
+        // Copied by Spider-Man digging for Bringer (another Spider-Man in hand)
+        let mut state = board();
+        state.hand.add_card(db.get_card("Superior Spider-Man").unwrap());
+        let mut spider_man = Permanent::new(db.get_card("Superior Spider-Man").unwrap(), 1);
+        process_etb_triggers_verbose(&mut state, &mut spider_man, &db, false, &mut rng).unwrap();
+        assert_eq!(spider_man.is_copy_of, Some("Test Looter"));
+        assert_eq!(state.draws_by_source.get("Test Looter"), Some(&3));
+        assert_eq!(state.hand.size(), 2);
+    }
+
+    #[test]
+    fn test_real_card_database_has_no_unknown_abilities() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        assert!(unknown_abilities(&db).is_empty(), "{:?}", unknown_abilities(&db));
+    }
+}
@@ -0,0 +1,39 @@
+//! Structured game events, recorded into `GameState::events` when
+//! `GameState::record_events` is on
+
+use crate::card::ColorFlags;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// Mana development at the end of a turn
+    ManaSnapshot {
+        turn: u32,
+        /// Lands we could tap this turn (untapped at the start of the turn, or played untapped)
+        untapped_sources: u32,
+        /// Colors our lands can produce
+        colors: ColorFlags,
+    },
+    /// A step of resolving the opening hand, before turn 1
+    Mulligan(MulliganEvent),
+    /// The combo resolved: its estimated damage and the opponent's life before it and
+    /// after combat (see `combo_estimate_discrepancy`)
+    ComboResolved {
+        turn: u32,
+        estimate: u32,
+        life_before: i32,
+        life_after: i32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MulliganEvent {
+    /// Drew a hand (the Bo1 smoother draws two opening hands and keeps one)
+    Drew { cards: u32, lands: u32 },
+    /// Shuffled a hand of `cards` cards back to mulligan
+    Mulliganed { cards: u32 },
+    /// Scried after a mulligan, putting `bottomed` of the `cards` cards on the bottom
+    Scried { cards: u32, bottomed: u32 },
+    /// Kept the final hand
+    Kept { cards: u32, lands: u32 },
+}
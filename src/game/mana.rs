@@ -1,6 +1,7 @@
 use crate::card::{Card, ColorFlags, CreatureCard, ManaCost, ManaColor};
 use crate::game::state::GameState;
 use crate::game::zones::Permanent;
+use crate::simulation::decisions::DecisionEngine;
 
 /// Mana pool tracking each color and colorless mana
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +39,11 @@ impl ManaPool {
         }
     }
 
+    /// All mana in the pool, colored and colorless
+    pub fn total(&self) -> u32 {
+        self.white + self.blue + self.black + self.red + self.green + self.colorless
+    }
+
     /// Check if we can pay a mana cost
     pub fn can_pay(&self, cost: &ManaCost) -> bool {
         // Check colored requirements
@@ -61,21 +67,16 @@ impl ManaPool {
         }
 
         // Check if we have enough remaining for generic
-        let remaining = self.white - cost.white
-            + self.blue - cost.blue
-            + self.black - cost.black
-            + self.red - cost.red
-            + self.green - cost.green
-            + self.colorless - cost.colorless;
-
-        remaining >= cost.generic
+        let colored = cost.mana_value() - cost.generic;
+        self.total() - colored >= cost.generic
     }
 
-    /// Pay a mana cost from the pool
+    /// Pay a mana cost from the pool, or leave it untouched and return false if it can't be paid
     pub fn pay(&mut self, cost: &ManaCost) -> bool {
         if !self.can_pay(cost) {
             return false;
         }
+        let before = self.total();
 
         // Pay colored costs first
         self.white -= cost.white;
@@ -117,6 +118,9 @@ impl ManaPool {
             generic_remaining -= to_pay;
         }
 
+        // can_pay promised enough mana: every pip and all of the generic came out of the pool
+        debug_assert_eq!(generic_remaining, 0, "can_pay accepted {:?} but the generic wasn't covered", cost);
+        debug_assert_eq!(self.total() + cost.mana_value(), before);
         true
     }
 
@@ -137,6 +141,30 @@ impl Default for ManaPool {
     }
 }
 
+/// Lands whose colors depend on the board, the chosen type, or life total
+const CONDITIONAL_LANDS: &[&str] = &[
+    "Cavern of Souls",
+    "Wastewood Verge",
+    "Gloomlake Verge",
+    "Multiversal Passage",
+    "Starting Town",
+];
+
+/// Colors of a land that always taps for the same colors, cached on the permanent at entry
+/// (None for non-lands and conditional lands, which can_tap_for_mana re-evaluates)
+pub fn fixed_land_colors(card: &Card) -> Option<ColorFlags> {
+    match card {
+        Card::Land(land) if !CONDITIONAL_LANDS.contains(&land.base.name.as_str()) => {
+            let mut flags = ColorFlags::new();
+            for color in &land.colors {
+                flags.insert(*color);
+            }
+            Some(flags)
+        }
+        _ => None,
+    }
+}
+
 /// Get the colors a land can tap for as bitflags (no allocations)
 /// Handles special lands like Cavern of Souls, Verge lands, Starting Town
 #[inline]
@@ -148,6 +176,9 @@ pub fn can_tap_for_mana(
     if permanent.tapped {
         return ColorFlags::new();
     }
+    if let Some(colors) = permanent.fixed_colors {
+        return colors;
+    }
 
     let land = match &permanent.card {
         Card::Land(l) => l,
@@ -251,6 +282,24 @@ pub fn can_tap_for_mana(
     flags
 }
 
+/// Get available mana colors from battlefield lands as bitflags (no allocations)
+/// Uses can_tap_for_mana to correctly handle conditional lands like Verge lands
+/// With `untapped_only`, tapped lands are ignored (colors available right now)
+#[inline]
+pub fn get_available_colors(state: &GameState, untapped_only: bool) -> ColorFlags {
+    let mut colors = ColorFlags::new();
+
+    for permanent in state.battlefield.permanents() {
+        if matches!(permanent.card, Card::Land(_)) && !(untapped_only && permanent.tapped) {
+            // Use can_tap_for_mana to correctly evaluate conditional lands (Verge, Cavern, etc.)
+            let land_colors = can_tap_for_mana(permanent, state, None);
+            colors.0 |= land_colors.0;
+        }
+    }
+
+    colors
+}
+
 /// Check if a creature matches a Cavern of Souls chosen type
 fn creature_matches_cavern_type(creature: &CreatureCard, chosen_type: &str) -> bool {
     creature.creature_types.iter().any(|t| t == chosen_type)
@@ -356,6 +405,67 @@ pub fn can_afford_cost(
 }
 
 
+/// Graveyard cards a delve spell exiles to pay `cost`: the fewest that leave the rest
+/// affordable, expendable cards before reanimation targets and combo pieces last.
+/// `casting_from` is the spell's own graveyard index when it's cast from there, so it
+/// can't pay for itself. None if the spell can't be cast even after exiling up to its
+/// generic requirement.
+pub fn delve_exile_indices(cost: &ManaCost, state: &GameState, casting_from: Option<usize>) -> Option<Vec<usize>> {
+    let mut order: Vec<usize> = (0..state.graveyard.size()).filter(|&idx| Some(idx) != casting_from).collect();
+    order.sort_by_key(|&idx| {
+        let card = &state.graveyard.cards()[idx];
+        (DecisionEngine::is_combo_piece(card), DecisionEngine::is_reanimation_target(card))
+    });
+
+    let max_exiled = (cost.generic as usize).min(order.len());
+    (0..=max_exiled)
+        .find(|&exiled| {
+            let reduced = ManaCost { generic: cost.generic - exiled as u32, ..cost.clone() };
+            can_afford_cost(&reduced, state, None)
+        })
+        .map(|exiled| order[..exiled].to_vec())
+}
+
+/// Pay a delve spell's cost: exile the graveyard cards chosen by `delve_exile_indices`
+/// and tap lands for the rest. Returns true if successful.
+/// A spell cast from the graveyard must already have left it.
+pub fn pay_delve_cost(cost: &ManaCost, state: &mut GameState) -> bool {
+    let Some(indices) = delve_exile_indices(cost, state, None) else {
+        return false;
+    };
+    let reduced = ManaCost { generic: cost.generic - indices.len() as u32, ..cost.clone() };
+    if !tap_lands_for_cost(&reduced, state, None) {
+        return false;
+    }
+    for card in state.graveyard.remove_cards(&indices) {
+        state.exile.add_card(card);
+    }
+    true
+}
+
+/// Pay `cost` for casting `card`: delve spells through `pay_delve_cost`, everything else
+/// by tapping lands. Returns true if successful.
+pub fn pay_spell_cost(
+    card: &Card,
+    cost: &ManaCost,
+    state: &mut GameState,
+    for_creature: Option<&CreatureCard>,
+) -> bool {
+    match card {
+        Card::Instant(spell) | Card::Sorcery(spell) if spell.delve => pay_delve_cost(cost, state),
+        _ => tap_lands_for_cost(cost, state, for_creature),
+    }
+}
+
+/// Check if the spell at `gy_idx` in the graveyard can be cast from there (a delve
+/// spell can't exile itself to pay)
+pub fn can_cast_spell_from_graveyard(card: &Card, gy_idx: usize, state: &GameState) -> bool {
+    match card {
+        Card::Instant(c) | Card::Sorcery(c) if c.delve => delve_exile_indices(&c.base.mana_cost, state, Some(gy_idx)).is_some(),
+        _ => can_cast_spell(card, state),
+    }
+}
+
 /// Check if a spell can be cast with the current game state
 pub fn can_cast_spell(card: &Card, state: &GameState) -> bool {
     match card {
@@ -373,6 +483,7 @@ pub fn can_cast_spell(card: &Card, state: &GameState) -> bool {
             // Check regular mana cost
             can_afford_cost(&c.base.mana_cost, state, for_creature)
         }
+        Card::Instant(c) | Card::Sorcery(c) if c.delve => delve_exile_indices(&c.base.mana_cost, state, None).is_some(),
         Card::Instant(c) => can_afford_cost(&c.base.mana_cost, state, None),
         Card::Sorcery(c) => can_afford_cost(&c.base.mana_cost, state, None),
         Card::Enchantment(c) => can_afford_cost(&c.base.mana_cost, state, None),
@@ -380,6 +491,26 @@ pub fn can_cast_spell(card: &Card, state: &GameState) -> bool {
     }
 }
 
+/// Check if a spell could be cast if every untapped land made any color,
+/// i.e. whether it is only held back by colors rather than the amount of mana
+pub fn can_cast_spell_ignoring_colors(card: &Card, state: &GameState) -> bool {
+    let total = |cost: &ManaCost| {
+        cost.white + cost.blue + cost.black + cost.red + cost.green + cost.colorless + cost.generic
+    };
+    let (cheapest, for_creature) = match card {
+        Card::Land(_) => return false,
+        Card::Creature(c) => {
+            let regular = total(&c.base.mana_cost);
+            let cheapest = c.impending_cost.as_ref().map_or(regular, |cost| total(cost).min(regular));
+            (cheapest, Some(c))
+        }
+        Card::Instant(c) | Card::Sorcery(c) | Card::Enchantment(c) => (total(&c.base.mana_cost), None),
+        Card::Saga(c) => (total(&c.base.mana_cost), None),
+    };
+    let relaxed = ManaCost { generic: cheapest, ..Default::default() };
+    can_afford_cost(&relaxed, state, for_creature)
+}
+
 /// Tap lands to pay a mana cost. Returns true if successful.
 /// This is the key function that taps lands DURING casting, not before.
 /// 
@@ -485,6 +616,7 @@ pub fn tap_lands_for_cost(
     }
 
     // Now actually tap the lands and add mana to pool
+    state.mana_spent_this_turn += lands_to_tap.len() as u32;
     for (idx, color_char) in lands_to_tap {
         if let Some(perm) = state.battlefield.permanents_mut().get_mut(idx) {
             perm.tapped = true;
@@ -568,5 +700,80 @@ mod tests {
         assert_eq!(pool.white, 0);
         assert_eq!(pool.blue, 1);
     }
-}
 
+    #[test]
+    fn test_unaffordable_cost_leaves_pool_untouched() {
+        let mut pool = ManaPool::new();
+        pool.add_mana('B', 2);
+        pool.add_mana('G', 1);
+        pool.add_mana('C', 1);
+        assert_eq!(pool.total(), 4);
+
+        // Enough mana in total, but only one green
+        let cost = ManaCost { green: 2, generic: 1, ..Default::default() };
+        let before = pool;
+        assert!(!pool.pay(&cost));
+        assert_eq!(pool, before);
+
+        // Right colors, one short on generic
+        let cost = ManaCost { black: 1, green: 1, generic: 3, ..Default::default() };
+        assert!(!pool.pay(&cost));
+        assert_eq!(pool, before);
+
+        assert!(pool.pay(&ManaCost { black: 1, generic: 2, ..Default::default() }));
+        assert_eq!(pool.total(), 1);
+    }
+
+    #[test]
+    fn test_fixed_land_colors_cached_but_verge_reevaluated() {
+        let db = crate::card::CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+
+        let forest = Permanent::new(db.get_card("Forest").unwrap(), 1);
+        assert_eq!(forest.fixed_colors, Some(ColorFlags(ColorFlags::GREEN)));
+        assert_eq!(Some(can_tap_for_mana(&forest, &state, None)), forest.fixed_colors);
+
+        let verge = Permanent::new(db.get_card("Gloomlake Verge").unwrap(), 1);
+        assert_eq!(verge.fixed_colors, None);
+        state.battlefield.add_permanent(verge.clone());
+        assert_eq!(can_tap_for_mana(&verge, &state, None), ColorFlags(ColorFlags::BLUE));
+
+        state.battlefield.add_permanent(Permanent::new(db.get_card("Island").unwrap(), 1));
+        assert_eq!(can_tap_for_mana(&verge, &state, None), ColorFlags(ColorFlags::BLUE | ColorFlags::BLACK));
+    }
+
+    #[test]
+    fn test_delve_spell_castable_with_one_land_and_four_expendable_cards() {
+        let db = crate::card::CardDatabase::fixture();
+        let mut state = GameState::new();
+        state.battlefield.add_permanent(Permanent::new(db.get_card("Island").unwrap(), 1));
+
+        let Card::Instant(mut spell) = db.get_card("Cache Grab").unwrap() else {
+            panic!("Cache Grab should be an instant");
+        };
+        spell.base.mana_cost = ManaCost { blue: 1, generic: 4, ..Default::default() };
+        spell.delve = true;
+        let spell = Card::Instant(spell);
+
+        let terror = db.get_card("Terror of the Peaks").unwrap();
+        let greeter = db.get_card("Town Greeter").unwrap();
+        state.graveyard.add_card(terror);
+        state.graveyard.add_card(db.get_card("Forest").unwrap());
+        state.graveyard.add_card(db.get_card("Forest").unwrap());
+        assert!(!can_cast_spell(&spell, &state), "one graveyard card short");
+
+        state.graveyard.add_card(greeter);
+        state.graveyard.add_card(db.get_card("Forest").unwrap());
+        state.graveyard.add_card(db.get_card("Cache Grab").unwrap());
+        assert!(can_cast_spell(&spell, &state));
+        let Card::Instant(delve) = &spell else { unreachable!() };
+        assert!(pay_delve_cost(&delve.base.mana_cost, &mut state));
+
+        // The Island paid for the blue; the four expendable cards were exiled
+        assert!(state.battlefield.permanents()[0].tapped);
+        assert_eq!(state.exile.size(), 4);
+        assert!(state.exile.cards().iter().all(|c| !DecisionEngine::is_reanimation_target(c)));
+        let left: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(left, vec!["Terror of the Peaks", "Town Greeter"]);
+    }
+}
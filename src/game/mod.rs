@@ -3,3 +3,4 @@ pub mod state;
 pub mod zones;
 pub mod turns;
 pub mod cards;
+pub mod events;
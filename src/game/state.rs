@@ -1,19 +1,183 @@
-use crate::card::Card;
+use crate::card::{Card, CreatureCard, DeckColorIdentity};
 use crate::game::zones::{Battlefield, Exile, Graveyard, Hand, Library};
+use crate::game::events::GameEvent;
 use crate::game::mana::ManaPool;
+use crate::simulation::decisions::DecisionEngine;
+use crate::simulation::mulligan::DEFAULT_OPENING_HAND_SIZE;
+use crate::vlog;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
 
 /// Game phases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Phase {
     Untap,
+    Upkeep,
     Draw,
     Main1,
     Combat,
     Main2,
     End,
 }
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Untap => write!(f, "Untap"),
+            Phase::Upkeep => write!(f, "Upkeep"),
+            Phase::Draw => write!(f, "Draw"),
+            Phase::Main1 => write!(f, "Main 1"),
+            Phase::Combat => write!(f, "Combat"),
+            Phase::Main2 => write!(f, "Main 2"),
+            Phase::End => write!(f, "End"),
+        }
+    }
+}
+
+impl Phase {
+    /// The phase that follows this one in a turn (None after End; the next turn starts at Untap)
+    pub fn next(self) -> Option<Phase> {
+        match self {
+            Phase::Untap => Some(Phase::Upkeep),
+            Phase::Upkeep => Some(Phase::Draw),
+            Phase::Draw => Some(Phase::Main1),
+            Phase::Main1 => Some(Phase::Combat),
+            Phase::Combat => Some(Phase::Main2),
+            Phase::Main2 => Some(Phase::End),
+            Phase::End => None,
+        }
+    }
+}
+
+/// Which eligible creatures attack each combat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CombatPolicy {
+    /// Every eligible creature attacks
+    #[default]
+    AlphaStrike,
+    /// Only attack with creatures that survive a block from any known blocker
+    Value,
+}
+
+/// When Spider-Man is cast onto a Bringer graveyard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComboPolicy {
+    /// Hold the combo until it is estimated lethal
+    #[default]
+    WaitForLethal,
+    /// Fire the combo as soon as it is castable, lethal or not
+    FireWhenAssembled,
+}
+
+/// What the land drop is chosen for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LandPolicy {
+    /// Prefer a land that lets us cast a spell this turn
+    #[default]
+    EnableCast,
+    /// Prefer a land adding a color we don't have yet, even over enabling a cast
+    FixColorsFirst,
+}
+
+/// Surveil decision: true to put the card in the graveyard, false to keep it on top
+pub type SurveilPolicy = fn(&Card, &GameState) -> bool;
+
+/// A standing effect that replaces what happens to a card
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementEffect {
+    /// Cards that would be put into our graveyard are exiled instead (Rest in Peace)
+    GraveyardExiles,
+    /// Creatures that would enter the battlefield from the graveyard are exiled instead
+    ReanimationExiles,
+    /// The named creature is exiled instead of entering from the graveyard
+    CantBeReanimated(String),
+}
+
+/// Replacement effects in play for the current game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ReplacementEffects(pub Vec<ReplacementEffect>);
+
+impl ReplacementEffects {
+    pub fn add(&mut self, effect: ReplacementEffect) {
+        if !self.0.contains(&effect) {
+            self.0.push(effect);
+        }
+    }
+
+    /// Whether cards that would go to our graveyard are exiled instead
+    pub fn exiles_to_graveyard(&self) -> bool {
+        self.0.contains(&ReplacementEffect::GraveyardExiles)
+    }
+
+    /// Whether `card` is exiled instead of entering the battlefield from the graveyard
+    pub fn exiles_reanimated(&self, card: &Card) -> bool {
+        self.0.iter().any(|effect| match effect {
+            ReplacementEffect::GraveyardExiles => false,
+            ReplacementEffect::ReanimationExiles => true,
+            ReplacementEffect::CantBeReanimated(name) => card.name() == name,
+        })
+    }
+}
+
+/// A creature the opponent could block with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blocker {
+    pub power: u32,
+    pub toughness: u32,
+}
+
+/// How the opponent's graveyard hate works
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraveyardHateMode {
+    /// Exile our graveyard once (Bojuka Bog)
+    OneShot,
+    /// Exile our graveyard, then cards that would go there are exiled instead (Rest in Peace)
+    Continuous,
+}
+
+/// Opponent graveyard hate, starting at the beginning of our turn `turn`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct GraveyardHate {
+    pub turn: u32,
+    pub mode: GraveyardHateMode,
+}
+
+/// How hard the opponent attacks us, growing with their turn number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpponentCurve {
+    /// 3 damage every turn (we're dead on their 7th turn)
+    Flat,
+    /// 1 damage on their first turn, 2 more on each turn after (dead on their 5th)
+    Linear,
+    /// 1 damage on their first turn, doubling each turn after (dead on their 5th)
+    Exponential,
+}
+
+impl OpponentCurve {
+    /// Damage dealt on the opponent's `opponent_turn` (their first turn is 1)
+    pub fn damage(self, opponent_turn: u32) -> i32 {
+        if opponent_turn == 0 {
+            return 0;
+        }
+        match self {
+            OpponentCurve::Flat => 3,
+            OpponentCurve::Linear => 2 * opponent_turn as i32 - 1,
+            OpponentCurve::Exponential => 1 << (opponent_turn - 1).min(30),
+        }
+    }
+}
+
+/// Chance of being on the play when it isn't forced
+pub const DEFAULT_PLAY_PROBABILITY: f64 = 0.5;
 
+/// `draws_by_source` label for the normal draw step
+pub const DRAW_STEP: &str = "DrawStep";
 
+/// Opponent's library size after drawing a 7-card opening hand from 60
+pub const DEFAULT_OPPONENT_LIBRARY_SIZE: u32 = 53;
 
 /// Complete game state
 #[derive(Debug, Clone)]
@@ -29,12 +193,82 @@ pub struct GameState {
     pub turn: u32,
     pub phase: Phase,
     pub on_the_play: bool,
-    pub land_played_this_turn: bool,
+    pub lands_played_this_turn: u32,
+    /// Land plays allowed this turn beyond the first (`extra_land_drop` permanents)
+    pub extra_land_drops: u32,
+    /// Spells (creatures included) cast this turn
+    pub spells_cast_this_turn: u32,
+    /// Turn the first spell of the game was cast (None until one is)
+    pub first_spell_turn: Option<u32>,
 
-    // Life totals
+    // Mana accounting: lands tapped to pay costs vs untapped lands this turn
+    pub mana_spent_this_turn: u32,
+    pub mana_available_this_turn: u32,
+
+    // (estimated combo damage, opponent life) recorded when the combo is cast, checked after combat
+    pub combo_estimate: Option<(u32, i32)>,
+
+    // Creatures returned by this turn's Bringer mass-reanimate
+    pub reanimated_count: Option<u32>,
+
+    // Opponent's life when this turn started, to measure the damage dealt this turn
+    pub opponent_life_at_turn_start: i32,
+
+    // When to fire the combo, and the first turn we held a castable non-lethal combo
+    pub combo_policy: ComboPolicy,
+    pub held_combo_turn: Option<u32>,
+
+    // Which cards surveil puts in the graveyard
+    pub surveil_policy: SurveilPolicy,
+
+    // What the land drop is chosen for
+    pub land_policy: LandPolicy,
+
+    // Colors the deck plays, for color fixing (set from the deck at setup)
+    pub color_identity: DeckColorIdentity,
+
+    // Cards drawn this game (kept opening hand included), split into lands and spells
+    pub lands_drawn: u32,
+    pub spells_drawn: u32,
+
+    // Cards drawn this game (opening hand not included) by what drew them
+    pub draws_by_source: HashMap<String, u32>,
+
+    // Life totals, and all the life we've gained this game
     pub life: i32,
+    pub total_life_gained: u32,
     pub opponent_life: i32,
 
+    // Opponent's library (for mill wins)
+    pub opponent_library_size: u32,
+
+    // Tried to draw from our own empty library (a loss)
+    pub decked_out: bool,
+
+    // Superior Spider-Men milled or surveilled into the graveyard while none was in hand
+    pub spider_men_binned: u32,
+
+    // Opponent's known blockers, and how we attack into them
+    pub opponent_blockers: Vec<Blocker>,
+    pub combat_policy: CombatPolicy,
+
+    // Opponent's graveyard hate, and the replacement effects in play (Rest in Peace among them)
+    pub graveyard_hate: Option<GraveyardHate>,
+    pub replacement_effects: ReplacementEffects,
+
+    // Chance of being on the play, cards in the opening hand, and the last turn we can win on before the opponent kills us
+    pub play_probability: f64,
+    pub opening_hand_size: usize,
+    pub opponent_clock: Option<u32>,
+    pub opponent_curve: Option<OpponentCurve>,
+
+    // Counterspells the opponent has left for our Spider-Man casts
+    pub opponent_counters: u32,
+
+    // Structured event log (only filled when recording is on)
+    pub record_events: bool,
+    pub events: Vec<GameEvent>,
+
     // Mana
     pub mana_pool: ManaPool,
 }
@@ -50,26 +284,184 @@ impl GameState {
             turn: 0,
             phase: Phase::Untap,
             on_the_play: false,
-            land_played_this_turn: false,
+            lands_played_this_turn: 0,
+            extra_land_drops: 0,
+            spells_cast_this_turn: 0,
+            first_spell_turn: None,
+            mana_spent_this_turn: 0,
+            mana_available_this_turn: 0,
+            combo_estimate: None,
+            reanimated_count: None,
+            opponent_life_at_turn_start: 20,
+            combo_policy: ComboPolicy::default(),
+            land_policy: LandPolicy::default(),
+            color_identity: DeckColorIdentity::default(),
+            held_combo_turn: None,
+            surveil_policy: DecisionEngine::surveil_to_graveyard,
+            lands_drawn: 0,
+            spells_drawn: 0,
+            draws_by_source: HashMap::new(),
             life: 20,
+            total_life_gained: 0,
+            decked_out: false,
+            spider_men_binned: 0,
             opponent_life: 20,
+            opponent_library_size: DEFAULT_OPPONENT_LIBRARY_SIZE,
+            opponent_blockers: Vec::new(),
+            combat_policy: CombatPolicy::default(),
+            graveyard_hate: None,
+            replacement_effects: ReplacementEffects::default(),
+            play_probability: DEFAULT_PLAY_PROBABILITY,
+            opening_hand_size: DEFAULT_OPENING_HAND_SIZE,
+            opponent_clock: None,
+            opponent_curve: None,
+            opponent_counters: 0,
+            record_events: false,
+            events: Vec::new(),
             mana_pool: ManaPool::new(),
         }
     }
 
-    /// Draw a card from the library to hand
+    /// Move on to `phase`, which must directly follow the current one
+    pub fn advance_phase(&mut self, phase: Phase) -> Result<(), String> {
+        if self.phase.next() != Some(phase) {
+            return Err(format!("Can't go from {} to {}", self.phase, phase));
+        }
+        self.phase = phase;
+        Ok(())
+    }
+
+    /// Lands and sorcery-speed spells can only be played in a main phase
+    pub fn can_cast_sorcery_speed(&self) -> bool {
+        matches!(self.phase, Phase::Main1 | Phase::Main2)
+    }
+
+    /// Count a spell (creature included) cast this turn
+    pub fn record_spell_cast(&mut self) {
+        self.spells_cast_this_turn += 1;
+        self.first_spell_turn.get_or_insert(self.turn);
+    }
+
+    /// Whether a land drop remains this turn (one, plus any extra land drops)
+    pub fn can_play_land(&self) -> bool {
+        self.lands_played_this_turn < 1 + self.extra_land_drops
+    }
+
+    /// Draw a card from the library to hand for the draw step
     pub fn draw_card(&mut self) -> bool {
+        self.draw_card_from(DRAW_STEP)
+    }
+
+    /// Draw a card from the library to hand, counting it under `source` in `draws_by_source`
+    pub fn draw_card_from(&mut self, source: &str) -> bool {
         if let Some(card) = self.library.draw() {
+            self.count_drawn(&card);
             self.hand.add_card(card);
+            match self.draws_by_source.get_mut(source) {
+                Some(count) => *count += 1,
+                None => {
+                    self.draws_by_source.insert(source.to_string(), 1);
+                }
+            }
             true
         } else {
+            self.decked_out = true;
             false
         }
     }
 
-    /// Add a card to the graveyard
+    /// Counterspells the opponent spends countering `creature` (two to pay for ward), or
+    /// None if it's hexproof or they don't have enough left
+    pub fn counterspells_to_counter(&self, creature: &CreatureCard) -> Option<u32> {
+        if creature.hexproof {
+            return None;
+        }
+        let cost = if creature.ward { 2 } else { 1 };
+        (self.opponent_counters >= cost).then_some(cost)
+    }
+
+    /// We've lost: killed by the opponent, or drew from an empty library
+    pub fn has_lost(&self) -> bool {
+        self.life <= 0 || self.decked_out
+    }
+
+    /// Count a card drawn (or kept in the opening hand) as a land or a spell
+    pub fn count_drawn(&mut self, card: &Card) {
+        if matches!(card, Card::Land(_)) {
+            self.lands_drawn += 1;
+        } else {
+            self.spells_drawn += 1;
+        }
+    }
+
+    /// Add a card to the graveyard (exiled instead under a Rest in Peace effect)
     pub fn add_to_graveyard(&mut self, card: Card) {
-        self.graveyard.add_card(card);
+        if self.replacement_effects.exiles_to_graveyard() {
+            self.exile.add_card(card);
+        } else {
+            self.graveyard.add_card(card);
+        }
+    }
+
+    /// Put a card milled or surveilled from the library into the graveyard, counting a
+    /// Spider-Man binned while we have none in hand (the combo needs it)
+    pub fn mill_to_graveyard(&mut self, card: Card) {
+        if card.name() == "Superior Spider-Man" && !self.hand.cards().iter().any(|c| c.name() == "Superior Spider-Man") {
+            self.spider_men_binned += 1;
+        }
+        self.add_to_graveyard(card);
+    }
+
+    /// Apply the opponent's graveyard hate if it starts this turn
+    pub fn apply_graveyard_hate(&mut self, verbose: bool) {
+        let Some(hate) = self.graveyard_hate else {
+            return;
+        };
+        if hate.turn != self.turn {
+            return;
+        }
+
+        let exiled = self.graveyard.size();
+        for card in self.graveyard.take_all() {
+            self.exile.add_card(card);
+        }
+        if hate.mode == GraveyardHateMode::Continuous {
+            self.replacement_effects.add(ReplacementEffect::GraveyardExiles);
+        }
+        if verbose {
+            vlog!(1, "[Graveyard hate] Opponent exiles our graveyard ({} cards){}", exiled,
+                if self.replacement_effects.exiles_to_graveyard() { ", cards now go to exile instead" } else { "" });
+        }
+    }
+
+    /// The opponent attacks on their turn before our current one (they go first on the draw)
+    pub fn apply_opponent_attack(&mut self, verbose: bool) {
+        let Some(curve) = self.opponent_curve else {
+            return;
+        };
+        let opponent_turn = if self.on_the_play { self.turn - 1 } else { self.turn };
+        let damage = curve.damage(opponent_turn);
+        if damage == 0 {
+            return;
+        }
+
+        self.life -= damage;
+        if verbose {
+            vlog!(1, "[Opponent] Attacks for {} (life: {})", damage, self.life);
+        }
+    }
+
+    /// Gain `amount` life
+    pub fn gain_life(&mut self, amount: u32) {
+        self.life += amount as i32;
+        self.total_life_gained += amount;
+    }
+
+    /// Mill the opponent, returning how many cards were actually milled
+    pub fn mill_opponent(&mut self, count: u32) -> u32 {
+        let milled = count.min(self.opponent_library_size);
+        self.opponent_library_size -= milled;
+        milled
     }
 
     /// Add a card to exile
@@ -86,23 +478,79 @@ impl GameState {
 
     /// Reset turn state
     pub fn reset_turn_state(&mut self) {
-        self.land_played_this_turn = false;
+        self.lands_played_this_turn = 0;
+        self.extra_land_drops = self.battlefield.permanents().iter()
+            .filter(|p| p.card.abilities().iter().any(|a| a == "extra_land_drop"))
+            .count() as u32;
+        self.spells_cast_this_turn = 0;
+        self.mana_spent_this_turn = 0;
+        self.mana_available_this_turn = 0;
+        self.combo_estimate = None;
+        self.reanimated_count = None;
+        self.opponent_life_at_turn_start = self.opponent_life;
         self.mana_pool.clear();
     }
 
-    /// Reset game state for reuse without reallocating
+    /// Damage dealt to the opponent so far this turn
+    pub fn damage_dealt_this_turn(&self) -> u32 {
+        (self.opponent_life_at_turn_start - self.opponent_life).max(0) as u32
+    }
+
+    /// Count untapped lands as this turn's available mana (call after untapping)
+    pub fn record_available_mana(&mut self) {
+        self.mana_available_this_turn = self
+            .battlefield
+            .permanents()
+            .iter()
+            .filter(|p| !p.tapped && matches!(p.card, Card::Land(_)))
+            .count() as u32;
+    }
+
+    /// Fraction of this turn's available mana that was spent (None with no lands)
+    pub fn mana_utilization_this_turn(&self) -> Option<f64> {
+        if self.mana_available_this_turn == 0 {
+            None
+        } else {
+            Some(self.mana_spent_this_turn as f64 / self.mana_available_this_turn as f64)
+        }
+    }
+
+    /// Reset game state for reuse without reallocating (settings - combat, combo, surveil
+    /// and land policies, graveyard hate, play probability, opponent clock and curve, and event recording - are kept).
+    /// Replacement effects are cleared, since graveyard hate adds one mid-game; `run_game_with_config`
+    /// puts back `GameConfig::replacement_effects`.
     pub fn reset(&mut self) {
-        self.library = Library::new();  // We'll optimize this more later
-        self.hand = Hand::new();
-        self.graveyard = Graveyard::new();
-        self.battlefield = Battlefield::new();
-        self.exile = Exile::new();
+        self.library.clear();
+        self.hand.clear();
+        self.graveyard.clear();
+        self.battlefield.clear();
+        self.exile.clear();
         self.turn = 0;
         self.phase = Phase::Untap;
         self.on_the_play = false;
-        self.land_played_this_turn = false;
+        self.lands_played_this_turn = 0;
+        self.extra_land_drops = 0;
+        self.spells_cast_this_turn = 0;
+        self.first_spell_turn = None;
+        self.mana_spent_this_turn = 0;
+        self.mana_available_this_turn = 0;
+        self.combo_estimate = None;
+        self.reanimated_count = None;
+        self.opponent_life_at_turn_start = 20;
+        self.held_combo_turn = None;
+        self.lands_drawn = 0;
+        self.spells_drawn = 0;
+        self.draws_by_source.clear();
         self.life = 20;
+        self.total_life_gained = 0;
+        self.decked_out = false;
+        self.spider_men_binned = 0;
         self.opponent_life = 20;
+        self.opponent_library_size = DEFAULT_OPPONENT_LIBRARY_SIZE;
+        self.opponent_blockers.clear();
+        self.replacement_effects.0.clear();
+        self.opponent_counters = 0;
+        self.events.clear();
         self.mana_pool = ManaPool::new();
     }
 }
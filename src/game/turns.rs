@@ -1,19 +1,30 @@
 use crate::card::Card;
-use crate::game::state::GameState;
+use crate::game::state::{GameState, Phase};
 use crate::game::zones::CounterType;
 use crate::game::cards;
+use crate::game::events::GameEvent;
+use crate::game::mana::get_available_colors;
+use crate::rng::GameRng;
+use crate::simulation::decisions::DecisionEngine;
+use crate::vlog;
 
-/// Start a new turn: increment turn counter, untap all permanents, reset land drop
+/// Start a new turn at Untap: increment turn counter, untap all permanents, reset land drop
 pub fn start_turn(state: &mut GameState) {
     state.turn += 1;
+    state.phase = Phase::Untap;
     state.reset_turn_state();
     state.untap_all();
+    state.record_available_mana();
+}
+
+/// Whether this turn's draw step is skipped: only turn 1 when on the play
+pub fn skips_draw(state: &GameState) -> bool {
+    state.turn == 1 && state.on_the_play
 }
 
 /// Draw phase: draw 1 card (skip on turn 1 if on play)
 pub fn draw_phase(state: &mut GameState) {
-    // Skip draw on turn 1 if on the play
-    if state.turn == 1 && state.on_the_play {
+    if skips_draw(state) {
         return;
     }
 
@@ -23,7 +34,7 @@ pub fn draw_phase(state: &mut GameState) {
 
 /// Precombat main phase start: advance saga counters and resolve chapters
 /// According to MTG rules, saga lore counters are added at the beginning of the precombat main phase
-pub fn precombat_main_phase_start(state: &mut GameState, verbose: bool) {
+pub fn precombat_main_phase_start(state: &mut GameState, rng: &mut GameRng, verbose: bool) {
     // First pass: collect saga info (names, turn_entered) without modifying
     let mut sagas_to_advance: Vec<(usize, String, usize)> = Vec::new(); // (index, name, max_chapters)
 
@@ -48,7 +59,7 @@ pub fn precombat_main_phase_start(state: &mut GameState, verbose: bool) {
 
     // Third pass: resolve chapters
     for (saga_name, chapter) in &saga_chapters {
-        cards::resolve_saga_chapter(state, saga_name, *chapter as u32, verbose);
+        cards::resolve_saga_chapter(state, saga_name, *chapter as u32, rng, verbose);
     }
 
     // Fourth pass: remove completed sagas (put in graveyard)
@@ -64,19 +75,57 @@ pub fn precombat_main_phase_start(state: &mut GameState, verbose: bool) {
     // Remove in reverse order to preserve indices, putting sagas in graveyard
     for idx in indices_to_remove.into_iter().rev() {
         if let Some(permanent) = state.battlefield.remove_permanent(idx) {
-            state.graveyard.add_card(permanent.card);
+            state.add_to_graveyard(permanent.card);
         }
     }
 }
 
 /// Upkeep phase: trigger upkeep effects
-pub fn upkeep_phase(_state: &mut GameState) {
-    // Upkeep effects would be triggered here
+pub fn upkeep_phase(state: &mut GameState, verbose: bool) {
     // Saga advancement happens in precombat_main_phase_start (per MTG rules)
+    upkeep_triggers(state, verbose);
+}
+
+/// Resolve "at the beginning of your upkeep" abilities of permanents on the battlefield.
+/// Supported abilities: `upkeep_mill_N` (mill N cards).
+///
+/// Ardyn's Starscourge is a beginning-of-combat trigger, not an upkeep one, so it
+/// resolves in `simulate_combat` instead.
+pub fn upkeep_triggers(state: &mut GameState, verbose: bool) {
+    let triggers: Vec<(String, String)> = state.battlefield.permanents().iter()
+        .flat_map(|p| {
+            p.card.abilities().iter()
+                .filter(|a| a.starts_with("upkeep_"))
+                .map(|a| (p.card.name().to_string(), a.clone()))
+        })
+        .collect();
+
+    for (name, ability) in triggers {
+        if let Some(count) = ability.strip_prefix("upkeep_mill_").and_then(|n| n.parse::<usize>().ok()) {
+            let milled = state.library.mill(count);
+            if verbose {
+                let names: Vec<&str> = milled.iter().map(|c| c.name()).collect();
+                vlog!(2, "  [Upkeep] {} mills {}: {}", name, count, names.join(", "));
+            }
+            for card in milled {
+                state.mill_to_graveyard(card);
+            }
+        }
+    }
 }
 
-/// End phase: decrement time counters (impending creatures only, NOT sagas), discard to 7
+/// End phase: decrement time counters (impending creatures only, NOT sagas), discard to 7,
+/// and record a mana snapshot when event recording is on
 pub fn end_phase(state: &mut GameState) {
+    if state.record_events {
+        let snapshot = GameEvent::ManaSnapshot {
+            turn: state.turn,
+            untapped_sources: state.mana_available_this_turn,
+            colors: get_available_colors(state, false),
+        };
+        state.events.push(snapshot);
+    }
+
     // Decrement time counters on impending creatures only
     // Sagas also use time counters but they count UP, not down - don't touch them!
     for permanent in state.battlefield.permanents_mut() {
@@ -92,10 +141,9 @@ pub fn end_phase(state: &mut GameState) {
 
     // Discard to hand size 7 if needed
     while state.hand.size() > 7 {
-        // In a full implementation, this would choose which card to discard
-        // For now, just remove the last card
-        if let Some(card) = state.hand.remove_card(state.hand.size() - 1) {
-            state.add_to_graveyard(card);
-        }
+        let Some(card) = DecisionEngine::choose_discard(state).and_then(|idx| state.hand.remove_card(idx)) else {
+            break;
+        };
+        state.add_to_graveyard(card);
     }
 }
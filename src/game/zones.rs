@@ -1,4 +1,6 @@
-use crate::card::Card;
+use crate::card::{Card, ColorFlags, CreatureCard};
+use crate::game::mana;
+use crate::game::state::GameState;
 use std::collections::HashMap;
 
 /// Counter types for permanents (e.g., time counters for impending creatures)
@@ -17,18 +19,20 @@ pub struct Permanent {
     pub chosen_type: Option<String>,      // For Cavern of Souls
     pub chosen_basic_type: Option<String>, // For Multiversal Passage
     pub is_copy_of: Option<&'static str>, // For Superior Spider-Man (tracks copied creature for types/triggers, but Spider-Man stays 4/4)
+    pub fixed_colors: Option<ColorFlags>, // Colors of a land whose mana never depends on the board (None for conditional lands)
 }
 
 impl Permanent {
     pub fn new(card: Card, turn_entered: u32) -> Self {
         Permanent {
-            card,
             tapped: false,
             turn_entered,
             counters: HashMap::new(),
             chosen_type: None,
             chosen_basic_type: None,
             is_copy_of: None,
+            fixed_colors: mana::fixed_land_colors(&card),
+            card,
         }
     }
 
@@ -55,18 +59,25 @@ impl Permanent {
 }
 
 /// Library (deck) - ordered stack of cards
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Library {
     cards: Vec<Card>,
+    /// Skip shuffles that cannot change the draw order (off by default, since
+    /// skipping also skips the RNG draws and so changes later results for a seed)
+    skip_redundant_shuffles: bool,
 }
 
 impl Library {
     pub fn new() -> Self {
-        Library { cards: Vec::new() }
+        Library { cards: Vec::new(), skip_redundant_shuffles: false }
     }
 
     pub fn with_capacity(cap: usize) -> Self {
-        Library { cards: Vec::with_capacity(cap) }
+        Library { cards: Vec::with_capacity(cap), skip_redundant_shuffles: false }
+    }
+
+    pub fn set_skip_redundant_shuffles(&mut self, skip: bool) {
+        self.skip_redundant_shuffles = skip;
     }
 
     pub fn clear(&mut self) {
@@ -108,10 +119,32 @@ impl Library {
         self.cards.is_empty()
     }
 
+    /// Shuffle the remaining cards. The library only ever holds undrawn cards, so this is
+    /// already a Fisher-Yates over just the remaining cards.
     pub fn shuffle(&mut self, rng: &mut crate::rng::GameRng) {
+        if self.skip_redundant_shuffles && self.is_uniform() {
+            return;
+        }
         rng.shuffle(&mut self.cards);
     }
 
+    /// Tutor: remove the first card matching `pred`, then shuffle the rest.
+    /// Returns None (without shuffling) if nothing matches.
+    pub fn search_and_take(&mut self, pred: impl Fn(&Card) -> bool, rng: &mut crate::rng::GameRng) -> Option<Card> {
+        let idx = self.cards.iter().position(pred)?;
+        let card = self.cards.remove(idx);
+        self.shuffle(rng);
+        Some(card)
+    }
+
+    /// True if every remaining card is the same card, so any shuffle leaves the draws unchanged
+    fn is_uniform(&self) -> bool {
+        match self.cards.first() {
+            Some(first) => self.cards.iter().all(|c| c.name() == first.name()),
+            None => true,
+        }
+    }
+
     pub fn cards(&self) -> &[Card] {
         &self.cards
     }
@@ -122,7 +155,7 @@ impl Library {
 }
 
 /// Hand - cards in hand
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Hand {
     cards: Vec<Card>,
 }
@@ -159,10 +192,23 @@ impl Hand {
     pub fn cards(&self) -> &[Card] {
         &self.cards
     }
+
+    /// Non-land cards in hand that can be cast with the current untapped mana
+    /// (reanimation-only creatures are never offered)
+    pub fn castable_spells<'a>(&'a self, state: &GameState) -> Vec<(usize, &'a Card)> {
+        self.cards
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !matches!(c, Card::Land(_)) && c.is_hardcastable() && mana::can_cast_spell(c, state))
+            .collect()
+    }
 }
 
 /// Graveyard - discard pile (ordered stack)
-#[derive(Debug, Clone)]
+///
+/// Cards are kept in the order they were put there, so the last card is the top
+/// (most recently milled or discarded).
+#[derive(Debug, Clone, Default)]
 pub struct Graveyard {
     cards: Vec<Card>,
 }
@@ -188,8 +234,60 @@ impl Graveyard {
         &self.cards
     }
 
-    pub fn clear_creatures(&mut self) {
-        self.cards.retain(|c| !matches!(c, Card::Creature(_)));
+    pub fn size(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Remove every card, returning them in graveyard order
+    pub fn take_all(&mut self) -> Vec<Card> {
+        self.cards.drain(..).collect()
+    }
+
+    /// Creature cards in the graveyard, in graveyard order
+    pub fn creatures(&self) -> impl Iterator<Item = &CreatureCard> {
+        self.cards.iter().filter_map(|c| match c {
+            Card::Creature(creature) => Some(creature),
+            _ => None,
+        })
+    }
+
+    /// Top card of the graveyard (the most recent one put there)
+    pub fn peek_last(&self) -> Option<&Card> {
+        self.cards.last()
+    }
+
+    /// Topmost creature card in the graveyard
+    pub fn top_creature(&self) -> Option<&CreatureCard> {
+        self.creatures().last()
+    }
+
+    /// Remove and return the topmost creature card
+    pub fn take_top_creature(&mut self) -> Option<Card> {
+        let idx = self.cards.iter().rposition(|c| matches!(c, Card::Creature(_)))?;
+        Some(self.cards.remove(idx))
+    }
+
+    /// Number of different card types among the graveyard's cards (instants and
+    /// sorceries count separately, as do enchantments and sagas)
+    pub fn distinct_card_types(&self) -> u32 {
+        self.cards
+            .iter()
+            .fold(0u8, |seen, c| seen | 1 << c.card_type() as u8)
+            .count_ones()
+    }
+
+    /// Total power of all creature cards in the graveyard
+    pub fn total_creature_power(&self) -> u32 {
+        self.creatures().map(|c| c.power).sum()
+    }
+
+    /// Remove every creature card, returning them in graveyard order
+    pub fn take_creatures(&mut self) -> Vec<Card> {
+        let (creatures, rest) = std::mem::take(&mut self.cards)
+            .into_iter()
+            .partition(|c| matches!(c, Card::Creature(_)));
+        self.cards = rest;
+        creatures
     }
 
     pub fn remove_card(&mut self, index: usize) -> Option<Card> {
@@ -199,10 +297,44 @@ impl Graveyard {
             None
         }
     }
+
+    /// Put a card back at `index` (clamped to the top)
+    pub fn insert_card(&mut self, index: usize, card: Card) {
+        self.cards.insert(index.min(self.cards.len()), card);
+    }
+
+    /// Remove several cards at once by their current indices.
+    /// Indices may be given in any order; duplicates and out-of-range indices are ignored.
+    /// Returns the removed cards in graveyard order.
+    pub fn remove_cards(&mut self, indices: &[usize]) -> Vec<Card> {
+        let mut sorted: Vec<usize> = indices.iter().copied().filter(|&i| i < self.cards.len()).collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        // Remove from highest to lowest so earlier indices stay valid
+        let mut removed: Vec<Card> = sorted.iter().rev().map(|&i| self.cards.remove(i)).collect();
+        removed.reverse();
+        removed
+    }
+
+    /// Flashback-style spells in the graveyard that can be cast with the current untapped mana
+    pub fn castable_spells<'a>(&'a self, state: &GameState) -> Vec<(usize, &'a Card)> {
+        self.cards
+            .iter()
+            .enumerate()
+            .filter(|&(idx, c)| c.is_castable_from_graveyard() && mana::can_cast_spell_from_graveyard(c, idx, state))
+            .collect()
+    }
+
+    /// Remove the first card with the given name
+    pub fn remove_card_by_name(&mut self, name: &str) -> Option<Card> {
+        let idx = self.cards.iter().position(|c| c.name() == name)?;
+        Some(self.cards.remove(idx))
+    }
 }
 
 /// Battlefield - permanents in play
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Battlefield {
     permanents: Vec<Permanent>,
 }
@@ -246,7 +378,7 @@ impl Battlefield {
 }
 
 /// Exile - exiled cards
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Exile {
     cards: Vec<Card>,
 }
@@ -267,5 +399,163 @@ impl Exile {
     pub fn add_card(&mut self, card: Card) {
         self.cards.push(card);
     }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn size(&self) -> usize {
+        self.cards.len()
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardDatabase;
+
+    fn graveyard_of(names: &[&str]) -> Graveyard {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut graveyard = Graveyard::new();
+        for name in names {
+            graveyard.add_card(db.get_card(name).expect("Card should exist"));
+        }
+        graveyard
+    }
+
+    #[test]
+    fn test_distinct_card_types() {
+        assert_eq!(graveyard_of(&[]).distinct_card_types(), 0);
+        assert_eq!(graveyard_of(&["Swamp", "Forest", "Island"]).distinct_card_types(), 1);
+
+        // Instant and sorcery are two types; duplicates don't add more
+        let spells = graveyard_of(&["Cache Grab", "Analyze the Pollen", "Cache Grab"]);
+        assert_eq!(spells.distinct_card_types(), 2);
+
+        let mixed = graveyard_of(&["Swamp", "Town Greeter", "Cache Grab", "Analyze the Pollen",
+            "Dredger's Insight", "Awaken the Honored Dead", "Bringer of the Last Gift"]);
+        assert_eq!(mixed.distinct_card_types(), 6);
+    }
+
+    fn names(cards: &[Card]) -> Vec<&str> {
+        cards.iter().map(|c| c.name()).collect()
+    }
+
+    fn library_of(db: &CardDatabase, names: &[&str], skip: bool) -> Library {
+        let mut library = Library::new();
+        library.set_skip_redundant_shuffles(skip);
+        for name in names {
+            library.add_card(db.get_card(name).expect("Card should exist"));
+        }
+        library
+    }
+
+    #[test]
+    fn test_skip_redundant_shuffles_keeps_draw_distribution() {
+        use crate::rng::GameRng;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mixed = ["Forest", "Island", "Swamp", "Cache Grab", "Town Greeter", "Forest"];
+
+        // A mixed library is never skipped, so every seed gives the same order either way
+        let mut top_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for seed in 0..2000 {
+            let mut normal = library_of(&db, &mixed, false);
+            let mut skipping = library_of(&db, &mixed, true);
+            normal.shuffle(&mut GameRng::new(Some(seed)));
+            skipping.shuffle(&mut GameRng::new(Some(seed)));
+            assert_eq!(names(normal.cards()), names(skipping.cards()));
+            *top_counts.entry(skipping.cards()[0].name().to_string()).or_insert(0) += 1;
+        }
+        // Forest is 2 of 6 cards and should be on top about a third of the time
+        let forest_rate = top_counts["Forest"] as f64 / 2000.0;
+        assert!((forest_rate - 1.0 / 3.0).abs() < 0.05, "Forest on top {:.3}", forest_rate);
+
+        // A uniform library draws identically with or without the shuffle, and skipping saves the RNG calls
+        let mut skipping = library_of(&db, &["Swamp"; 5], true);
+        let mut rng = GameRng::new(Some(7));
+        skipping.shuffle(&mut rng);
+        assert_eq!(names(skipping.cards()), vec!["Swamp"; 5]);
+        assert_eq!(rng.random(), GameRng::new(Some(7)).random());
+    }
+
+    #[test]
+    fn test_remove_cards_any_index_order() {
+        let names_in = ["Forest", "Island", "Swamp", "Cache Grab", "Town Greeter"];
+        for indices in [[0, 2, 4], [4, 2, 0], [2, 0, 4]] {
+            let mut graveyard = graveyard_of(&names_in);
+            let removed = graveyard.remove_cards(&indices);
+
+            assert_eq!(names(&removed), vec!["Forest", "Swamp", "Town Greeter"]);
+            assert_eq!(names(graveyard.cards()), vec!["Island", "Cache Grab"]);
+        }
+    }
+
+    #[test]
+    fn test_remove_cards_ignores_duplicates_and_out_of_range() {
+        let mut graveyard = graveyard_of(&["Forest", "Island", "Swamp"]);
+        let removed = graveyard.remove_cards(&[1, 1, 7]);
+
+        assert_eq!(names(&removed), vec!["Island"]);
+        assert_eq!(names(graveyard.cards()), vec!["Forest", "Swamp"]);
+    }
+
+    #[test]
+    fn test_total_creature_power_mixed_graveyard() {
+        // Town Greeter (1) + Kiora (3) + Bringer (6); lands and spells don't count
+        let graveyard = graveyard_of(&["Forest", "Town Greeter", "Cache Grab",
+            "Kiora, the Rising Tide", "Bringer of the Last Gift"]);
+
+        assert_eq!(graveyard.creatures().count(), 3);
+        assert_eq!(graveyard.total_creature_power(), 1 + 3 + 6);
+        assert_eq!(Graveyard::new().total_creature_power(), 0);
+    }
+
+    #[test]
+    fn test_take_creatures_leaves_only_non_creatures() {
+        let mut graveyard = graveyard_of(&["Town Greeter", "Forest", "Bringer of the Last Gift",
+            "Cache Grab", "Kiora, the Rising Tide"]);
+
+        let taken = graveyard.take_creatures();
+        assert_eq!(names(&taken), vec!["Town Greeter", "Bringer of the Last Gift", "Kiora, the Rising Tide"]);
+        assert_eq!(names(graveyard.cards()), vec!["Forest", "Cache Grab"]);
+        assert!(graveyard.take_creatures().is_empty());
+    }
+
+    #[test]
+    fn test_remove_card_by_name() {
+        let mut graveyard = graveyard_of(&["Forest", "Island", "Forest"]);
+
+        let removed = graveyard.remove_card_by_name("Forest");
+        assert_eq!(removed.map(|c| c.name().to_string()), Some("Forest".to_string()));
+        assert_eq!(names(graveyard.cards()), vec!["Island", "Forest"]);
+        assert!(graveyard.remove_card_by_name("Swamp").is_none());
+    }
+
+    #[test]
+    fn test_search_and_take_returns_card_and_shuffles_rest() {
+        use crate::rng::GameRng;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let order = ["Forest", "Island", "Swamp", "Watery Grave", "Terror of the Peaks", "Town Greeter",
+            "Cache Grab", "Kiora, the Rising Tide", "Formidable Speaker", "Superior Spider-Man"];
+        let mut library = library_of(&db, &order, false);
+        let mut rng = GameRng::new(Some(3));
+
+        let found = library.search_and_take(|c| c.name() == "Kiora, the Rising Tide", &mut rng);
+        assert_eq!(found.map(|c| c.name().to_string()), Some("Kiora, the Rising Tide".to_string()));
+        assert_eq!(library.size(), order.len() - 1);
+
+        let mut rest = names(library.cards());
+        assert_ne!(rest, order.iter().copied().filter(|n| *n != "Kiora, the Rising Tide").collect::<Vec<_>>());
+        rest.sort();
+        let mut expected: Vec<&str> = order.iter().copied().filter(|n| *n != "Kiora, the Rising Tide").collect();
+        expected.sort();
+        assert_eq!(rest, expected);
+
+        // No match: nothing removed, no shuffle
+        let before: Vec<String> = library.cards().iter().map(|c| c.name().to_string()).collect();
+        assert!(library.search_and_take(|c| c.name() == "Bringer of the Last Gift", &mut rng).is_none());
+        assert_eq!(library.cards().iter().map(|c| c.name().to_string()).collect::<Vec<_>>(), before);
+    }
+}
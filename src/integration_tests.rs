@@ -113,3 +113,74 @@ fn test_deterministic_rng_sequence() {
         assert_eq!(results[0].turn_with_ubg, results[i].turn_with_ubg);
     }
 }
+
+#[test]
+fn test_reused_state_matches_fresh_state() {
+    use crate::game::state::GameState;
+    use crate::simulation::engine::run_game_with_state;
+
+    let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+    let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+    // run_game reuses a thread-local state; compare against a brand new state per seed
+    for seed in 0..10_000u64 {
+        let reused = run_game(&deck, seed, &db, false);
+        let fresh = run_game_with_state(&mut GameState::new(), &mut Vec::new(), &deck, seed, &db, false);
+
+        assert_eq!(reused.win_turn, fresh.win_turn, "win_turn differs for seed {}", seed);
+        assert_eq!(reused.turn_with_ubg, fresh.turn_with_ubg, "turn_with_ubg differs for seed {}", seed);
+    }
+}
+
+#[test]
+fn test_required_card_always_in_opening_hand() {
+    use crate::game::state::GameState;
+    use crate::rng::GameRng;
+    use crate::simulation::engine::{find_seed_with_opening_hand, setup_game};
+    use crate::simulation::mulligan::DEFAULT_OPENING_HAND_SIZE;
+
+    let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+    let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+    let required = vec!["Superior Spider-Man".to_string()];
+
+    let mut total_reshuffles = 0;
+    for seed in 0..200u64 {
+        let (hand_seed, reshuffles) = find_seed_with_opening_hand(&deck, seed, &required, DEFAULT_OPENING_HAND_SIZE)
+            .expect("Should find a hand with Spider-Man");
+        total_reshuffles += reshuffles;
+
+        // Replaying the chosen seed gives a kept hand with the required card
+        let mut state = GameState::new();
+        setup_game(&mut state, &deck, &mut GameRng::new(Some(hand_seed)), &mut Vec::new());
+        assert!(state.hand.cards().iter().any(|c| c.name() == "Superior Spider-Man"),
+            "Seed {} (from {}) should have Spider-Man in hand", hand_seed, seed);
+    }
+    assert!(total_reshuffles > 0, "A 4-of should not be in every opening hand");
+}
+
+#[test]
+fn test_stock_deck_win_rate_stays_in_a_sane_band() {
+    use crate::simulation::engine::{run_game_with_config, GameConfig};
+    use crate::simulation::stats::RunningStats;
+
+    let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+    let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+    // The goldfish wins almost every game eventually, so race a turn-7 clock (about 75% wins):
+    // a broken combo pushes the win rate out of the band in either direction
+    let config = GameConfig { opponent_clock: Some(7), ..GameConfig::default() };
+    let mut stats = RunningStats::default();
+    for seed in 0..5000u64 {
+        let result = run_game_with_config(&deck, seed, &db, false, &config);
+        if let Some(win_turn) = result.win_turn {
+            assert!((1..=7).contains(&win_turn), "seed {} won on turn {}", seed, win_turn);
+        }
+        stats.push(&result);
+    }
+
+    assert_eq!(stats.games, 5000);
+    let win_rate = stats.win_rate();
+    assert!((0.30..=0.90).contains(&win_rate), "win rate {:.1}% outside 30-90%", win_rate * 100.0);
+    let avg_win_turn = stats.avg_win_turn();
+    assert!((4.0..=7.0).contains(&avg_win_turn), "average win turn {:.2} outside 4-7", avg_win_turn);
+}
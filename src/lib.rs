@@ -1,6 +1,8 @@
 pub mod card;
 pub mod cli;
 pub mod game;
+pub mod log;
+pub mod profile;
 pub mod rng;
 pub mod simulation;
 
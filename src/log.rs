@@ -0,0 +1,80 @@
+//! Leveled log for verbose games
+//!
+//! Engine code only logs when its `verbose` flag is on; the level (`-v`, `-vv`, `-vvv`)
+//! then picks which lines are shown:
+//! 1. turn summaries, casts, combat and Terror damage
+//! 2. ETB, mill, surveil and other resolution details
+//! 3. mana choices and combo-damage internals
+//!
+//! The level is per thread (verbose games run on the calling thread), and lines go to
+//! stdout unless `capture` is collecting them.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+/// Most detailed level, and the default so library callers see every line
+pub const MAX_LEVEL: u8 = 3;
+
+thread_local! {
+    static LEVEL: Cell<u8> = const { Cell::new(MAX_LEVEL) };
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Show lines up to `level` (0 silences the log)
+pub fn set_level(level: u8) {
+    LEVEL.with(|l| l.set(level.min(MAX_LEVEL)));
+}
+
+pub fn level() -> u8 {
+    LEVEL.with(|l| l.get())
+}
+
+/// Whether lines at `level` are shown
+pub fn enabled(level: u8) -> bool {
+    level <= self::level()
+}
+
+/// Write one line to the capture buffer or stdout
+pub fn write_line(args: fmt::Arguments) {
+    CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(buffer) => {
+            fmt::Write::write_fmt(buffer, args).expect("writing to a String cannot fail");
+            buffer.push('\n');
+        }
+        None => println!("{}", args),
+    });
+}
+
+/// Run `f`, collecting the lines it logs instead of printing them
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = CAPTURED.with(|captured| captured.replace(Some(String::new())));
+    let result = f();
+    let log = CAPTURED.with(|captured| captured.replace(previous)).unwrap_or_default();
+    (result, log)
+}
+
+/// `println!` gated by log level: `vlog!(2, "Mill 4: {}", names)`
+#[macro_export]
+macro_rules! vlog {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write_line(format_args!($($arg)*))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_filters_by_level() {
+        set_level(1);
+        let ((), log) = capture(|| {
+            vlog!(1, "turn {}", 1);
+            vlog!(2, "mill detail");
+        });
+        set_level(MAX_LEVEL);
+        assert_eq!(log, "turn 1\n");
+    }
+}
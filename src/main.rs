@@ -1,15 +1,24 @@
-mod card;
-mod cli;
-mod game;
-mod rng;
-mod simulation;
+use mtg_reanimator::{card, game, simulation};
 
 use card::CardDatabase;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use rayon::prelude::*;
-use simulation::deck::parse_deck_file;
-use simulation::engine::run_game;
-use std::collections::HashMap;
+use simulation::deck::{diff_decks, format_deck_list, missing_ubg_colors, parse_deck_file};
+use game::state::{
+    GraveyardHate, GraveyardHateMode, LandPolicy, OpponentCurve, ReplacementEffect, ReplacementEffects,
+    DEFAULT_PLAY_PROBABILITY,
+};
+use simulation::mulligan::DEFAULT_OPENING_HAND_SIZE;
+use simulation::engine::{
+    find_seed_with_opening_hand, run_game, run_game_with_config, GameConfig, GameResult, LossReason, WinLine,
+    MAX_OPENING_HAND_RESHUFFLES,
+};
+use simulation::scenario::{parse_scenario_file, run_scenarios, weighted_win_rate};
+use simulation::results::{read_results_file, write_results_file};
+use simulation::seeds::{curate, format_curated, format_seeds_csv, parse_seeds_file};
+use simulation::stats::{run_until_converged, AutoStop, RunSummary, RunningStats};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
 
 
 #[derive(Parser)]
@@ -27,9 +36,63 @@ struct Cli {
     #[arg(short, long, default_value = "deck.txt")]
     deck: String,
 
-    /// Enable verbose output for single game
-    #[arg(short, long)]
-    verbose: bool,
+    /// Log a single game: -v turns and casts, -vv resolution details, -vvv mana and combo internals
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Card database file (defaults to $MTG_CARDS, then ./cards.json, then the built-in copy)
+    #[arg(long, global = true)]
+    cards: Option<String>,
+}
+
+/// Load the card database from --cards, $MTG_CARDS or ./cards.json, falling back to the
+/// copy embedded in the binary. Returns the database and where it came from.
+fn load_card_database(cards: Option<String>) -> Result<(CardDatabase, String), card::CardDatabaseError> {
+    if let Some(path) = cards.or_else(|| std::env::var("MTG_CARDS").ok()) {
+        return CardDatabase::from_file(&path).map(|db| (db, path));
+    }
+    if std::path::Path::new("cards.json").exists() {
+        return CardDatabase::from_file("cards.json").map(|db| (db, "cards.json".to_string()));
+    }
+    Ok((CardDatabase::embedded(), "built-in cards.json".to_string()))
+}
+
+/// Opponent graveyard hate to model in `run`
+#[derive(Clone, Copy, ValueEnum)]
+enum GyHate {
+    /// Exile the graveyard once (Bojuka Bog)
+    OneShot,
+    /// Exile the graveyard and keep exiling cards that would go there (Rest in Peace)
+    Continuous,
+}
+
+/// How the opponent's attacks grow in `run`
+#[derive(Clone, Copy, ValueEnum)]
+enum Curve {
+    /// 3 damage every turn
+    Flat,
+    /// 1 damage on their first turn, 2 more each turn after
+    Linear,
+    /// 1 damage on their first turn, doubling each turn after
+    Exponential,
+}
+
+/// Force which side of the coin flip we're on in `run`
+#[derive(Clone, Copy, ValueEnum)]
+enum Start {
+    /// Always on the play (skip the turn-1 draw)
+    Play,
+    /// Always on the draw
+    Draw,
+}
+
+/// What the land drop is chosen for in `run`
+#[derive(Clone, Copy, ValueEnum)]
+enum LandOrder {
+    /// Prefer a land that lets us cast a spell this turn
+    EnableCast,
+    /// Prefer a land adding a color we don't have yet, even if it enters tapped
+    FixColorsFirst,
 }
 
 #[derive(Subcommand)]
@@ -48,9 +111,120 @@ enum Commands {
         #[arg(short, long)]
         seed: Option<u64>,
 
-        /// Enable verbose output for single game
-        #[arg(short, long)]
-        verbose: bool,
+        /// Log the first game: -v turns and casts, -vv resolution details, -vvv mana and combo internals
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+
+        /// Print the parsed deck before simulating
+        #[arg(long)]
+        print_deck: bool,
+
+        /// Parse the deck and exit without simulating
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only count games whose kept opening hand contains this card (repeatable)
+        #[arg(long = "require-in-hand", value_name = "CARD")]
+        require_in_hand: Vec<String>,
+
+        /// Print only win rate (with 95% CI), average win turn and average UBG turn as `key: value` lines
+        #[arg(long)]
+        summary_only: bool,
+
+        /// Opponent exiles our graveyard starting on --gy-hate-turn
+        #[arg(long, value_enum)]
+        gy_hate: Option<GyHate>,
+
+        /// Turn on which graveyard hate comes down
+        #[arg(long, default_value = "3")]
+        gy_hate_turn: u32,
+
+        /// Creatures that would enter the battlefield from our graveyard are exiled instead
+        #[arg(long)]
+        reanimation_exiles: bool,
+
+        /// This creature is exiled instead of entering from our graveyard (repeatable)
+        #[arg(long, value_name = "CARD")]
+        cant_reanimate: Vec<String>,
+
+        /// Always start on the play or on the draw instead of flipping a coin
+        /// (seeds still line up with random-start runs)
+        #[arg(long, value_enum)]
+        start: Option<Start>,
+
+        /// We lose if we haven't won by the end of this turn
+        #[arg(long)]
+        opponent_clock: Option<u32>,
+
+        /// The opponent attacks every turn, with damage growing along this curve
+        #[arg(long, value_enum)]
+        opponent_curve: Option<Curve>,
+
+        /// The opponent counters our first N Superior Spider-Man casts
+        #[arg(long, default_value = "0")]
+        opponent_counters: u32,
+
+        /// Cards in the opening hand before mulligans (8 for an extra-card effect)
+        #[arg(long, default_value = "7")]
+        opening_hand_size: usize,
+
+        /// How the land drop is chosen (default: enable-cast)
+        #[arg(long, value_enum)]
+        land_policy: Option<LandOrder>,
+
+        /// Also time a single-threaded sample of games and print where the time goes
+        #[arg(long)]
+        profile: bool,
+
+        /// JSON file of weighted matchup scenarios; reports a weighted win rate
+        #[arg(long, conflicts_with_all = [
+            "require_in_hand", "seeds_file", "curate", "bincode", "record_exiled", "auto", "start", "verbose", "profile",
+        ])]
+        scenario_file: Option<String>,
+
+        /// Play exactly the seeds in this file (one per line) instead of --num-games, reporting each
+        #[arg(long, conflicts_with_all = ["seed", "auto"])]
+        seeds_file: Option<String>,
+
+        /// Also write --seeds-file results to this CSV file
+        #[arg(long, requires = "seeds_file")]
+        seeds_csv: Option<String>,
+
+        /// Write one representative game per category (fastest, typical and narrow win, each
+        /// loss reason) to <DIR>/curated.txt, a seed file for --seeds-file
+        #[arg(long, value_name = "DIR", conflicts_with = "auto")]
+        curate: Option<String>,
+
+        /// Save every game's result to this binary file, for `report`
+        #[arg(long, value_name = "PATH", conflicts_with = "auto")]
+        bincode: Option<String>,
+
+        /// Record each game's exile zone at game end and list the most commonly exiled cards
+        #[arg(long, conflicts_with_all = ["summary_only", "auto"])]
+        record_exiled: bool,
+
+        /// Ignore --num-games and simulate in batches until the win-rate 95% CI is within --ci-target
+        #[arg(long)]
+        auto: bool,
+
+        /// Target 95% CI half-width for --auto, in percentage points
+        #[arg(long, default_value = "0.5")]
+        ci_target: f64,
+
+        /// Maximum number of games for --auto
+        #[arg(long, default_value = "1000000")]
+        max_games: usize,
+    },
+
+    /// Inspect a deck file without simulating
+    Inspect {
+        /// Deck file to use
+        #[arg(short, long, default_value = "deck.txt")]
+        deck: String,
+
+        /// Print the full parsed deck list
+        #[arg(long)]
+        print_deck: bool,
     },
 
     /// Compare two deck configurations
@@ -64,6 +238,28 @@ enum Commands {
         /// Number of games per deck
         #[arg(short, long, default_value = "1000")]
         num_games: usize,
+
+        /// Fail if both files parse to the same cards (e.g. one list fed twice, reordered)
+        #[arg(long)]
+        assert_different: bool,
+    },
+
+    /// Rank every deck in a directory and estimate how likely each is the best
+    Tournament {
+        /// Directory of deck files (*.txt)
+        dir: String,
+
+        /// Number of games per deck
+        #[arg(short, long, default_value = "1000")]
+        num_games: usize,
+
+        /// Seed for reproducibility
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Games not won by the end of this turn count as losses
+        #[arg(long, default_value = "6")]
+        opponent_clock: u32,
     },
 
     /// Optimize land configuration
@@ -98,6 +294,56 @@ enum Commands {
         /// Seed for reproducibility
         #[arg(short, long)]
         seed: Option<u64>,
+
+        /// Analyze exactly the seeds in this file (one per line) instead of --num-games
+        #[arg(long, conflicts_with = "seed")]
+        seeds_file: Option<String>,
+    },
+
+    /// Play a fixed board forward over many library orders
+    Solve {
+        /// JSON board file (see the simulation::solve module docs)
+        state_file: String,
+
+        /// Number of library orders to play out
+        #[arg(short, long, default_value = "1000")]
+        num_games: usize,
+
+        /// Seed for reproducibility
+        #[arg(short, long)]
+        seed: Option<u64>,
+    },
+
+    /// Play games with randomly mutated decks, reporting any that panic
+    Fuzz {
+        /// Deck file to mutate
+        #[arg(short, long, default_value = "deck.txt")]
+        deck: String,
+
+        /// Number of mutated decks to try
+        #[arg(short, long, default_value = "1000")]
+        iterations: usize,
+
+        /// Games to play with each mutated deck
+        #[arg(short, long, default_value = "5")]
+        games: usize,
+
+        /// Seed for reproducibility
+        #[arg(short, long)]
+        seed: Option<u64>,
+    },
+
+    /// Re-aggregate a results file saved by `run --bincode` without re-simulating
+    Report {
+        /// Binary results file
+        results_file: String,
+    },
+
+    /// Dump the loaded card database as normalized JSON
+    DumpCards {
+        /// Output file (stdout if omitted)
+        #[arg(short, long)]
+        out: Option<String>,
     },
 
     /// Simulate mana production per turn
@@ -120,9 +366,9 @@ fn main() {
     let cli = Cli::parse();
 
     // Load the card database
-    let db = match CardDatabase::from_file("cards.json") {
-        Ok(db) => {
-            eprintln!("✓ Loaded {} cards from cards.json", db.card_count());
+    let db = match load_card_database(cli.cards) {
+        Ok((db, source)) => {
+            eprintln!("✓ Loaded {} cards from {}", db.card_count(), source);
             db
         }
         Err(e) => {
@@ -131,153 +377,651 @@ fn main() {
         }
     };
 
-    match cli.command {
+    let unknown = game::cards::unknown_abilities(&db);
+    if !unknown.is_empty() {
+        let listed: Vec<String> = unknown
+            .iter()
+            .map(|(ability, cards)| format!("{} ({})", ability, cards.join(", ")))
+            .collect();
+        eprintln!("⚠ Unknown abilities will be ignored: {}", listed.join("; "));
+    }
+
+    let mut out = io::stdout();
+    let result = match cli.command {
         Some(Commands::Run {
             num_games,
             deck,
             seed,
             verbose,
+            print_deck,
+            dry_run,
+            require_in_hand,
+            summary_only,
+            gy_hate,
+            gy_hate_turn,
+            reanimation_exiles,
+            cant_reanimate,
+            start,
+            opponent_clock,
+            opponent_curve,
+            opponent_counters,
+            opening_hand_size,
+            land_policy,
+            profile,
+            scenario_file,
+            seeds_file,
+            seeds_csv,
+            curate,
+            bincode,
+            record_exiled,
+            auto,
+            ci_target,
+            max_games,
         }) => {
-            run_simulation(&db, &deck, num_games, seed, verbose);
+            let printed = if print_deck || dry_run {
+                print_deck_list(&mut out, &db, &deck)
+            } else {
+                Ok(())
+            };
+            if dry_run {
+                printed
+            } else {
+                let seeds = seeds_file.as_deref().map(load_seeds);
+                let options = RunOptions {
+                    num_games: seeds.as_ref().map_or(num_games, |seeds| seeds.len()),
+                    seed,
+                    verbose,
+                    require_in_hand,
+                    summary_only,
+                    scenario_file,
+                    seeds,
+                    seeds_csv,
+                    curate,
+                    bincode,
+                    profile,
+                    auto: auto.then_some(AutoStop {
+                        target_half_width: ci_target / 100.0,
+                        max_games,
+                        batch_size: AUTO_BATCH_SIZE,
+                    }),
+                    config: GameConfig {
+                        graveyard_hate: gy_hate.map(|mode| GraveyardHate {
+                            turn: gy_hate_turn,
+                            mode: match mode {
+                                GyHate::OneShot => GraveyardHateMode::OneShot,
+                                GyHate::Continuous => GraveyardHateMode::Continuous,
+                            },
+                        }),
+                        play_probability: match start {
+                            Some(Start::Play) => 1.0,
+                            Some(Start::Draw) => 0.0,
+                            None => DEFAULT_PLAY_PROBABILITY,
+                        },
+                        opponent_clock,
+                        opponent_curve: opponent_curve.map(|curve| match curve {
+                            Curve::Flat => OpponentCurve::Flat,
+                            Curve::Linear => OpponentCurve::Linear,
+                            Curve::Exponential => OpponentCurve::Exponential,
+                        }),
+                        opponent_counters,
+                        opening_hand_size,
+                        land_policy: match land_policy {
+                            Some(LandOrder::EnableCast) | None => LandPolicy::EnableCast,
+                            Some(LandOrder::FixColorsFirst) => LandPolicy::FixColorsFirst,
+                        },
+                        record_exiled,
+                        replacement_effects: ReplacementEffects(
+                            reanimation_exiles.then_some(ReplacementEffect::ReanimationExiles).into_iter()
+                                .chain(cant_reanimate.into_iter().map(ReplacementEffect::CantBeReanimated))
+                                .collect(),
+                        ),
+                        ..GameConfig::default()
+                    },
+                };
+                printed.and_then(|()| run_simulation(&mut out, &db, &deck, &options))
+            }
+        }
+        Some(Commands::Inspect { deck, print_deck }) => {
+            inspect_deck(&mut out, &db, &deck, print_deck)
         }
         Some(Commands::Compare {
             deck1,
             deck2,
             num_games,
+            assert_different,
         }) => {
-            compare_decks(&db, &deck1, &deck2, num_games);
+            compare_decks(&mut out, &db, &deck1, &deck2, num_games, assert_different)
+        }
+        Some(Commands::Tournament { dir, num_games, seed, opponent_clock }) => {
+            run_deck_tournament(&mut out, &db, &dir, num_games, seed, opponent_clock)
         }
         Some(Commands::Optimize { configs, games, strategy, deck }) => {
-            optimize_lands(&db, configs, games, &strategy, &deck);
+            optimize_lands(&mut out, &db, configs, games, &strategy, &deck)
+        }
+        Some(Commands::Analyze { num_games, deck, seed, seeds_file }) => {
+            let seeds = seeds_file.as_deref().map(load_seeds);
+            analyze_turn4_failures(&mut out, &db, &deck, num_games, seed, seeds.as_deref())
+        }
+        Some(Commands::Solve { state_file, num_games, seed }) => {
+            solve_board(&mut out, &db, &state_file, num_games, seed)
         }
-        Some(Commands::Analyze { num_games, deck, seed }) => {
-            analyze_turn4_failures(&db, &deck, num_games, seed);
+        Some(Commands::Fuzz { deck, iterations, games, seed }) => {
+            fuzz_deck(&mut out, &db, &deck, iterations, games, seed)
         }
+        Some(Commands::Report { results_file }) => report_results(&mut out, &results_file),
+        Some(Commands::DumpCards { out: path }) => dump_cards(&mut out, &db, path.as_deref()),
         Some(Commands::Mana { deck, num_games, turns }) => {
-            run_mana_sim(&db, &deck, num_games, turns);
+            run_mana_sim(&mut out, &db, &deck, num_games, turns)
         }
         None => {
             // Default: run simulation with CLI args
-            let num_games = if cli.verbose { 1 } else { 1000 };
-            run_simulation(&db, &cli.deck, num_games, cli.seed, cli.verbose);
+            let num_games = if cli.verbose > 0 { 1 } else { 1000 };
+            let options = RunOptions {
+                num_games,
+                seed: cli.seed,
+                verbose: cli.verbose,
+                require_in_hand: Vec::new(),
+                summary_only: false,
+                scenario_file: None,
+                seeds: None,
+                seeds_csv: None,
+                curate: None,
+                bincode: None,
+                profile: false,
+                auto: None,
+                config: GameConfig::default(),
+            };
+            run_simulation(&mut out, &db, &cli.deck, &options)
         }
+    };
+
+    if let Err(e) = result {
+        eprintln!("✗ Failed to write output: {}", e);
+        std::process::exit(1);
     }
 }
 
-fn run_simulation(db: &CardDatabase, deck_file: &str, num_games: usize, seed: Option<u64>, verbose: bool) {
-    let deck = match parse_deck_file(deck_file, db) {
+fn load_seeds(path: &str) -> Vec<u64> {
+    match parse_seeds_file(path) {
+        Ok(seeds) => seeds,
+        Err(e) => {
+            eprintln!("✗ Failed to load seeds file '{}': {}", path, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn load_deck(db: &CardDatabase, deck_file: &str) -> Vec<card::Card> {
+    match parse_deck_file(deck_file, db) {
         Ok(deck) => deck,
         Err(e) => {
             eprintln!("✗ Failed to parse deck file '{}': {}", deck_file, e);
             std::process::exit(1);
         }
+    }
+}
+
+fn print_deck_list(out: &mut dyn Write, db: &CardDatabase, deck_file: &str) -> io::Result<()> {
+    let deck = load_deck(db, deck_file);
+    writeln!(out, "\n=== Parsed Deck: {} ===\n", deck_file)?;
+    write!(out, "{}", format_deck_list(&deck))?;
+
+    Ok(())
+}
+
+fn inspect_deck(out: &mut dyn Write, db: &CardDatabase, deck_file: &str, print_deck: bool) -> io::Result<()> {
+    let deck = load_deck(db, deck_file);
+
+    writeln!(out, "\n=== Deck Inspection ===\n")?;
+    writeln!(out, "Deck: {} ({} cards)", deck_file, deck.len())?;
+
+    let mut type_counts: Vec<(card::CardType, usize)> = Vec::new();
+    for c in &deck {
+        match type_counts.iter_mut().find(|(t, _)| *t == c.card_type()) {
+            Some((_, count)) => *count += 1,
+            None => type_counts.push((c.card_type(), 1)),
+        }
+    }
+    type_counts.sort();
+    for (card_type, count) in &type_counts {
+        writeln!(out, "  {:12} {}", format!("{}:", card_type), count)?;
+    }
+
+    for color in missing_ubg_colors(&deck) {
+        writeln!(out, "⚠ No land can produce {:?} mana", color)?;
+    }
+
+    if print_deck {
+        writeln!(out)?;
+        write!(out, "{}", format_deck_list(&deck))?;
+    }
+
+    Ok(())
+}
+
+/// Options for the Run command
+struct RunOptions {
+    num_games: usize,
+    seed: Option<u64>,
+    /// Log level for the first game (0 = no log)
+    verbose: u8,
+    require_in_hand: Vec<String>,
+    summary_only: bool,
+    scenario_file: Option<String>,
+    /// Play exactly these seeds (from --seeds-file), reporting each
+    seeds: Option<Vec<u64>>,
+    seeds_csv: Option<String>,
+    /// Directory for the curated games of the run
+    curate: Option<String>,
+    /// Binary file to save every game's result to
+    bincode: Option<String>,
+    /// Time a sample of `PROFILE_GAMES` games after the run
+    profile: bool,
+    auto: Option<AutoStop>,
+    config: GameConfig,
+}
+
+/// Games per batch between convergence checks in `run --auto`
+const AUTO_BATCH_SIZE: usize = 1000;
+
+/// Games timed by `run --profile`
+const PROFILE_GAMES: usize = 200;
+
+fn run_simulation(out: &mut dyn Write, db: &CardDatabase, deck_file: &str, options: &RunOptions) -> io::Result<()> {
+    let RunOptions { num_games, seed, verbose: log_level, .. } = *options;
+    let verbose = log_level > 0;
+    if verbose {
+        mtg_reanimator::log::set_level(log_level);
+    }
+    let deck = load_deck(db, deck_file);
+
+    for name in &options.require_in_hand {
+        if !deck.iter().any(|c| c.name() == name) {
+            eprintln!("✗ Required card '{}' is not in deck '{}'", name, deck_file);
+            std::process::exit(1);
+        }
+    }
+    // The Bo1 smoother draws two opening hands
+    let hand_size = options.config.opening_hand_size;
+    if hand_size == 0 || deck.len() < hand_size * 2 {
+        eprintln!("✗ Opening hand size must be between 1 and {} for deck '{}' (got {})", deck.len() / 2, deck_file, hand_size);
+        std::process::exit(1);
+    }
+
+    if !options.summary_only {
+        writeln!(out, "\n=== MTG Reanimator Simulator ===\n")?;
+        writeln!(out, "Deck: {} ({} cards)", deck_file, deck.len())?;
+        match &options.auto {
+            Some(stop) => writeln!(out, "Games: auto (until 95% CI within ±{:.2}%, at most {})",
+                stop.target_half_width * 100.0, stop.max_games)?,
+            None => writeln!(out, "Games: {}", num_games)?,
+        }
+        if let Some(s) = seed {
+            writeln!(out, "Seed: {}", s)?;
+        }
+        if options.seeds.is_some() {
+            writeln!(out, "Seeds: from file")?;
+        }
+        if !options.require_in_hand.is_empty() {
+            writeln!(out, "Opening hand must contain: {}", options.require_in_hand.join(", "))?;
+        }
+        match options.config.play_probability {
+            p if p >= 1.0 => writeln!(out, "Start: always on the play")?,
+            p if p <= 0.0 => writeln!(out, "Start: always on the draw")?,
+            _ => {}
+        }
+        if let Some(hate) = options.config.graveyard_hate {
+            writeln!(out, "Graveyard hate: {:?} from turn {}", hate.mode, hate.turn)?;
+        }
+        for effect in &options.config.replacement_effects.0 {
+            writeln!(out, "Replacement effect: {:?}", effect)?;
+        }
+        if options.config.opponent_counters > 0 {
+            writeln!(out, "Opponent counterspells for Spider-Man: {}", options.config.opponent_counters)?;
+        }
+        if options.config.opening_hand_size != DEFAULT_OPENING_HAND_SIZE {
+            writeln!(out, "Opening hand size: {}", options.config.opening_hand_size)?;
+        }
+        if options.config.land_policy == LandPolicy::FixColorsFirst {
+            writeln!(out, "Land policy: fix colors first")?;
+        }
+        writeln!(out)?;
+    }
+
+    if let Some(path) = &options.scenario_file {
+        return run_scenario_file(out, db, &deck, path, options);
+    }
+
+    // Play one game, reshuffling with new seeds until the opening hand has the required cards
+    let play_game = |seed: u64, verbose: bool| -> (GameResult, u32) {
+        if options.require_in_hand.is_empty() {
+            return (run_game_with_config(&deck, seed, db, verbose, &options.config), 0);
+        }
+        match find_seed_with_opening_hand(&deck, seed, &options.require_in_hand, options.config.opening_hand_size) {
+            Some((hand_seed, reshuffles)) => (run_game_with_config(&deck, hand_seed, db, verbose, &options.config), reshuffles),
+            None => {
+                eprintln!("✗ No opening hand with the required cards after {} reshuffles", MAX_OPENING_HAND_RESHUFFLES);
+                std::process::exit(1);
+            }
+        }
     };
 
-    println!("\n=== MTG Reanimator Simulator ===\n");
-    println!("Deck: {} ({} cards)", deck_file, deck.len());
-    println!("Games: {}", num_games);
-    if let Some(s) = seed {
-        println!("Seed: {}", s);
+    // Play one game, writing its log to `out` when verbose
+    let play_logged = |out: &mut dyn Write, seed: u64, verbose: bool| -> io::Result<(GameResult, u32)> {
+        if !verbose {
+            return Ok(play_game(seed, false));
+        }
+        let (played, log) = mtg_reanimator::log::capture(|| play_game(seed, true));
+        out.write_all(log.as_bytes())?;
+        Ok(played)
+    };
+
+    if let Some(stop) = &options.auto {
+        let base_seed = seed.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64
+        });
+        let start = std::time::Instant::now();
+        let stats = run_until_converged(stop, base_seed, |seed| play_game(seed, false).0);
+        let elapsed = start.elapsed();
+        if options.summary_only {
+            write!(out, "{}", stats.summary().format_key_value())?;
+            return Ok(());
+        }
+        let (low, high) = stats.win_rate_ci();
+
+        if stop.converged(&stats) {
+            writeln!(out, "Converged after {} games", stats.games)?;
+        } else {
+            writeln!(out, "Stopped at the {}-game cap before converging", stats.games)?;
+        }
+        writeln!(out, "Win rate: {:.2}% (95% CI {:.2}-{:.2}%, ±{:.2}%)",
+            stats.win_rate() * 100.0, low * 100.0, high * 100.0, stats.ci_half_width() * 100.0)?;
+        writeln!(out, "Average win turn: {:.2}", stats.avg_win_turn())?;
+        writeln!(out, "\nCompleted in {:.2?} ({:.0} games/sec)",
+            elapsed, stats.games as f64 / elapsed.as_secs_f64())?;
+        return Ok(());
+    }
+
+    // Seed of every game, in order
+    let game_seeds: Vec<u64> = match (&options.seeds, seed) {
+        (Some(seeds), _) => seeds.clone(),
+        (None, Some(base_seed)) => (0..num_games).map(|i| base_seed + i as u64).collect(),
+        (None, None) => {
+            let base_seed = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64;
+            if verbose {
+                writeln!(out, "Seed: {}", base_seed)?;
+            }
+            (0..num_games).map(|i| base_seed.wrapping_add(i as u64)).collect()
+        }
+    };
+
+    // Only the summary is wanted (--summary-only): fold each thread's games into running
+    // totals instead of keeping every result. Every other run still collects the full Vec,
+    // since the report needs per-game results.
+    let keeps_results = options.seeds_csv.is_some() || options.bincode.is_some() || options.curate.is_some();
+    let sequential = verbose || (seed.is_some() && options.seeds.is_none());
+    if options.summary_only && !keeps_results && !sequential {
+        let summary = game_seeds.par_iter()
+            .fold(RunningStats::default, |mut stats, &seed| {
+                stats.push(&play_game(seed, false).0);
+                stats
+            })
+            .reduce(RunningStats::default, RunningStats::merge)
+            .summary();
+        write!(out, "{}", summary.format_key_value())?;
+        return Ok(());
     }
-    println!();
 
     let start = std::time::Instant::now();
-    let results: Vec<_> = if let Some(base_seed) = seed {
-        // Sequential with fixed seed
-        (0..num_games)
-            .map(|i| run_game(&deck, base_seed + i as u64, db, verbose && i == 0))
-            .collect()
-    } else if verbose {
-        // Sequential for verbose mode (verbose only makes sense for first game)
-        let seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64;
-        println!("Seed: {}", seed);
-        (0..num_games)
-            .map(|i| run_game(&deck, seed.wrapping_add(i as u64), db, i == 0))
-            .collect()
+    let played: Vec<(GameResult, u32)> = if sequential {
+        // Sequential with a fixed seed or a log (verbose only makes sense for first game)
+        game_seeds.iter()
+            .enumerate()
+            .map(|(i, &seed)| play_logged(out, seed, verbose && i == 0))
+            .collect::<io::Result<_>>()?
     } else {
-        // Parallel with random seeds
-        (0..num_games)
-            .into_par_iter()
-            .map(|i| {
-                let seed = (std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_nanos() as u64)
-                    .wrapping_add(i as u64);
-                run_game(&deck, seed, db, false)
-            })
-            .collect()
+        game_seeds.par_iter().map(|&seed| play_game(seed, false)).collect()
     };
     let elapsed = start.elapsed();
+    let total_reshuffles: u64 = played.iter().map(|(_, r)| *r as u64).sum();
+    let results: Vec<GameResult> = played.into_iter().map(|(r, _)| r).collect();
+
+    if let (Some(seeds), Some(path)) = (&options.seeds, &options.seeds_csv) {
+        if let Err(e) = std::fs::write(path, format_seeds_csv(seeds, &results)) {
+            eprintln!("✗ Failed to write '{}': {}", path, e);
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(path) = &options.bincode {
+        if let Err(e) = write_results_file(path, &results) {
+            eprintln!("✗ Failed to write '{}': {}", path, e);
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(dir) = &options.curate {
+        let path = std::path::Path::new(dir).join("curated.txt");
+        let written = std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&path, format_curated(&curate(&game_seeds, &results))));
+        if let Err(e) = written {
+            eprintln!("✗ Failed to write '{}': {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
 
     // Calculate statistics
-    let wins: Vec<_> = results.iter().filter(|r| r.win_turn.is_some()).collect();
-    let win_rate = wins.len() as f64 / num_games as f64;
+    let summary = RunSummary::from_results(&results);
+    if options.summary_only {
+        write!(out, "{}", summary.format_key_value())?;
+        return Ok(());
+    }
 
-    let avg_win_turn = if !wins.is_empty() {
-        wins.iter().map(|r| r.win_turn.unwrap() as f64).sum::<f64>() / wins.len() as f64
-    } else {
-        0.0
-    };
+    let avg_reshuffles = (!options.require_in_hand.is_empty()).then(|| total_reshuffles as f64 / num_games as f64);
+    print_results(out, &results, &summary, avg_reshuffles)?;
 
-    // Turn distribution
-    let mut turn_dist: HashMap<u32, usize> = HashMap::new();
-    for r in &results {
-        if let Some(turn) = r.win_turn {
-            *turn_dist.entry(turn).or_insert(0) += 1;
+    if let Some(seeds) = &options.seeds {
+        writeln!(out, "\nPer-seed results:")?;
+        for (seed, result) in seeds.iter().zip(&results) {
+            match result.win_turn {
+                Some(turn) => writeln!(out, "  Seed {}: won on turn {}", seed, turn)?,
+                None => writeln!(out, "  Seed {}: no win", seed)?,
+            }
         }
     }
 
-    // Mana color availability
-    let has_ubg: Vec<_> = results.iter().filter(|r| r.turn_with_ubg.is_some()).collect();
-    let avg_ubg_turn = if !has_ubg.is_empty() {
-        has_ubg
-            .iter()
-            .map(|r| r.turn_with_ubg.unwrap() as f64)
-            .sum::<f64>()
-            / has_ubg.len() as f64
+    writeln!(out)?;
+    writeln!(
+        out,
+        "Simulation completed in {:.2?} ({:.0} games/sec)",
+        elapsed,
+        num_games as f64 / elapsed.as_secs_f64()
+    )?;
+
+    if options.profile {
+        let base_seed = seed.unwrap_or(0);
+        let ((), profile) = mtg_reanimator::profile::collect(|| {
+            for i in 0..PROFILE_GAMES.min(num_games) {
+                play_game(base_seed.wrapping_add(i as u64), false);
+            }
+        });
+        print_profile(out, &profile)?;
+    }
+
+    Ok(())
+}
+
+fn report_results(out: &mut dyn Write, path: &str) -> io::Result<()> {
+    let results = match read_results_file(path) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("✗ Failed to load results file '{}': {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    writeln!(out, "\n=== MTG Reanimator Report ===\n")?;
+    writeln!(out, "Results: {} ({} games)\n", path, results.len())?;
+    print_results(out, &results, &RunSummary::from_results(&results), None)
+}
+
+/// Print the aggregate results of a batch (shared by `run` and `report`)
+fn print_results(out: &mut dyn Write, results: &[GameResult], summary: &RunSummary, avg_reshuffles: Option<f64>) -> io::Result<()> {
+    let num_games = results.len();
+    let utilizations: Vec<f64> = results.iter().filter_map(|r| r.mana_utilization).collect();
+    let avg_utilization = if !utilizations.is_empty() {
+        utilizations.iter().sum::<f64>() / utilizations.len() as f64
     } else {
         0.0
     };
 
-    println!("=== Results ===\n");
-    println!("Win rate: {:.1}% ({}/{})", win_rate * 100.0, wins.len(), num_games);
-    println!("Average win turn: {:.2}", avg_win_turn);
-    println!("Average UBG available: turn {:.2}", avg_ubg_turn);
-    println!();
+    writeln!(out, "=== Results ===\n")?;
+    writeln!(out, "Win rate: {:.1}% ({}/{})", summary.win_rate() * 100.0, summary.wins, num_games)?;
+    writeln!(out, "Average win turn: {:.2}", summary.avg_win_turn)?;
+    let mill_wins = results.iter().filter(|r| r.win_line == Some(WinLine::MillOpponent)).count();
+    if mill_wins > 0 {
+        writeln!(out, "Wins by milling opponent: {}", mill_wins)?;
+    }
+    let reanimated: Vec<u32> = results.iter().filter_map(|r| r.reanimated_count).collect();
+    if !reanimated.is_empty() {
+        let avg = reanimated.iter().sum::<u32>() as f64 / reanimated.len() as f64;
+        writeln!(out, "Average creatures reanimated on the winning turn: {:.2} ({} wins)", avg, reanimated.len())?;
+    }
+    let damage_wins: Vec<(u32, u32)> = results.iter()
+        .filter_map(|r| r.winning_turn_damage.zip(r.overkill))
+        .collect();
+    if !damage_wins.is_empty() {
+        let wins = damage_wins.len() as f64;
+        let avg_damage = damage_wins.iter().map(|(damage, _)| *damage).sum::<u32>() as f64 / wins;
+        let avg_overkill = damage_wins.iter().map(|(_, overkill)| *overkill).sum::<u32>() as f64 / wins;
+        writeln!(out, "Average damage on the winning turn: {:.1} ({:.1} overkill)", avg_damage, avg_overkill)?;
+    }
+    let decked_out = results.iter().filter(|r| r.loss_reason == Some(LossReason::DeckedOut)).count();
+    if decked_out > 0 {
+        writeln!(out, "Losses to decking out: {}", decked_out)?;
+    }
+    writeln!(out, "Average UBG available: turn {:.2}", summary.avg_ubg_turn)?;
+    if summary.avg_first_spell_turn > 0.0 {
+        writeln!(out, "Average first spell cast: turn {:.2}", summary.avg_first_spell_turn)?;
+    }
+    writeln!(out, "Average mana utilization: {:.1}%", avg_utilization * 100.0)?;
+    let flooded = results.iter().filter(|r| r.flooded).count();
+    let screwed = results.iter().filter(|r| r.screwed).count();
+    writeln!(out, "Flooded (2+ more lands than spells drawn by turn 4): {:.1}%", flooded as f64 / num_games as f64 * 100.0)?;
+    writeln!(out, "Screwed (missed a land drop on turns 1-3): {:.1}%", screwed as f64 / num_games as f64 * 100.0)?;
+    if let Some(avg) = avg_reshuffles {
+        writeln!(out, "Average reshuffles for required hand: {:.2}", avg)?;
+    }
+    writeln!(out)?;
 
-    println!("Turn distribution:");
-    let mut turns: Vec<_> = turn_dist.iter().collect();
-    turns.sort_by_key(|(t, _)| *t);
-    for (turn, count) in turns {
+    writeln!(out, "Turn distribution (won on turn, won by turn):")?;
+    for ((turn, count), (_, cumulative)) in summary.turn_distribution.iter().zip(summary.cumulative_win_rates()) {
         let pct = *count as f64 / num_games as f64 * 100.0;
         let bar = "█".repeat((pct / 2.0) as usize);
-        println!("  Turn {:2}: {:5.1}% {} ({})", turn, pct, bar, count);
+        writeln!(out, "  Turn {:2}: {:5.1}% {:5.1}% {} ({})", turn, pct, cumulative * 100.0, bar, count)?;
     }
 
     let no_win = results.iter().filter(|r| r.win_turn.is_none()).count();
     if no_win > 0 {
         let pct = no_win as f64 / num_games as f64 * 100.0;
-        println!("  No win: {:5.1}% ({})", pct, no_win);
+        writeln!(out, "  No win: {:5.1}% ({})", pct, no_win)?;
     }
 
-    println!();
-    println!(
-        "Simulation completed in {:.2?} ({:.0} games/sec)",
-        elapsed,
-        num_games as f64 / elapsed.as_secs_f64()
-    );
+    let mut exiled_counts: HashMap<&str, usize> = HashMap::new();
+    for name in results.iter().flat_map(|r| &r.exiled_cards) {
+        *exiled_counts.entry(name).or_insert(0) += 1;
+    }
+    if !exiled_counts.is_empty() {
+        writeln!(out, "\nMost commonly exiled at game end (copies per game):\n")?;
+        let mut exiled: Vec<_> = exiled_counts.into_iter().collect();
+        exiled.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        for (name, count) in exiled.into_iter().take(5) {
+            writeln!(out, "  {:30} {:5.2} ({})", name, count as f64 / num_games as f64, count)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_profile(out: &mut dyn Write, profile: &mtg_reanimator::profile::Profile) -> io::Result<()> {
+    use mtg_reanimator::profile::Section;
+
+    writeln!(out, "\nProfile ({} games, single-threaded, {:.2?}):", profile.games, profile.total)?;
+    let total = profile.total.as_secs_f64().max(f64::MIN_POSITIVE);
+    let mut rows: Vec<(&str, std::time::Duration)> = Section::ALL.iter()
+        .map(|section| (section.label(), profile.section(*section)))
+        .collect();
+    rows.push(("Other", profile.other()));
+    for (label, time) in rows {
+        writeln!(out, "  {:<15} {:>10.2?} {:5.1}%", label, time, time.as_secs_f64() / total * 100.0)?;
+    }
+    Ok(())
 }
 
-fn compare_decks(db: &CardDatabase, deck1_file: &str, deck2_file: &str, num_games: usize) {
-    println!("\n=== MTG Deck Comparison ===\n");
-    println!("Deck 1: {}", deck1_file);
-    println!("Deck 2: {}", deck2_file);
-    println!("Games per deck: {}", num_games);
-    println!();
+fn run_scenario_file(out: &mut dyn Write, db: &CardDatabase, deck: &[card::Card], path: &str, options: &RunOptions) -> io::Result<()> {
+    let scenarios = match parse_scenario_file(path) {
+        Ok(scenarios) => scenarios,
+        Err(e) => {
+            eprintln!("✗ Failed to load scenario file '{}': {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    let seed = options.seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+    });
+
+    let start = std::time::Instant::now();
+    let results = run_scenarios(&scenarios, deck, seed, options.num_games, db, &options.config);
+    let elapsed = start.elapsed();
+    let overall = weighted_win_rate(&results);
+
+    if options.summary_only {
+        writeln!(out, "win_rate: {:.1}%", overall * 100.0)?;
+        return Ok(());
+    }
+
+    let total_weight: f64 = results.iter().map(|r| r.weight).sum();
+    writeln!(out, "=== Scenarios ({}) ===\n", path)?;
+    writeln!(out, "{:30} {:>7} {:>9}", "Scenario", "Weight", "Win rate")?;
+    writeln!(out, "{:-<48}", "")?;
+    for result in &results {
+        writeln!(
+            out,
+            "{:30} {:>6.1}% {:>8.1}%",
+            result.name,
+            result.weight / total_weight * 100.0,
+            result.win_rate() * 100.0
+        )?;
+    }
+    writeln!(out, "{:-<48}", "")?;
+    writeln!(out, "{:30} {:>7} {:>8.1}%", "Weighted win rate", "", overall * 100.0)?;
+
+    writeln!(out, "\nCompleted in {:.2?}", elapsed)?;
+
+    Ok(())
+}
+
+fn compare_decks(
+    out: &mut dyn Write,
+    db: &CardDatabase,
+    deck1_file: &str,
+    deck2_file: &str,
+    num_games: usize,
+    assert_different: bool,
+) -> io::Result<()> {
+    writeln!(out, "\n=== MTG Deck Comparison ===\n")?;
+    writeln!(out, "Deck 1: {}", deck1_file)?;
+    writeln!(out, "Deck 2: {}", deck2_file)?;
+    writeln!(out, "Games per deck: {}", num_games)?;
+    writeln!(out)?;
 
     let deck1 = match parse_deck_file(deck1_file, db) {
         Ok(deck) => deck,
@@ -295,10 +1039,25 @@ fn compare_decks(db: &CardDatabase, deck1_file: &str, deck2_file: &str, num_game
         }
     };
 
+    let diff = diff_decks(&deck1, &deck2);
+    let identical = diff.is_empty();
+    if identical && assert_different {
+        eprintln!("✗ '{}' and '{}' are the same deck (only the card order differs)", deck1_file, deck2_file);
+        std::process::exit(1);
+    }
+    writeln!(out, "=== Differences (Deck 1 -> Deck 2) ===\n")?;
+    if identical {
+        writeln!(out, "  (identical lists)")?;
+    }
+    for line in &diff {
+        writeln!(out, "  {}", line)?;
+    }
+    writeln!(out)?;
+
     let start = std::time::Instant::now();
 
     // Run deck 1
-    println!("Running deck 1...");
+    writeln!(out, "Running deck 1...")?;
     let results1: Vec<_> = (0..num_games)
         .into_par_iter()
         .map(|i| {
@@ -312,7 +1071,7 @@ fn compare_decks(db: &CardDatabase, deck1_file: &str, deck2_file: &str, num_game
         .collect();
 
     // Run deck 2
-    println!("Running deck 2...");
+    writeln!(out, "Running deck 2...")?;
     let results2: Vec<_> = (0..num_games)
         .into_par_iter()
         .map(|i| {
@@ -345,68 +1104,150 @@ fn compare_decks(db: &CardDatabase, deck1_file: &str, deck2_file: &str, num_game
         0.0
     };
 
-    println!("\n=== Results ===\n");
-    println!(
+    writeln!(out, "\n=== Results ===\n")?;
+    writeln!(
+        out,
         "{:20} {:>12} {:>12}",
         "Metric", deck1_file, deck2_file
-    );
-    println!("{:-<50}", "");
-    println!(
+    )?;
+    writeln!(out, "{:-<50}", "")?;
+    writeln!(
+        out,
         "{:20} {:>11.1}% {:>11.1}%",
         "Win rate",
         win_rate1 * 100.0,
         win_rate2 * 100.0
-    );
-    println!(
+    )?;
+    writeln!(
+        out,
         "{:20} {:>12.2} {:>12.2}",
         "Avg win turn", avg_win1, avg_win2
-    );
+    )?;
 
     // Determine winner
-    println!();
+    writeln!(out)?;
     if win_rate1 > win_rate2 {
-        println!(
+        writeln!(
+            out,
             "✓ {} has {:.1}% higher win rate",
             deck1_file,
             (win_rate1 - win_rate2) * 100.0
-        );
+        )?;
     } else if win_rate2 > win_rate1 {
-        println!(
+        writeln!(
+            out,
             "✓ {} has {:.1}% higher win rate",
             deck2_file,
             (win_rate2 - win_rate1) * 100.0
-        );
+        )?;
     } else {
-        println!("Both decks have the same win rate");
+        writeln!(out, "Both decks have the same win rate")?;
     }
 
     if avg_win1 < avg_win2 && avg_win1 > 0.0 {
-        println!(
+        writeln!(
+            out,
             "✓ {} wins {:.2} turns faster on average",
             deck1_file,
             avg_win2 - avg_win1
-        );
+        )?;
     } else if avg_win2 < avg_win1 && avg_win2 > 0.0 {
-        println!(
+        writeln!(
+            out,
             "✓ {} wins {:.2} turns faster on average",
             deck2_file,
             avg_win1 - avg_win2
-        );
+        )?;
     }
+    if identical {
+        writeln!(out, "Note: both files are the same deck, so any difference above is sampling noise")?;
+    }
+
+    writeln!(out, "\nCompleted in {:.2?}", elapsed)?;
 
-    println!("\nCompleted in {:.2?}", elapsed);
+    Ok(())
 }
 
-fn optimize_lands(db: &CardDatabase, num_configs: usize, games_per_config: usize, strategy: &str, deck_file: &str) {
-    use simulation::optimize::{generate_random_land_config_weighted, generate_random_land_config_shuffle, build_deck_from_config_with_fixed, config_to_string, save_deck_to_file, DeckSaveParams, extract_fixed_cards_from_deck};
-    use crate::rng::GameRng;
+fn run_deck_tournament(
+    out: &mut dyn Write,
+    db: &CardDatabase,
+    dir: &str,
+    num_games: usize,
+    seed: Option<u64>,
+    opponent_clock: u32,
+) -> io::Result<()> {
+    use simulation::tournament::{run_tournament, BOOTSTRAP_ROUNDS};
+
+    let mut paths: Vec<_> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect(),
+        Err(e) => {
+            eprintln!("✗ Failed to read deck directory '{}': {}", dir, e);
+            std::process::exit(1);
+        }
+    };
+    paths.sort();
+    if paths.len() < 2 {
+        eprintln!("✗ A tournament needs at least two deck files in '{}'", dir);
+        std::process::exit(1);
+    }
+    let decks: Vec<(String, Vec<card::Card>)> = paths
+        .iter()
+        .map(|path| {
+            let path = path.to_string_lossy();
+            (path.to_string(), load_deck(db, &path))
+        })
+        .collect();
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+    });
+
+    writeln!(out, "\n=== Deck Tournament ===\n")?;
+    writeln!(out, "Decks: {} from {}", decks.len(), dir)?;
+    writeln!(out, "Games per deck: {} (won by turn {})", num_games, opponent_clock)?;
+    writeln!(out, "Seed: {}\n", seed)?;
+
+    let start = std::time::Instant::now();
+    let config = GameConfig { opponent_clock: Some(opponent_clock), ..GameConfig::default() };
+    let entries = run_tournament(&decks, num_games, seed, db, &config);
+    let elapsed = start.elapsed();
+
+    let width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0).max(4);
+    writeln!(out, "{:>4}  {:width$}  {:>9}  {:>17}  {:>8}  {:>7}", "Rank", "Deck", "Win rate", "95% CI", "Avg turn", "P(best)")?;
+    for (rank, entry) in entries.iter().enumerate() {
+        let (low, high) = entry.summary.win_rate_ci();
+        writeln!(
+            out,
+            "{:>4}  {:width$}  {:>8.1}%  {:>7.1}% - {:>5.1}%  {:>8.2}  {:>6.1}%",
+            rank + 1,
+            entry.name,
+            entry.summary.win_rate() * 100.0,
+            low * 100.0,
+            high * 100.0,
+            entry.summary.avg_win_turn,
+            entry.p_best * 100.0,
+        )?;
+    }
+    writeln!(out, "\nP(best): share of {} bootstrap resamples in which the deck had the top win rate", BOOTSTRAP_ROUNDS)?;
+    writeln!(out, "\nCompleted in {:.2?}", elapsed)?;
+    Ok(())
+}
+
+fn optimize_lands(out: &mut dyn Write, db: &CardDatabase, num_configs: usize, games_per_config: usize, strategy: &str, deck_file: &str) -> io::Result<()> {
+    use simulation::optimize::{generate_random_land_config_weighted, generate_random_land_config_shuffle, build_deck_from_config_with_fixed, config_to_string, draw_unique_config, save_deck_to_file, DeckSaveParams, extract_fixed_cards_from_deck};
+    use mtg_reanimator::rng::GameRng;
 
     let strategy_desc = match strategy {
         "weighted" => "Random counts for each land type, respecting max limits",
         "shuffle" => "Pool of max copies shuffled, take first 24",
         _ => {
             eprintln!("Unknown strategy '{}'. Use 'weighted' or 'shuffle'.", strategy);
-            return;
+            return Ok(());
         }
     };
 
@@ -415,35 +1256,39 @@ fn optimize_lands(db: &CardDatabase, num_configs: usize, games_per_config: usize
         Ok(cards) => cards,
         Err(e) => {
             eprintln!("Failed to parse deck file '{}': {}", deck_file, e);
-            return;
+            return Ok(());
         }
     };
 
     let fixed_card_count: usize = fixed_cards.iter().map(|(_, count)| count).sum();
 
-    println!("\n=== MTG Land Optimization ===\n");
-    println!("Base deck: {}", deck_file);
-    println!("Strategy: {}", strategy);
-    println!("  - {}\n", strategy_desc);
-    println!("Testing {} random land configurations", num_configs);
-    println!("Running {} games per configuration...\n", games_per_config);
-    println!("Fixed non-land cards: {} cards", fixed_card_count);
-    println!("Land slots to fill: 24 cards\n");
+    writeln!(out, "\n=== MTG Land Optimization ===\n")?;
+    writeln!(out, "Base deck: {}", deck_file)?;
+    writeln!(out, "Strategy: {}", strategy)?;
+    writeln!(out, "  - {}\n", strategy_desc)?;
+    writeln!(out, "Testing {} random land configurations", num_configs)?;
+    writeln!(out, "Running {} games per configuration...\n", games_per_config)?;
+    writeln!(out, "Fixed non-land cards: {} cards", fixed_card_count)?;
+    writeln!(out, "Land slots to fill: 24 cards\n")?;
 
     let mut best_config = None;
     let mut best_avg_turn = f64::INFINITY;
     let mut best_win_rate = 0.0;
     let mut best_turn_distribution: HashMap<u32, usize> = HashMap::new();
     let mut all_results: Vec<(simulation::optimize::LandConfig, f64, f64)> = Vec::new();
+    let mut seen_configs = HashSet::new();
 
     let start = std::time::Instant::now();
 
     for i in 0..num_configs {
-        // Generate random land configuration using selected strategy
+        // Generate a random land configuration we haven't tested yet using selected strategy
         let mut rng = GameRng::new(None);
-        let config = match strategy {
+        let Some(config) = draw_unique_config(&mut seen_configs, || match strategy {
             "shuffle" => generate_random_land_config_shuffle(&mut rng),
             _ => generate_random_land_config_weighted(&mut rng),
+        }) else {
+            writeln!(out, "No untested configurations left after {} unique configs", seen_configs.len())?;
+            break;
         };
 
         // Build deck from config using the fixed cards from the deck file
@@ -492,46 +1337,46 @@ fn optimize_lands(db: &CardDatabase, num_configs: usize, games_per_config: usize
                 }
             }
 
-            println!("[{}/{}] New best! Avg turn: {:.3}, Win rate: {:.1}%",
-                i + 1, num_configs, best_avg_turn, best_win_rate * 100.0);
-            println!("  Lands: {}\n", config_to_string(&config));
+            writeln!(out, "[{}/{}] New best! Avg turn: {:.3}, Win rate: {:.1}%",
+                i + 1, num_configs, best_avg_turn, best_win_rate * 100.0)?;
+            writeln!(out, "  Lands: {}\n", config_to_string(&config))?;
         }
 
         // Progress update every 100 configs
         if (i + 1) % 100 == 0 {
             let elapsed = start.elapsed().as_secs_f64();
             let eta = (elapsed / (i + 1) as f64) * (num_configs - i - 1) as f64;
-            println!("Progress: {}/{} ({:.1}%) - ETA: {:.0}s",
-                i + 1, num_configs, (i + 1) as f64 / num_configs as f64 * 100.0, eta);
+            writeln!(out, "Progress: {}/{} ({:.1}%) - ETA: {:.0}s",
+                i + 1, num_configs, (i + 1) as f64 / num_configs as f64 * 100.0, eta)?;
         }
     }
 
     let total_time = start.elapsed().as_secs_f64();
 
-    println!("\n=== Optimization Complete ===");
-    println!("Total time: {:.1}s", total_time);
-    println!("Configurations tested: {}", num_configs);
-    println!("Games per config: {}", games_per_config);
-    println!("Total games: {}\n", num_configs * games_per_config);
+    writeln!(out, "\n=== Optimization Complete ===")?;
+    writeln!(out, "Total time: {:.1}s", total_time)?;
+    writeln!(out, "Unique configurations tested: {}", all_results.len())?;
+    writeln!(out, "Games per config: {}", games_per_config)?;
+    writeln!(out, "Total games: {}\n", all_results.len() * games_per_config)?;
 
-    println!("=== BEST LAND CONFIGURATION ===");
-    println!("Average win turn: {:.3}", best_avg_turn);
-    println!("Win rate: {:.1}%", best_win_rate * 100.0);
-    println!("\nLand breakdown:");
+    writeln!(out, "=== BEST LAND CONFIGURATION ===")?;
+    writeln!(out, "Average win turn: {:.3}", best_avg_turn)?;
+    writeln!(out, "Win rate: {:.1}%", best_win_rate * 100.0)?;
+    writeln!(out, "\nLand breakdown:")?;
     if let Some(config) = &best_config {
         let mut lands: Vec<_> = config.iter().filter(|(_, count)| **count > 0).collect();
         lands.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
         for (name, count) in lands {
-            println!("  {} {}", count, name);
+            writeln!(out, "  {} {}", count, name)?;
         }
     }
 
     // Show top 10 configurations
-    println!("\n=== Top 10 Configurations ===");
+    writeln!(out, "\n=== Top 10 Configurations ===")?;
     all_results.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal));
     for (i, (config, win_rate, avg_turn)) in all_results.iter().take(10).enumerate() {
-        println!("[{}] Avg turn: {:.3}, Win rate: {:.1}%", i + 1, avg_turn, win_rate * 100.0);
-        println!("    {}", config_to_string(config));
+        writeln!(out, "[{}] Avg turn: {:.3}, Win rate: {:.1}%", i + 1, avg_turn, win_rate * 100.0)?;
+        writeln!(out, "    {}", config_to_string(config))?;
     }
 
     // Save best deck to file with all optimization metadata
@@ -545,14 +1390,24 @@ fn optimize_lands(db: &CardDatabase, num_configs: usize, games_per_config: usize
             fixed_cards: &fixed_cards,
         };
         match save_deck_to_file(config, &params) {
-            Ok(filename) => println!("\nBest deck saved to: {}", filename),
+            Ok(filename) => writeln!(out, "\nBest deck saved to: {}", filename)?,
             Err(e) => eprintln!("\nFailed to save deck: {}", e),
         }
     }
+
+    Ok(())
 }
 
-fn analyze_turn4_failures(db: &CardDatabase, deck_file: &str, num_games: usize, seed: Option<u64>) {
-    use simulation::analyze::{run_game_to_turn4, aggregate_results, FailureReason};
+fn analyze_turn4_failures(
+    out: &mut dyn Write,
+    db: &CardDatabase,
+    deck_file: &str,
+    num_games: usize,
+    seed: Option<u64>,
+    seeds: Option<&[u64]>,
+) -> io::Result<()> {
+    use simulation::analyze::{run_game_to_turn4, replay_held_combo, aggregate_results, first_combo_ready_turn,
+        ComboTurnPercentiles, FailureReason, TurnCastability, COMBO_HORIZON, GY_CREATURE_BUCKETS};
 
     let deck = match parse_deck_file(deck_file, db) {
         Ok(deck) => deck,
@@ -562,22 +1417,33 @@ fn analyze_turn4_failures(db: &CardDatabase, deck_file: &str, num_games: usize,
         }
     };
 
-    println!("\n=== Turn 4 Combo Failure Analysis ===\n");
-    println!("Deck: {} ({} cards)", deck_file, deck.len());
-    println!("Games: {}", num_games);
+    let num_games = seeds.map_or(num_games, |seeds| seeds.len());
+    writeln!(out, "\n=== Turn 4 Combo Failure Analysis ===\n")?;
+    writeln!(out, "Deck: {} ({} cards)", deck_file, deck.len())?;
+    writeln!(out, "Games: {}", num_games)?;
     if let Some(s) = seed {
-        println!("Seed: {}", s);
+        writeln!(out, "Seed: {}", s)?;
+    }
+    if seeds.is_some() {
+        writeln!(out, "Seeds: from file")?;
     }
-    println!();
+    writeln!(out)?;
 
     let start = std::time::Instant::now();
 
-    // Run games in parallel
-    let analyses: Vec<_> = if let Some(base_seed) = seed {
+    // Run games in parallel, replaying games that held a non-lethal combo under fire-when-assembled,
+    // and finding the first turn each could have comboed
+    let analyze_game = |seed: u64| (
+        (run_game_to_turn4(&deck, seed, db), replay_held_combo(&deck, seed, db)),
+        first_combo_ready_turn(&deck, seed, db, COMBO_HORIZON),
+    );
+    let ((analyses, replays), combo_turns): ((Vec<_>, Vec<_>), Vec<_>) = if let Some(seeds) = seeds {
+        seeds.par_iter().map(|&seed| analyze_game(seed)).unzip()
+    } else if let Some(base_seed) = seed {
         (0..num_games)
             .into_par_iter()
-            .map(|i| run_game_to_turn4(&deck, base_seed + i as u64, db))
-            .collect()
+            .map(|i| analyze_game(base_seed + i as u64))
+            .unzip()
     } else {
         (0..num_games)
             .into_par_iter()
@@ -587,43 +1453,39 @@ fn analyze_turn4_failures(db: &CardDatabase, deck_file: &str, num_games: usize,
                     .unwrap()
                     .as_nanos() as u64)
                     .wrapping_add(i as u64);
-                run_game_to_turn4(&deck, seed, db)
+                analyze_game(seed)
             })
-            .collect()
+            .unzip()
     };
+    let replays: Vec<_> = replays.into_iter().flatten().collect();
 
     let elapsed = start.elapsed();
 
     // Aggregate results
     let results = aggregate_results(&analyses);
 
-    println!("=== Results ===\n");
+    writeln!(out, "=== Results ===\n")?;
 
     // Sort failures by count (descending)
     let mut failures: Vec<_> = results.failure_counts.iter().collect();
     failures.sort_by(|a, b| b.1.cmp(a.1));
 
     // Print ranked failure reasons
-    println!("Failure Reasons (ranked by frequency):\n");
+    writeln!(out, "Failure Reasons (ranked by frequency):\n")?;
     for (reason, count) in &failures {
         let pct = **count as f64 / num_games as f64 * 100.0;
         let bar = "█".repeat((pct / 2.0) as usize);
 
-        if **reason == FailureReason::ComboAvailable {
-            println!("  {:30} {:5.1}% {} ({})",
-                format!("{}", reason), pct, bar, count);
-        } else {
-            println!("  {:30} {:5.1}% {} ({})",
-                format!("{}", reason), pct, bar, count);
-        }
+        writeln!(out, "  {:30} {:5.1}% {} ({})",
+            format!("{}", reason), pct, bar, count)?;
     }
 
-    println!("\n--- Statistics ---\n");
-    println!("Average lands by turn 4: {:.2}", results.avg_lands);
-    println!("Color availability:");
-    println!("  Blue:  {:5.1}%", results.color_availability.0);
-    println!("  Black: {:5.1}%", results.color_availability.1);
-    println!("  Green: {:5.1}%", results.color_availability.2);
+    writeln!(out, "\n--- Statistics ---\n")?;
+    writeln!(out, "Average lands by turn 4: {:.2}", results.avg_lands)?;
+    writeln!(out, "Color availability:")?;
+    writeln!(out, "  Blue:  {:5.1}%", results.color_availability.0)?;
+    writeln!(out, "  Black: {:5.1}%", results.color_availability.1)?;
+    writeln!(out, "  Green: {:5.1}%", results.color_availability.2)?;
 
     // Calculate additional stats from raw analyses
     let combo_ready = failures.iter()
@@ -631,15 +1493,201 @@ fn analyze_turn4_failures(db: &CardDatabase, deck_file: &str, num_games: usize,
         .map(|(_, c)| **c)
         .unwrap_or(0);
 
-    println!("\nTurn 4 combo ready: {:.1}% ({}/{})",
-        combo_ready as f64 / num_games as f64 * 100.0, combo_ready, num_games);
+    writeln!(out, "\nTurn 4 combo ready: {:.1}% ({}/{})",
+        combo_ready as f64 / num_games as f64 * 100.0, combo_ready, num_games)?;
+
+    let percentiles = ComboTurnPercentiles::from_turns(&combo_turns);
+    let turn = |t: Option<u32>| t.map_or(format!("not by turn {}", COMBO_HORIZON), |t| format!("by turn {}", t));
+    writeln!(out, "First combo-ready turn: 50% {}, 75% {}, 90% {}",
+        turn(percentiles.p50), turn(percentiles.p75), turn(percentiles.p90))?;
+    writeln!(out, "Never combo-ready by turn {}: {:.1}%", COMBO_HORIZON, percentiles.never * 100.0)?;
+
+    let failed_games = num_games - combo_ready;
+    if failed_games > 0 {
+        writeln!(out, "\nCounterfactual (failed games with one more land):\n")?;
+        let mut counterfactuals: Vec<_> = results.counterfactual_counts.iter().collect();
+        counterfactuals.sort_by(|a, b| b.1.cmp(a.1));
+        for (counterfactual, count) in counterfactuals {
+            let pct = *count as f64 / failed_games as f64 * 100.0;
+            writeln!(out, "  {:30} {:5.1}% ({})", format!("{}", counterfactual), pct, count)?;
+        }
+
+        writeln!(out, "\nCombo bottleneck (failed games, ranked):\n")?;
+        let mut bottlenecks: Vec<_> = results.bottleneck_counts.iter().collect();
+        bottlenecks.sort_by(|a, b| b.1.cmp(a.1));
+        for (bottleneck, count) in &bottlenecks {
+            let pct = **count as f64 / failed_games as f64 * 100.0;
+            writeln!(out, "  {:30} {:5.1}% ({})", format!("{}", bottleneck), pct, count)?;
+        }
+        let triad_complete = failed_games - bottlenecks.iter().map(|(_, count)| **count).sum::<usize>();
+        if triad_complete > 0 {
+            let pct = triad_complete as f64 / failed_games as f64 * 100.0;
+            writeln!(out, "  {:30} {:5.1}% ({})", "All pieces, not lethal", pct, triad_complete)?;
+        }
+    }
+
+    if combo_ready > 0 {
+        writeln!(out, "\nGraveyard creatures in combo-ready games:\n")?;
+        for (bucket, count) in results.graveyard_creature_histogram.iter().enumerate() {
+            let label = if bucket == GY_CREATURE_BUCKETS - 1 { format!("{}+", bucket) } else { bucket.to_string() };
+            let pct = *count as f64 / combo_ready as f64 * 100.0;
+            writeln!(out, "  {:4} {:5.1}% {} ({})", label, pct, "█".repeat((pct / 2.0) as usize), count)?;
+        }
+    }
+
+    if !results.exiled_counts.is_empty() {
+        writeln!(out, "\nMost commonly exiled by turn 4 (copies per game):\n")?;
+        let mut exiled: Vec<_> = results.exiled_counts.iter().collect();
+        exiled.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (name, count) in exiled.into_iter().take(5) {
+            writeln!(out, "  {:30} {:5.2} ({})", name, *count as f64 / num_games as f64, count)?;
+        }
+    }
+
+    if results.spider_man_binned_games > 0 {
+        writeln!(out, "\nNeeded Spider-Man milled or surveilled away by turn 4 (possible misplay): {:.1}% ({})",
+            results.spider_man_binned_games as f64 / num_games as f64 * 100.0, results.spider_man_binned_games)?;
+    }
+
+    if !results.draws_by_source.is_empty() {
+        writeln!(out, "\nCards drawn through turn 4 by source (per game):\n")?;
+        let mut draws: Vec<_> = results.draws_by_source.iter().collect();
+        draws.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (source, count) in draws {
+            writeln!(out, "  {:30} {:5.2} ({})", source, *count as f64 / num_games as f64, count)?;
+        }
+    }
+
+    writeln!(out, "\nCastability by turn (after land drop):\n")?;
+    writeln!(out, "  {:6} {:>10} {:>10} {:>10} {:>10}", "Turn", "Castable", "No spell", "Low mana", "Colors")?;
+    for (turn, counts) in results.castability_by_turn.iter().enumerate() {
+        let pct = |c: TurnCastability| *counts.get(&c).unwrap_or(&0) as f64 / num_games as f64 * 100.0;
+        writeln!(out, "  {:6} {:>9.1}% {:>9.1}% {:>9.1}% {:>9.1}%",
+            turn + 1,
+            pct(TurnCastability::Castable),
+            pct(TurnCastability::NoSpellInHand),
+            pct(TurnCastability::NotEnoughMana),
+            pct(TurnCastability::ColorScrew))?;
+    }
+
+    if !replays.is_empty() {
+        let faster = replays.iter().filter(|r| r.turns_saved() > 0).count();
+        let slower = replays.iter().filter(|r| r.turns_saved() < 0).count();
+        let net: i32 = replays.iter().map(|r| r.turns_saved()).sum();
+        writeln!(out, "\nHeld a castable non-lethal combo: {} games ({:.1}%)",
+            replays.len(), replays.len() as f64 / num_games as f64 * 100.0)?;
+        writeln!(out, "  If fired when assembled:")?;
+        writeln!(out, "    Won sooner:  {:5} ({:.1}%)", faster, faster as f64 / replays.len() as f64 * 100.0)?;
+        writeln!(out, "    Won later:   {:5} ({:.1}%)", slower, slower as f64 / replays.len() as f64 * 100.0)?;
+        writeln!(out, "    Net turns saved: {} ({:+.2} per held game)", net, net as f64 / replays.len() as f64)?;
+    }
+
+    writeln!(out, "\nCompleted in {:.2?} ({:.0} games/sec)",
+        elapsed, num_games as f64 / elapsed.as_secs_f64())?;
+
+    Ok(())
+}
+
+
+fn solve_board(out: &mut dyn Write, db: &CardDatabase, state_file: &str, num_games: usize, seed: Option<u64>) -> io::Result<()> {
+    use simulation::solve::{parse_board_file, solve};
+
+    let state = match parse_board_file(state_file).and_then(|board| board.to_state(db)) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("✗ Failed to load board '{}': {}", state_file, e);
+            std::process::exit(1);
+        }
+    };
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+    });
+
+    writeln!(out, "\n=== Solve From Board ===\n")?;
+    writeln!(out, "Board: {} (after turn {})", state_file, state.turn)?;
+    writeln!(out, "Hand: {} | Battlefield: {} | Graveyard: {} | Library: {}",
+        state.hand.size(), state.battlefield.permanents().len(), state.graveyard.size(), state.library.size())?;
+    writeln!(out, "Games: {}", num_games)?;
+    writeln!(out, "Seed: {}\n", seed)?;
+
+    let start = std::time::Instant::now();
+    let solved = solve(&state, num_games, seed, db);
+    let elapsed = start.elapsed();
+    let (low, high) = solved.stats.win_rate_ci();
+
+    writeln!(out, "Win rate: {:.1}% (95% CI {:.1}-{:.1}%)",
+        solved.stats.win_rate() * 100.0, low * 100.0, high * 100.0)?;
+    if let Some(fastest) = solved.fastest_win {
+        writeln!(out, "Average turns to win: {:.2}", solved.avg_remaining_turns())?;
+        writeln!(out, "Fastest win: {} turn(s)", fastest)?;
+    }
+
+    writeln!(out, "\nCompleted in {:.2?}", elapsed)?;
+
+    Ok(())
+}
+
+fn fuzz_deck(out: &mut dyn Write, db: &CardDatabase, deck_file: &str, iterations: usize, games: usize, seed: Option<u64>) -> io::Result<()> {
+    use simulation::fuzz::fuzz_decks;
+
+    let deck = load_deck(db, deck_file);
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+    });
+
+    writeln!(out, "\n=== Decklist Fuzz ===\n")?;
+    writeln!(out, "Base deck: {} ({} cards)", deck_file, deck.len())?;
+    writeln!(out, "Mutations: {} x {} games", iterations, games)?;
+    writeln!(out, "Seed: {}\n", seed)?;
+
+    let start = std::time::Instant::now();
+    let report = fuzz_decks(&deck, iterations, games, seed, db);
+    let elapsed = start.elapsed();
+
+    writeln!(out, "Games completed: {} (win rate {:.1}%)", report.games, report.win_rate() * 100.0)?;
+    if report.failures.is_empty() {
+        writeln!(out, "✓ No panics")?;
+    } else {
+        writeln!(out, "✗ {} mutated deck(s) panicked:", report.failures.len())?;
+        for failure in &report.failures {
+            writeln!(out, "\n  Mutation {} (game seed {}): {}", failure.iteration, failure.seed, failure.message)?;
+            for line in diff_decks(&deck, &failure.deck) {
+                writeln!(out, "    {}", line)?;
+            }
+        }
+    }
 
-    println!("\nCompleted in {:.2?} ({:.0} games/sec)",
-        elapsed, num_games as f64 / elapsed.as_secs_f64());
+    writeln!(out, "\nCompleted in {:.2?}", elapsed)?;
+    if !report.failures.is_empty() {
+        out.flush()?;
+        std::process::exit(1);
+    }
+
+    Ok(())
 }
 
+fn dump_cards(out: &mut dyn Write, db: &CardDatabase, path: Option<&str>) -> io::Result<()> {
+    let json = db.to_json();
+    match path {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, json + "\n") {
+                eprintln!("✗ Failed to write '{}': {}", path, e);
+                std::process::exit(1);
+            }
+            eprintln!("✓ Wrote {} cards to {}", db.card_count(), path);
+        }
+        None => writeln!(out, "{}", json)?,
+    }
+    Ok(())
+}
 
-fn run_mana_sim(db: &CardDatabase, deck_file: &str, num_games: usize, max_turns: usize) {
+fn run_mana_sim(out: &mut dyn Write, db: &CardDatabase, deck_file: &str, num_games: usize, max_turns: usize) -> io::Result<()> {
     use simulation::mana_sim::{run_mana_simulation, print_mana_results};
 
     let deck = match parse_deck_file(deck_file, db) {
@@ -657,8 +1705,177 @@ fn run_mana_sim(db: &CardDatabase, deck_file: &str, num_games: usize, max_turns:
     let results = run_mana_simulation(&deck, num_games, max_turns, db);
     let elapsed = start.elapsed();
 
-    print_mana_results(&results, deck_file, deck_size, land_count);
+    print_mana_results(out, &results, deck_file, deck_size, land_count)?;
+
+    writeln!(out, "\nCompleted in {:.2?} ({:.0} games/sec)",
+        elapsed, num_games as f64 / elapsed.as_secs_f64())?;
+
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_file_rejects_options_it_ignores() {
+        use clap::Parser;
+
+        for flag in [&["--require-in-hand", "Forest"][..], &["--seeds-file", "seeds.txt"], &["--curate", "out"],
+            &["--bincode", "results.bin"], &["--record-exiled"], &["--auto"], &["--start", "play"], &["-v"], &["--profile"]]
+        {
+            let args = [&["mtg-reanimator", "run", "--scenario-file", "scenarios.json"][..], flag].concat();
+            let err = Cli::try_parse_from(args).err().expect("conflicting options should be rejected");
+            assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+        }
+        assert!(Cli::try_parse_from(["mtg-reanimator", "run", "--scenario-file", "scenarios.json", "--summary-only"]).is_ok());
+    }
 
-    println!("\nCompleted in {:.2?} ({:.0} games/sec)",
-        elapsed, num_games as f64 / elapsed.as_secs_f64());
-}
\ No newline at end of file
+    #[test]
+    fn test_run_simulation_output_captured() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let options = RunOptions {
+            num_games: 20,
+            seed: Some(42),
+            verbose: 0,
+            require_in_hand: Vec::new(),
+            summary_only: false,
+            scenario_file: None,
+            seeds: None,
+            seeds_csv: None,
+            curate: None,
+            bincode: None,
+            profile: false,
+            auto: None,
+            config: GameConfig::default(),
+        };
+
+        let mut buffer = Vec::new();
+        run_simulation(&mut buffer, &db, "deck.txt", &options).expect("writing to a Vec cannot fail");
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains("Deck: deck.txt (60 cards)"));
+        assert!(output.contains("Win rate"));
+        assert!(!output.contains("Most commonly exiled at game end"));
+
+        let options = RunOptions { config: GameConfig { record_exiled: true, ..GameConfig::default() }, ..options };
+        let mut buffer = Vec::new();
+        run_simulation(&mut buffer, &db, "deck.txt", &options).expect("writing to a Vec cannot fail");
+        assert!(String::from_utf8(buffer).unwrap().contains("Most commonly exiled at game end"));
+    }
+
+    #[test]
+    fn test_auto_summary_only_prints_key_value_lines() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let options = RunOptions {
+            num_games: 0,
+            seed: Some(42),
+            verbose: 0,
+            require_in_hand: Vec::new(),
+            summary_only: true,
+            scenario_file: None,
+            seeds: None,
+            seeds_csv: None,
+            curate: None,
+            bincode: None,
+            profile: false,
+            auto: Some(AutoStop { target_half_width: 1.0, max_games: 20, batch_size: 10 }),
+            config: GameConfig::default(),
+        };
+
+        let mut buffer = Vec::new();
+        run_simulation(&mut buffer, &db, "deck.txt", &options).expect("writing to a Vec cannot fail");
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.starts_with("win_rate: "));
+        assert!(!output.contains("Converged after"));
+        assert!(!output.contains("Completed in"));
+    }
+
+    #[test]
+    fn test_log_level_one_omits_mill_details() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let run_at = |level: u8| {
+            let options = RunOptions {
+                num_games: 1,
+                seed: Some(1),
+                verbose: level,
+                require_in_hand: Vec::new(),
+                summary_only: false,
+                scenario_file: None,
+                seeds: None,
+                seeds_csv: None,
+                curate: None,
+                bincode: None,
+                profile: false,
+                auto: None,
+                config: GameConfig::default(),
+            };
+            let mut buffer = Vec::new();
+            run_simulation(&mut buffer, &db, "deck.txt", &options).expect("writing to a Vec cannot fail");
+            String::from_utf8(buffer).unwrap()
+        };
+
+        let quiet = run_at(1);
+        let detailed = run_at(2);
+        mtg_reanimator::log::set_level(mtg_reanimator::log::MAX_LEVEL);
+
+        assert!(quiet.contains("=== TURN 1 ==="));
+        assert!(detailed.contains("=== TURN 1 ==="));
+        assert!(detailed.contains("Mill 4:"));
+        assert!(!quiet.contains("Mill 4:"));
+    }
+
+    #[test]
+    fn test_seeds_file_plays_exactly_those_seeds() {
+        let dir = std::env::temp_dir();
+        let seeds_path = dir.join(format!("mtg-seeds-{}.txt", std::process::id()));
+        let csv_path = dir.join(format!("mtg-seeds-{}.csv", std::process::id()));
+        std::fs::write(&seeds_path, "# curated\n42\n7\n1000\n").unwrap();
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let seeds = parse_seeds_file(seeds_path.to_str().unwrap()).unwrap();
+        let options = RunOptions {
+            num_games: seeds.len(),
+            seed: None,
+            verbose: 0,
+            require_in_hand: Vec::new(),
+            summary_only: false,
+            scenario_file: None,
+            seeds: Some(seeds),
+            seeds_csv: Some(csv_path.to_str().unwrap().to_string()),
+            curate: None,
+            bincode: None,
+            profile: false,
+            auto: None,
+            config: GameConfig::default(),
+        };
+        let mut buffer = Vec::new();
+        run_simulation(&mut buffer, &db, "deck.txt", &options).expect("writing to a Vec cannot fail");
+        let output = String::from_utf8(buffer).unwrap();
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        std::fs::remove_file(&seeds_path).ok();
+        std::fs::remove_file(&csv_path).ok();
+
+        assert!(output.contains("Games: 3"));
+        assert!(output.contains("Seed 7: won on turn 5"));
+        assert_eq!(csv, "seed,win_turn\n42,9\n7,5\n1000,7\n");
+    }
+
+    #[test]
+    fn test_compare_reordered_deck_notes_identical_lists() {
+        let reordered_path = std::env::temp_dir().join(format!("mtg-reordered-{}.txt", std::process::id()));
+        let deck = std::fs::read_to_string("deck.txt").unwrap();
+        let reordered: Vec<&str> = deck.lines().rev().collect();
+        std::fs::write(&reordered_path, reordered.join("\n")).unwrap();
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut buffer = Vec::new();
+        compare_decks(&mut buffer, &db, "deck.txt", reordered_path.to_str().unwrap(), 2, false)
+            .expect("writing to a Vec cannot fail");
+        let output = String::from_utf8(buffer).unwrap();
+        std::fs::remove_file(&reordered_path).ok();
+
+        assert!(output.contains("(identical lists)"));
+        assert!(output.contains("Note: both files are the same deck"));
+    }
+}
@@ -0,0 +1,156 @@
+//! Wall-time profile of the game loop
+//!
+//! Engine code brackets its expensive parts with `section` guards; while `collect` is
+//! running on the thread, each guard adds the time spent inside it (minus time in nested
+//! sections) to a per-section total. Outside `collect` the guards do nothing beyond a
+//! thread-local check, so normal runs pay almost nothing.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// A profiled part of the game loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Mulligan,
+    MainPhase,
+    Combat,
+    Etb,
+}
+
+impl Section {
+    pub const ALL: [Section; 4] = [Section::Mulligan, Section::MainPhase, Section::Combat, Section::Etb];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Section::Mulligan => "Mulligan",
+            Section::MainPhase => "Main phase",
+            Section::Combat => "Combat",
+            Section::Etb => "ETB resolution",
+        }
+    }
+}
+
+/// Time spent per section over the profiled games
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub games: usize,
+    /// Wall time of the whole games
+    pub total: Duration,
+    /// Exclusive time per section, indexed like `Section::ALL`
+    pub sections: [Duration; 4],
+}
+
+impl Profile {
+    pub fn section(&self, section: Section) -> Duration {
+        self.sections[section as usize]
+    }
+
+    /// Game time spent outside every section (setup, draws, upkeep, ...)
+    pub fn other(&self) -> Duration {
+        self.total.saturating_sub(self.sections.iter().sum())
+    }
+}
+
+struct Recorder {
+    profile: Profile,
+    /// Time spent in nested sections, one entry per open section
+    nested: Vec<Duration>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Open section; its time is recorded when dropped
+pub struct Guard {
+    section: Option<Section>,
+    start: Option<Instant>,
+}
+
+fn open(section: Option<Section>) -> Guard {
+    let recording = RECORDER.with(|recorder| match recorder.borrow_mut().as_mut() {
+        Some(recorder) => {
+            recorder.nested.push(Duration::ZERO);
+            true
+        }
+        None => false,
+    });
+    Guard { section, start: recording.then(Instant::now) }
+}
+
+/// Time the rest of the enclosing scope as `section`
+pub fn section(section: Section) -> Guard {
+    open(Some(section))
+}
+
+/// Time the rest of the enclosing scope as one whole game
+pub fn game() -> Guard {
+    open(None)
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        RECORDER.with(|recorder| {
+            let mut recorder = recorder.borrow_mut();
+            let Some(recorder) = recorder.as_mut() else {
+                return;
+            };
+            let nested = recorder.nested.pop().unwrap_or_default();
+            if let Some(parent) = recorder.nested.last_mut() {
+                *parent += elapsed;
+            }
+            match self.section {
+                Some(section) => recorder.profile.sections[section as usize] += elapsed.saturating_sub(nested),
+                None => {
+                    recorder.profile.games += 1;
+                    recorder.profile.total += elapsed;
+                }
+            }
+        });
+    }
+}
+
+/// Run `f`, profiling the games it plays on this thread
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Profile) {
+    let previous = RECORDER.with(|recorder| {
+        recorder.replace(Some(Recorder { profile: Profile::default(), nested: Vec::new() }))
+    });
+    let result = f();
+    let recorder = RECORDER.with(|recorder| recorder.replace(previous));
+    (result, recorder.map(|r| r.profile).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardDatabase;
+    use crate::simulation::deck::parse_deck_file;
+    use crate::simulation::engine::run_game;
+
+    #[test]
+    fn test_profile_sections_add_up_to_game_time() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        let start = Instant::now();
+        let ((), profile) = collect(|| {
+            for seed in 0..20 {
+                run_game(&deck, seed, &db, false);
+            }
+        });
+        let elapsed = start.elapsed();
+
+        assert_eq!(profile.games, 20);
+        for section in Section::ALL {
+            assert!(profile.section(section) > Duration::ZERO, "no time in {}", section.label());
+        }
+        let sections: Duration = profile.sections.iter().sum();
+        assert!(sections <= profile.total, "{:?} in sections of {:?}", sections, profile.total);
+        assert_eq!(sections + profile.other(), profile.total);
+        assert!(profile.total <= elapsed, "{:?} of {:?}", profile.total, elapsed);
+    }
+}
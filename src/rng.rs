@@ -16,7 +16,7 @@ impl Mulberry32 {
 
     /// Generate next random number in [0, 1)
     /// Matches TypeScript's mulberry32 exactly
-    pub fn next(&mut self) -> f64 {
+    pub fn next_f64(&mut self) -> f64 {
         self.state = self.state.wrapping_add(0x6D2B79F5);
         let mut t = self.state;
         t = (t ^ (t >> 15)).wrapping_mul(t | 1);
@@ -31,6 +31,7 @@ impl Mulberry32 {
 #[derive(Clone)]
 pub struct GameRng {
     mulberry: Mulberry32,
+    seed: u64,
 }
 
 impl GameRng {
@@ -44,12 +45,18 @@ impl GameRng {
 
         // Use lower 32 bits for Mulberry32 (matches TypeScript behavior)
         let mulberry = Mulberry32::new(seed as u32);
-        GameRng { mulberry }
+        GameRng { mulberry, seed }
+    }
+
+    /// A separate generator seeded from this one's seed and `stream`, for side computations
+    /// (like the -vvv combo odds) that must not advance the game's own sequence
+    pub fn derived(&self, stream: u64) -> GameRng {
+        GameRng::new(Some(self.seed.wrapping_add(stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))))
     }
 
     /// Generate a random number in range [0, 1)
     pub fn random(&mut self) -> f64 {
-        self.mulberry.next()
+        self.mulberry.next_f64()
     }
 
     /// Generate a random integer in range [0, max)
@@ -83,6 +90,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_derived_depends_on_seed_and_stream_only() {
+        let mut rng = GameRng::new(Some(12345));
+        let first = rng.derived(3).random();
+        rng.random();
+        assert_eq!(rng.derived(3).random(), first, "Drawing from the parent doesn't move a derived stream");
+        assert_ne!(rng.derived(4).random(), first);
+        assert_ne!(GameRng::new(Some(54321)).derived(3).random(), first);
+    }
+
     #[test]
     fn test_different_seeds_produce_different_sequences() {
         let mut rng1 = GameRng::new(Some(12345));
@@ -111,6 +128,32 @@ mod tests {
         assert_eq!(arr1, arr2, "Same seed should produce same shuffle");
     }
 
+    #[test]
+    fn test_shuffle_is_uniform() {
+        // Each card should land in each position 1/n of the time. The chi-square statistic
+        // over the n x n position counts has (n-1)^2 = 25 degrees of freedom; 60 is far
+        // beyond its 99.9th percentile (~52.6), while a shuffle that skips a position or
+        // never leaves a card in place scores in the thousands.
+        const N: usize = 6;
+        const SHUFFLES: usize = 60_000;
+        let mut rng = GameRng::new(Some(7));
+        let mut counts = [[0u32; N]; N];
+        for _ in 0..SHUFFLES {
+            let mut deck: Vec<usize> = (0..N).collect();
+            rng.shuffle(&mut deck);
+            for (position, &card) in deck.iter().enumerate() {
+                counts[card][position] += 1;
+            }
+        }
+
+        let expected = (SHUFFLES / N) as f64;
+        let chi_square: f64 = counts.iter()
+            .flatten()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_square < 60.0, "chi-square {:.1} for position counts {:?}", chi_square, counts);
+    }
+
     #[test]
     fn test_random_range() {
         let mut rng = GameRng::new(Some(123));
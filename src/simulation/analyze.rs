@@ -3,7 +3,8 @@
 //! Analyzes why the combo couldn't execute on turn 4 across many simulations.
 
 use crate::card::{Card, CardDatabase};
-use crate::game::state::GameState;
+use crate::game::state::{ComboPolicy, GameState};
+use crate::simulation::engine::{run_game_with_config, GameConfig, MAX_TURNS};
 use crate::game::mana;
 use crate::game::cards::calculate_combo_damage;
 use std::collections::HashMap;
@@ -26,6 +27,41 @@ pub enum FailureReason {
     ComboAvailable,              // Combo could have fired on turn 4
 }
 
+/// Whether a failed turn-4 game would have been combo-ready with one more land
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Counterfactual {
+    FixableByLand,               // One more untapped land of any color
+    FixableByBlue,               // One more untapped blue source
+    FixableByBlack,              // One more untapped black source
+    FixableByGreen,              // One more untapped green source
+    NotFixable,                  // Still fails with an extra land of any color
+}
+
+/// Which piece of the combo triad (Spider-Man, a reanimation target, the mana to cast
+/// it) a failed turn-4 game lacked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComboBottleneck {
+    MissingSpiderMan,            // Only Spider-Man in hand was missing
+    MissingReanimationTarget,    // Only Bringer in the graveyard was missing
+    MissingMana,                 // Only 4 lands with U, B and G was missing
+    MissingMultiple,             // Two or more pieces were missing
+}
+
+/// Whether we could cast a spell on a turn, after making the land drop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TurnCastability {
+    Castable,                    // At least one spell in hand is castable
+    NoSpellInHand,               // Nothing but lands in hand
+    NotEnoughMana,               // Spells in hand, but none affordable even ignoring colors
+    ColorScrew,                  // A spell would be castable if our lands made the right colors
+}
+
+/// Turns covered by the early-turn castability tally
+pub const EARLY_TURNS: usize = 4;
+
+/// Buckets in the graveyard creature histogram; the last one collects everything above
+pub const GY_CREATURE_BUCKETS: usize = 5;
+
 /// Secondary details about card locations
 #[derive(Debug, Clone, Default)]
 pub struct CardLocations {
@@ -47,6 +83,13 @@ pub struct Turn4Analysis {
     pub primary_failure: FailureReason,
     pub lands_count: u32,
     pub colors_available: (bool, bool, bool), // (U, B, G)
+    pub counterfactual: Option<Counterfactual>, // None when the combo was available
+    pub bottleneck: Option<ComboBottleneck>,    // None when the whole triad was in place
+    pub exiled_cards: Vec<String>,              // Exile zone at the start of turn 4
+    pub early_turns: [TurnCastability; EARLY_TURNS], // Castability after the land drop on turns 1-4
+    pub graveyard_creatures: u32,                    // Creatures in the graveyard at the start of turn 4
+    pub draws_by_source: HashMap<String, u32>,       // Cards drawn through turn 4's draw step, by source
+    pub spider_men_binned: u32,                      // Needed Spider-Men milled/surveilled away by turn 4
 }
 
 /// Aggregate results from analyzing many games
@@ -55,6 +98,13 @@ pub struct AnalysisResults {
     pub failure_counts: HashMap<FailureReason, usize>,
     pub avg_lands: f64,
     pub color_availability: (f64, f64, f64), // % of games with U, B, G available
+    pub counterfactual_counts: HashMap<Counterfactual, usize>,
+    pub bottleneck_counts: HashMap<ComboBottleneck, usize>,
+    pub exiled_counts: HashMap<String, usize>,  // Total copies exiled per card name
+    pub castability_by_turn: [HashMap<TurnCastability, usize>; EARLY_TURNS],
+    pub graveyard_creature_histogram: [usize; GY_CREATURE_BUCKETS], // Combo-ready games only
+    pub draws_by_source: HashMap<String, usize>, // Total cards drawn per source
+    pub spider_man_binned_games: usize, // Games that milled or surveilled away a needed Spider-Man
 }
 
 impl fmt::Display for FailureReason {
@@ -73,6 +123,77 @@ impl fmt::Display for FailureReason {
     }
 }
 
+impl fmt::Display for TurnCastability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Castable => write!(f, "Castable spell"),
+            Self::NoSpellInHand => write!(f, "No spell in hand"),
+            Self::NotEnoughMana => write!(f, "Not enough mana"),
+            Self::ColorScrew => write!(f, "Wrong colors"),
+        }
+    }
+}
+
+impl fmt::Display for ComboBottleneck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSpiderMan => write!(f, "Missing Spider-Man"),
+            Self::MissingReanimationTarget => write!(f, "Missing reanimation target"),
+            Self::MissingMana => write!(f, "Missing mana/color"),
+            Self::MissingMultiple => write!(f, "Missing multiple"),
+        }
+    }
+}
+
+impl fmt::Display for Counterfactual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FixableByLand => write!(f, "Fixable by +1 land"),
+            Self::FixableByBlue => write!(f, "Fixable by +1 blue source"),
+            Self::FixableByBlack => write!(f, "Fixable by +1 black source"),
+            Self::FixableByGreen => write!(f, "Fixable by +1 green source"),
+            Self::NotFixable => write!(f, "Not fixable by mana"),
+        }
+    }
+}
+
+/// Classify whether any spell in hand can be cast with the current untapped lands,
+/// and if not, whether colors or the amount of mana is the problem
+pub fn classify_castability(state: &GameState) -> TurnCastability {
+    let spells: Vec<&Card> = state.hand.cards().iter()
+        .filter(|c| !matches!(c, Card::Land(_)))
+        .collect();
+
+    if spells.is_empty() {
+        TurnCastability::NoSpellInHand
+    } else if spells.iter().any(|c| mana::can_cast_spell(c, state)) {
+        TurnCastability::Castable
+    } else if spells.iter().any(|c| mana::can_cast_spell_ignoring_colors(c, state)) {
+        TurnCastability::ColorScrew
+    } else {
+        TurnCastability::NotEnoughMana
+    }
+}
+
+/// Classify castability after making the land drop the engine would choose
+/// (on a copy, so the real game is unaffected)
+fn castability_after_land_drop(state: &GameState) -> TurnCastability {
+    use crate::simulation::decisions::DecisionEngine;
+
+    if !state.can_play_land() {
+        return classify_castability(state);
+    }
+    let Some(land_idx) = DecisionEngine::choose_land_to_play(state.hand.cards(), state) else {
+        return classify_castability(state);
+    };
+
+    let mut after_land = state.clone();
+    if let Some(land) = after_land.hand.remove_card(land_idx) {
+        let _ = crate::game::cards::play_land(&mut after_land, &land, false);
+    }
+    classify_castability(&after_land)
+}
+
 /// Analyze the game state at turn 4 to determine why combo couldn't fire
 /// This should be called at the START of turn 4's main phase (after draw)
 pub fn analyze_turn4_state(state: &GameState) -> Turn4Analysis {
@@ -197,10 +318,80 @@ pub fn analyze_turn4_state(state: &GameState) -> Turn4Analysis {
         &locations, combo_damage, state.opponent_life,
     );
 
+    // Counterfactual pass: would one more land (or one more missing color) have flipped it?
+    let counterfactual = if primary_failure == FailureReason::ComboAvailable {
+        None
+    } else {
+        Some(classify_counterfactual(
+            total_mana, has_blue, has_black, has_green,
+            &locations, combo_damage, state.opponent_life,
+        ))
+    };
+
     Turn4Analysis {
         primary_failure,
         lands_count: total_mana,  // Total mana available (battlefield + playable land)
         colors_available: (has_blue, has_black, has_green),
+        counterfactual,
+        bottleneck: classify_bottleneck(total_mana, has_blue, has_black, has_green, &locations),
+        exiled_cards: state.exile.cards().iter().map(|c| c.name().to_string()).collect(),
+        early_turns: [TurnCastability::NoSpellInHand; EARLY_TURNS],
+        graveyard_creatures: state.graveyard.creatures().count() as u32,
+        draws_by_source: state.draws_by_source.clone(),
+        spider_men_binned: state.spider_men_binned,
+    }
+}
+
+/// Re-check combo readiness of a failed game with one hypothetical extra untapped land.
+/// A colorless extra land is tried first, then one that adds each missing color.
+fn classify_counterfactual(
+    lands_count: u32,
+    has_blue: bool,
+    has_black: bool,
+    has_green: bool,
+    locations: &CardLocations,
+    combo_damage: u32,
+    opponent_life: i32,
+) -> Counterfactual {
+    let ready = |blue: bool, black: bool, green: bool| {
+        determine_primary_failure(
+            lands_count + 1, blue, black, green,
+            locations, combo_damage, opponent_life,
+        ) == FailureReason::ComboAvailable
+    };
+
+    if ready(has_blue, has_black, has_green) {
+        Counterfactual::FixableByLand
+    } else if !has_blue && ready(true, has_black, has_green) {
+        Counterfactual::FixableByBlue
+    } else if !has_black && ready(has_blue, true, has_green) {
+        Counterfactual::FixableByBlack
+    } else if !has_green && ready(has_blue, has_black, true) {
+        Counterfactual::FixableByGreen
+    } else {
+        Counterfactual::NotFixable
+    }
+}
+
+/// Which combo piece was missing, or None if Spider-Man, Bringer in the graveyard and
+/// the mana to cast Spider-Man were all in place (a combo-ready game, or one short on damage)
+fn classify_bottleneck(
+    lands_count: u32,
+    has_blue: bool,
+    has_black: bool,
+    has_green: bool,
+    locations: &CardLocations,
+) -> Option<ComboBottleneck> {
+    let missing_spider_man = locations.spider_man.in_hand == 0;
+    let missing_target = locations.bringer.in_graveyard == 0;
+    let missing_mana = lands_count < 4 || !(has_blue && has_black && has_green);
+
+    match (missing_spider_man, missing_target, missing_mana) {
+        (false, false, false) => None,
+        (true, false, false) => Some(ComboBottleneck::MissingSpiderMan),
+        (false, true, false) => Some(ComboBottleneck::MissingReanimationTarget),
+        (false, false, true) => Some(ComboBottleneck::MissingMana),
+        _ => Some(ComboBottleneck::MissingMultiple),
     }
 }
 
@@ -259,66 +450,138 @@ fn determine_primary_failure(
     FailureReason::ComboAvailable
 }
 
-/// Run a game to turn 4 only (for analysis)
-/// Analyzes state at the START of turn 4 (after draw, before main phase)
-pub fn run_game_to_turn4(
+/// Play a game up to the START of `last_turn`'s main phase (after untap, upkeep, draw and
+/// saga advancement), calling `at_main_phase` at that point of every turn. Stops there
+/// early when it returns true, or when we've lost.
+pub fn run_game_to_turn(
     deck: &[Card],
     seed: u64,
     db: &CardDatabase,
-) -> Turn4Analysis {
-    use crate::simulation::mulligan::resolve_mulligans;
+    last_turn: u32,
+    mut at_main_phase: impl FnMut(&GameState) -> bool,
+) -> GameState {
     use crate::rng::GameRng;
-    use crate::simulation::engine::execute_turn;
-    use crate::game::turns::{start_turn, draw_phase, upkeep_phase, precombat_main_phase_start};
+    use crate::simulation::engine::{begin_turn, finish_turn, setup_game};
 
     let mut rng = GameRng::new(Some(seed));
     let mut state = GameState::new();
 
-    // Determine if on play or draw (50/50) - BEFORE shuffling to match TypeScript RNG sequence
-    state.on_the_play = rng.random() < 0.5;
+    // Shuffle, mulligan and draw the opening hand exactly like run_game
+    setup_game(&mut state, deck, &mut rng, &mut Vec::with_capacity(deck.len()));
 
-    // Shuffle deck into library
-    let mut shuffled_deck = deck.to_vec();
-    rng.shuffle(&mut shuffled_deck);
-    for card in shuffled_deck {
-        state.library.add_card(card);
-    }
-
-    // Mulligan phase: resolve mulligans to get opening hand
-    let mut library_cards = Vec::new();
-    for _ in 0..state.library.size() {
-        if let Some(card) = state.library.draw() {
-            library_cards.push(card);
+    loop {
+        begin_turn(&mut state, false, &mut rng);
+        if at_main_phase(&state) || state.turn >= last_turn || state.has_lost() {
+            return state;
         }
+        finish_turn(&mut state, db, false, &mut rng);
     }
+}
 
-    let opening_hand = resolve_mulligans(&mut library_cards, &mut rng);
+/// Run a game to turn 4 only (for analysis)
+/// Analyzes state at the START of turn 4 (after draw, before main phase)
+pub fn run_game_to_turn4(
+    deck: &[Card],
+    seed: u64,
+    db: &CardDatabase,
+) -> Turn4Analysis {
+    // Note castability after each land drop on turns 1-4
+    let mut early_turns = [TurnCastability::NoSpellInHand; EARLY_TURNS];
+    let state = run_game_to_turn(deck, seed, db, EARLY_TURNS as u32, |state| {
+        early_turns[state.turn as usize - 1] = castability_after_land_drop(state);
+        false
+    });
 
-    // Put remaining cards back in library
-    for card in library_cards {
-        state.library.add_card(card);
+    // Analyze state at START of turn 4 main phase
+    // All lands are untapped (from start_turn), we've drawn for the turn, sagas advanced
+    Turn4Analysis {
+        early_turns,
+        ..analyze_turn4_state(&state)
     }
+}
 
-    // Add opening hand to hand
-    for card in opening_hand {
-        state.hand.add_card(card);
+/// Last turn checked for the combo in the robustness percentiles
+pub const COMBO_HORIZON: u32 = 10;
+
+/// First turn whose main phase starts with the combo available (by the turn-4 analysis
+/// rules), or None if it isn't by `last_turn`
+pub fn first_combo_ready_turn(deck: &[Card], seed: u64, db: &CardDatabase, last_turn: u32) -> Option<u32> {
+    let mut ready_turn = None;
+    run_game_to_turn(deck, seed, db, last_turn, |state| {
+        if analyze_turn4_state(state).primary_failure == FailureReason::ComboAvailable {
+            ready_turn = Some(state.turn);
+        }
+        ready_turn.is_some()
+    });
+    ready_turn
+}
+
+/// Percentiles of the first combo-ready turn over many games
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboTurnPercentiles {
+    /// Turn by which half, three quarters and 90% of games could combo
+    /// (None if fewer than that did by `COMBO_HORIZON`)
+    pub p50: Option<u32>,
+    pub p75: Option<u32>,
+    pub p90: Option<u32>,
+    /// Fraction of games that never could by `COMBO_HORIZON`
+    pub never: f64,
+}
+
+impl ComboTurnPercentiles {
+    pub fn from_turns(turns: &[Option<u32>]) -> Self {
+        // Games that never comboed sort last
+        let mut sorted: Vec<u32> = turns.iter().map(|turn| turn.unwrap_or(u32::MAX)).collect();
+        sorted.sort_unstable();
+        let percentile = |p: f64| -> Option<u32> {
+            let rank = ((p * sorted.len() as f64).ceil() as usize).max(1);
+            sorted.get(rank - 1).copied().filter(|&turn| turn != u32::MAX)
+        };
+        let never = turns.iter().filter(|turn| turn.is_none()).count();
+        ComboTurnPercentiles {
+            p50: percentile(0.5),
+            p75: percentile(0.75),
+            p90: percentile(0.9),
+            never: if turns.is_empty() { 0.0 } else { never as f64 / turns.len() as f64 },
+        }
     }
+}
+
+/// A game where we held a castable but non-lethal combo, replayed on the same seed
+/// firing the combo as soon as it was assembled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeldComboReplay {
+    pub held_turn: u32,
+    pub wait_win_turn: Option<u32>,
+    pub fire_win_turn: Option<u32>,
+}
 
-    // Run turns 1-3 fully
-    for _ in 0..3 {
-        execute_turn(&mut state, db, false, &mut rng);
+impl HeldComboReplay {
+    /// Turns saved by firing early (negative if firing was slower); a loss counts as
+    /// winning the turn after the last one played
+    pub fn turns_saved(&self) -> i32 {
+        let turn = |win: Option<u32>| win.unwrap_or(MAX_TURNS + 1) as i32;
+        turn(self.wait_win_turn) - turn(self.fire_win_turn)
     }
+}
 
-    // Turn 4: only do start_turn (untap), upkeep, draw, and precombat main start - then analyze
-    // This gives us the state at the START of turn 4's main phase (after saga advancement)
-    start_turn(&mut state);
-    upkeep_phase(&mut state);
-    draw_phase(&mut state);
-    precombat_main_phase_start(&mut state, false);
+/// Play a full game, and if it held a castable non-lethal combo, replay it under
+/// `ComboPolicy::FireWhenAssembled` (None if the combo was never held)
+pub fn replay_held_combo(deck: &[Card], seed: u64, db: &CardDatabase) -> Option<HeldComboReplay> {
+    let waited = run_game_with_config(deck, seed, db, false, &GameConfig::default());
+    let held_turn = waited.held_combo_turn?;
 
-    // Analyze state at START of turn 4 main phase
-    // All lands are untapped (from start_turn), we've drawn for the turn, sagas advanced
-    analyze_turn4_state(&state)
+    let fire_config = GameConfig {
+        combo_policy: ComboPolicy::FireWhenAssembled,
+        ..GameConfig::default()
+    };
+    let fired = run_game_with_config(deck, seed, db, false, &fire_config);
+
+    Some(HeldComboReplay {
+        held_turn,
+        wait_win_turn: waited.win_turn,
+        fire_win_turn: fired.win_turn,
+    })
 }
 
 /// Aggregate results from multiple analyses
@@ -327,6 +590,13 @@ pub fn aggregate_results(analyses: &[Turn4Analysis]) -> AnalysisResults {
         failure_counts: HashMap::new(),
         avg_lands: 0.0,
         color_availability: (0.0, 0.0, 0.0),
+        counterfactual_counts: HashMap::new(),
+        bottleneck_counts: HashMap::new(),
+        exiled_counts: HashMap::new(),
+        castability_by_turn: Default::default(),
+        graveyard_creature_histogram: [0; GY_CREATURE_BUCKETS],
+        draws_by_source: HashMap::new(),
+        spider_man_binned_games: 0,
     };
 
     if analyses.is_empty() {
@@ -340,6 +610,28 @@ pub fn aggregate_results(analyses: &[Turn4Analysis]) -> AnalysisResults {
 
     for analysis in analyses {
         *results.failure_counts.entry(analysis.primary_failure).or_insert(0) += 1;
+        if let Some(counterfactual) = analysis.counterfactual {
+            *results.counterfactual_counts.entry(counterfactual).or_insert(0) += 1;
+        }
+        if let Some(bottleneck) = analysis.bottleneck {
+            *results.bottleneck_counts.entry(bottleneck).or_insert(0) += 1;
+        }
+        for name in &analysis.exiled_cards {
+            *results.exiled_counts.entry(name.clone()).or_insert(0) += 1;
+        }
+        for (source, count) in &analysis.draws_by_source {
+            *results.draws_by_source.entry(source.clone()).or_insert(0) += *count as usize;
+        }
+        if analysis.spider_men_binned > 0 {
+            results.spider_man_binned_games += 1;
+        }
+        for (turn_counts, castability) in results.castability_by_turn.iter_mut().zip(analysis.early_turns) {
+            *turn_counts.entry(castability).or_insert(0) += 1;
+        }
+        if analysis.primary_failure == FailureReason::ComboAvailable {
+            let bucket = (analysis.graveyard_creatures as usize).min(GY_CREATURE_BUCKETS - 1);
+            results.graveyard_creature_histogram[bucket] += 1;
+        }
         total_lands += analysis.lands_count as u64;
         if analysis.colors_available.0 { blue_count += 1; }
         if analysis.colors_available.1 { black_count += 1; }
@@ -357,3 +649,186 @@ pub fn aggregate_results(analyses: &[Turn4Analysis]) -> AnalysisResults {
     results
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo_pieces_in_place() -> CardLocations {
+        let mut locations = CardLocations::default();
+        locations.spider_man.in_hand = 1;
+        locations.bringer.in_graveyard = 1;
+        locations.terror.in_graveyard = 1;
+        locations
+    }
+
+    #[test]
+    fn test_three_lands_fixable_by_land() {
+        let locations = combo_pieces_in_place();
+
+        let failure = determine_primary_failure(3, true, true, true, &locations, 30, 20);
+        assert_eq!(failure, FailureReason::InsufficientLands);
+
+        let counterfactual = classify_counterfactual(3, true, true, true, &locations, 30, 20);
+        assert_eq!(counterfactual, Counterfactual::FixableByLand);
+    }
+
+    #[test]
+    fn test_missing_color_fixable_by_that_color() {
+        let locations = combo_pieces_in_place();
+
+        let counterfactual = classify_counterfactual(4, true, false, true, &locations, 30, 20);
+        assert_eq!(counterfactual, Counterfactual::FixableByBlack);
+    }
+
+    #[test]
+    fn test_missing_spider_man_not_fixable() {
+        let mut locations = combo_pieces_in_place();
+        locations.spider_man.in_hand = 0;
+
+        let counterfactual = classify_counterfactual(3, true, true, true, &locations, 30, 20);
+        assert_eq!(counterfactual, Counterfactual::NotFixable);
+    }
+
+    #[test]
+    fn test_combo_turn_percentiles() {
+        // Every game combos by turn 4
+        let always = ComboTurnPercentiles::from_turns(&[Some(3), Some(4), Some(4), Some(2), Some(4)]);
+        assert!(always.p90.is_some_and(|turn| turn <= 4), "{:?}", always);
+        assert_eq!(always.never, 0.0);
+
+        // 2 of 10 never combo: the 90th percentile is past the horizon
+        let mut turns = vec![Some(4); 5];
+        turns.extend([Some(5), Some(6), Some(7), None, None]);
+        let tail = ComboTurnPercentiles::from_turns(&turns);
+        assert_eq!((tail.p50, tail.p75, tail.p90), (Some(4), Some(7), None));
+        assert_eq!(tail.never, 0.2);
+    }
+
+    #[test]
+    fn test_first_combo_ready_turn_agrees_with_turn4_analysis() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        let mut ready_on_turn4 = 0;
+        for seed in 0..40 {
+            let ready = run_game_to_turn4(&deck, seed, &db).primary_failure == FailureReason::ComboAvailable;
+            let first = first_combo_ready_turn(&deck, seed, &db, COMBO_HORIZON);
+            if ready {
+                ready_on_turn4 += 1;
+                assert!(first.is_some_and(|turn| turn <= 4), "seed {}: {:?}", seed, first);
+            } else {
+                assert_ne!(first, Some(4), "seed {}", seed);
+            }
+        }
+        assert!(ready_on_turn4 > 0);
+    }
+
+    #[test]
+    fn test_pollen_evidence_records_exiled_cards() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = crate::rng::GameRng::new(Some(1));
+        let mut state = GameState::new();
+        for name in ["Cache Grab", "Town Greeter", "Dredger's Insight", "Bringer of the Last Gift", "Cache Grab", "Awaken the Honored Dead"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+
+        let pollen = db.get_card("Analyze the Pollen").unwrap();
+        crate::game::cards::cast_spell(&mut state, &pollen, crate::game::cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+
+        // Spells first, then enchantments/sagas by mana value, until 8 MV; Bringer is never exiled
+        let expected = vec!["Cache Grab", "Dredger's Insight", "Cache Grab", "Awaken the Honored Dead"];
+        assert_eq!(state.exile.size(), 4);
+        assert_eq!(analyze_turn4_state(&state).exiled_cards, expected);
+
+        let results = aggregate_results(&[analyze_turn4_state(&state)]);
+        assert_eq!(results.exiled_counts.get("Cache Grab"), Some(&2));
+        assert!(!results.exiled_counts.contains_key("Bringer of the Last Gift"));
+    }
+
+    #[test]
+    fn test_off_color_lands_tallied_as_color_screw() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+        state.turn = 2;
+        state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Swamp").unwrap(), 1));
+        for name in ["Swamp", "Island", "Cache Grab"] {
+            state.hand.add_card(db.get_card(name).unwrap());
+        }
+
+        // Two lands after the drop would pay for Cache Grab, but neither makes green
+        let castability = castability_after_land_drop(&state);
+        assert_eq!(castability, TurnCastability::ColorScrew);
+        assert_eq!(state.lands_played_this_turn, 0, "the real state is left untouched");
+
+        let mut analysis = analyze_turn4_state(&state);
+        analysis.early_turns[1] = castability;
+        let results = aggregate_results(&[analysis]);
+        assert_eq!(results.castability_by_turn[1].get(&TurnCastability::ColorScrew), Some(&1));
+
+        // Only one land: the same hand is simply short on mana
+        state.hand.remove_card(0);
+        state.hand.remove_card(0);
+        state.battlefield = Default::default();
+        assert_eq!(classify_castability(&state), TurnCastability::NotEnoughMana);
+
+        state.hand.remove_card(0);
+        assert_eq!(classify_castability(&state), TurnCastability::NoSpellInHand);
+    }
+
+    #[test]
+    fn test_spider_man_and_mana_without_graveyard_misses_reanimation_target() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+        state.hand.add_card(db.get_card("Superior Spider-Man").unwrap());
+        for name in ["Watery Grave", "Underground Mortuary", "Forest", "Island"] {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card(name).unwrap(), 1));
+        }
+
+        let analysis = analyze_turn4_state(&state);
+        assert_eq!(analysis.primary_failure, FailureReason::NoBringerInGraveyard);
+        assert_eq!(analysis.bottleneck, Some(ComboBottleneck::MissingReanimationTarget));
+
+        // Without Spider-Man too, two pieces are missing
+        state.hand.remove_card(0);
+        assert_eq!(analyze_turn4_state(&state).bottleneck, Some(ComboBottleneck::MissingMultiple));
+    }
+
+    #[test]
+    fn test_graveyard_creatures_bucketed_for_combo_ready_games() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+        for name in ["Bringer of the Last Gift", "Cache Grab", "Terror of the Peaks", "Town Greeter"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+
+        let mut analysis = analyze_turn4_state(&state);
+        assert_eq!(analysis.graveyard_creatures, 3);
+
+        // Not combo-ready: left out of the histogram
+        let results = aggregate_results(std::slice::from_ref(&analysis));
+        assert_eq!(results.graveyard_creature_histogram, [0; GY_CREATURE_BUCKETS]);
+
+        analysis.primary_failure = FailureReason::ComboAvailable;
+        let results = aggregate_results(&[analysis]);
+        assert_eq!(results.graveyard_creature_histogram, [0, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_firing_held_combo_wins_sooner() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        // No Terror: the combo never deals trigger damage, so waiting for lethal holds it forever,
+        // while firing it puts a board of 6/6s into play to attack with
+        let mut deck = Vec::new();
+        for (name, count) in [("Watery Grave", 20), ("Bringer of the Last Gift", 22), ("Superior Spider-Man", 18)] {
+            deck.extend(std::iter::repeat_n(db.get_card(name).unwrap(), count));
+        }
+
+        let replays: Vec<HeldComboReplay> = (0..40)
+            .filter_map(|seed| replay_held_combo(&deck, seed, &db))
+            .collect();
+        assert!(!replays.is_empty(), "a non-lethal combo should be held in some games");
+        assert!(replays.iter().all(|r| r.fire_win_turn.is_some()));
+        assert!(replays.iter().any(|r| r.turns_saved() > 0));
+        assert!(replays.iter().map(|r| r.turns_saved()).sum::<i32>() > 0);
+    }
+}
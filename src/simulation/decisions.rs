@@ -1,30 +1,61 @@
 use crate::card::{Card, CardType, LandCard, LandSubtype, ManaColor};
-use crate::game::mana;
-use crate::game::state::GameState;
+use crate::game::{cards, mana};
+use crate::game::state::{ComboPolicy, GameState, LandPolicy};
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashSet;
 
 /// Decision engine for MTG Reanimator AI
 pub struct DecisionEngine;
 
-impl DecisionEngine {
-    /// Choose which land to play - matches TypeScript's sophisticated logic
-    pub fn choose_land_to_play(hand: &[Card], state: &GameState) -> Option<usize> {
-        let lands: Vec<(usize, &Card)> = hand
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, card)| {
-                if matches!(card, Card::Land(_)) {
-                    Some((idx, card))
-                } else {
-                    None
-                }
-            })
-            .collect();
+/// A choice from hand and the priority rule that made it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decision {
+    pub index: usize,
+    pub reason: String,
+}
 
-        if lands.is_empty() {
-            return None;
+/// What a land drop is judged against: our untapped mana and colors, and the colors
+/// spells in hand still need
+struct LandContext<'a> {
+    state: &'a GameState,
+    colors_available: HashSet<ManaColor>,
+    mana_after_land_drop: u32,
+    spells_in_hand: Vec<&'a Card>,
+    missing_colors: HashSet<ManaColor>,
+}
+
+/// The land-drop rule that places a land in hand, best first. Under
+/// `LandPolicy::FixColorsFirst` a land adding a new color is `FixesColors` ahead of the rest.
+#[derive(Debug)]
+enum LandRule<'a> {
+    FixesColors(Vec<ManaColor>),
+    /// Untapped, and lets us cast this spell this turn
+    EnablesCast(&'a Card),
+    ProvidesMissing,
+    Surveil,
+    /// Nothing to cast this turn, so a tapped land costs nothing now
+    Tapped,
+    Other,
+}
+
+/// Sort key for a land drop: the rule, then `rule_and_key`'s tie-breaks
+type LandKey = (u8, (u8, bool, Reverse<usize>, bool));
+
+impl LandRule<'_> {
+    fn rank(&self) -> u8 {
+        match self {
+            LandRule::FixesColors(_) => 0,
+            LandRule::EnablesCast(_) => 1,
+            LandRule::ProvidesMissing => 2,
+            LandRule::Surveil => 3,
+            LandRule::Tapped => 4,
+            LandRule::Other => 5,
         }
+    }
+}
 
+impl<'a> LandContext<'a> {
+    fn new(hand: &'a [Card], state: &'a GameState) -> Self {
         let mut mana_available = 0;
         let mut colors_available = HashSet::new();
         for perm in state.battlefield.permanents() {
@@ -40,54 +71,314 @@ impl DecisionEngine {
             }
         }
 
-        let mana_after_land_drop = mana_available + 1;
         let spells_in_hand: Vec<&Card> = hand.iter().filter(|c| !matches!(c, Card::Land(_))).collect();
-
         let mut missing_colors = HashSet::new();
         for spell in &spells_in_hand {
-            Self::add_missing_colors(spell, &colors_available, &mut missing_colors);
+            DecisionEngine::add_missing_colors(spell, &colors_available, &mut missing_colors);
         }
 
-        let enters_tapped = |land: &LandCard| -> bool {
-            match land.subtype {
-                LandSubtype::Fastland => {
-                    state.battlefield.permanents().iter().filter(|p| matches!(p.card, Card::Land(_))).count() >= 3
-                }
-                LandSubtype::Town => state.turn > 3,
-                LandSubtype::Utility => {
-                    // Verge lands: simplified to always enter untapped
-                    // Other utility lands: use card definition
-                    if land.base.name.ends_with("Verge") {
-                        false
-                    } else {
-                        land.enters_tapped
-                    }
+        LandContext { state, colors_available, mana_after_land_drop: mana_available + 1, spells_in_hand, missing_colors }
+    }
+
+    fn enters_tapped(&self, land: &LandCard) -> bool {
+        match land.subtype {
+            LandSubtype::Fastland => {
+                self.state.battlefield.permanents().iter().filter(|p| matches!(p.card, Card::Land(_))).count() >= 3
+            }
+            LandSubtype::Town => self.state.turn > 3,
+            LandSubtype::Utility => {
+                // Verge lands: simplified to always enter untapped
+                // Other utility lands: use card definition
+                if land.base.name.ends_with("Verge") {
+                    false
+                } else {
+                    land.enters_tapped
                 }
-                _ => land.enters_tapped,
             }
+            _ => land.enters_tapped,
+        }
+    }
+
+    fn provides_missing(&self, land: &LandCard) -> bool {
+        land.colors.iter().any(|c| self.missing_colors.contains(c))
+    }
+
+    /// Missing colors `land` adds, in WUBRG order
+    fn missing_added(&self, land: &LandCard) -> Vec<ManaColor> {
+        [ManaColor::White, ManaColor::Blue, ManaColor::Black, ManaColor::Red, ManaColor::Green]
+            .into_iter()
+            .filter(|color| self.missing_colors.contains(color) && land.colors.contains(color))
+            .collect()
+    }
+
+    /// The rule placing `land`, ignoring `LandPolicy::FixColorsFirst`
+    fn play_rule(&self, land: &LandCard) -> LandRule<'a> {
+        if let Some(spell) = self.enabled_spell(land) {
+            LandRule::EnablesCast(spell)
+        } else if self.provides_missing(land) {
+            LandRule::ProvidesMissing
+        } else if land.has_surveil {
+            LandRule::Surveil
+        } else if self.enters_tapped(land) {
+            LandRule::Tapped
+        } else {
+            LandRule::Other
+        }
+    }
+
+    /// The rule placing `land` and its sort key. Ties within a rule go to surveil lands,
+    /// then (among lands enabling a cast) the most colors, then (among lands providing a
+    /// missing color) tapped lands.
+    fn rule_and_key(&self, land: &LandCard) -> (LandRule<'a>, LandKey) {
+        let play_rule = self.play_rule(land);
+        let colors = if matches!(play_rule, LandRule::EnablesCast(_)) { land.colors.len() } else { 0 };
+        let tie_breaks = (play_rule.rank(), !land.has_surveil, Reverse(colors), !self.enters_tapped(land));
+        let new_colors = self.new_colors(land);
+        let rule = if self.state.land_policy == LandPolicy::FixColorsFirst && !new_colors.is_empty() {
+            LandRule::FixesColors(new_colors)
+        } else {
+            play_rule
         };
+        let rank = rule.rank();
+        (rule, (rank, tie_breaks))
+    }
+
+    /// Why `land` is played under `rule` (for the -vvv log)
+    fn reason(&self, land: &LandCard, rule: &LandRule) -> String {
+        let names = |colors: &[ManaColor]| colors.iter().map(|color| format!("{:?}", color)).collect::<Vec<_>>().join(", ");
+        let missing = self.missing_added(land);
+        match rule {
+            LandRule::FixesColors(new_colors) => format!("fixing colors first: adds {}", names(new_colors)),
+            LandRule::EnablesCast(spell) if missing.is_empty() => format!("lets us cast {} this turn", spell.name()),
+            LandRule::EnablesCast(spell) => {
+                format!("lets us cast {} this turn (adds missing {})", spell.name(), names(&missing))
+            }
+            LandRule::ProvidesMissing => format!("provides missing {}", names(&missing)),
+            LandRule::Surveil => "nothing to cast this turn, so surveil".to_string(),
+            LandRule::Tapped => "nothing to cast this turn, so play a tapped land now".to_string(),
+            LandRule::Other => "nothing to cast this turn".to_string(),
+        }
+    }
 
-        let provides_missing = |land: &LandCard| land.colors.iter().any(|c| missing_colors.contains(c));
+    /// Colors `land` taps for that our untapped lands don't
+    fn new_colors(&self, land: &LandCard) -> Vec<ManaColor> {
+        land.colors.iter()
+            .filter(|c| **c != ManaColor::Colorless && !self.colors_available.contains(c))
+            .copied()
+            .collect()
+    }
 
-        let can_cast_this_turn = |land: &LandCard| -> bool {
-            if enters_tapped(land) { return false; }
-            let mut colors_after = colors_available.clone();
-            for color in &land.colors { colors_after.insert(*color); }
-            spells_in_hand.iter().any(|spell| {
-                spell.mana_value() <= mana_after_land_drop && Self::has_colors(spell, &colors_after)
-            })
+    /// First spell in hand we could cast this turn after playing `land`
+    fn enabled_spell(&self, land: &LandCard) -> Option<&'a Card> {
+        if self.enters_tapped(land) {
+            return None;
+        }
+        let mut colors_after = self.colors_available.clone();
+        for color in &land.colors {
+            colors_after.insert(*color);
+        }
+        self.spells_in_hand.iter().copied().find(|spell| {
+            spell.mana_value() <= self.mana_after_land_drop && DecisionEngine::has_colors(spell, &colors_after)
+        })
+    }
+}
+
+/// The rule of the spell priority order that places a castable spell. Spells are cast
+/// by `rank`; ties go to the cheaper spell where `ranks_by_mana_value` says so.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellRule {
+    /// Spider-Man onto a Bringer graveyard, lethal or fired as soon as it's assembled
+    Combo { damage: u32, lethal: bool },
+    /// Formidable Speaker with Bringer already in the graveyard but no Spider-Man in hand
+    TutorForSpiderMan,
+    /// Formidable Speaker (ahead of Kiora) can discard Bringer or Terror from hand
+    DiscardComboPiece { speaker: bool },
+    Mill,
+    AwakenTheHonoredDead,
+    /// Spider-Man copies Bringer to return Ardyn for Starscourge
+    ArdynLine,
+    /// Spider-Man with a second one in hand, copying a mill creature to dig
+    DigWithSpiderMan,
+    ManaValue,
+}
+
+impl SpellRule {
+    /// Lower ranks are cast first
+    pub fn rank(self) -> u8 {
+        match self {
+            SpellRule::Combo { .. } => 0,
+            SpellRule::TutorForSpiderMan => 1,
+            SpellRule::DiscardComboPiece { speaker: true } => 2,
+            SpellRule::DiscardComboPiece { speaker: false } => 3,
+            SpellRule::Mill => 4,
+            SpellRule::AwakenTheHonoredDead => 5,
+            SpellRule::ArdynLine | SpellRule::DigWithSpiderMan | SpellRule::ManaValue => 6,
+        }
+    }
+
+    /// Why a spell placed by this rule is cast (for the -vvv log)
+    pub fn reason(self, card: &Card, state: &GameState) -> String {
+        match self {
+            SpellRule::Combo { damage, lethal: true } => {
+                format!("combo is lethal ({} damage vs {} life)", damage, state.opponent_life)
+            }
+            SpellRule::Combo { damage, lethal: false } => {
+                format!("firing the combo as soon as it's assembled ({} damage vs {} life)", damage, state.opponent_life)
+            }
+            SpellRule::TutorForSpiderMan => "tutors for Spider-Man with Bringer already in the graveyard".to_string(),
+            SpellRule::DiscardComboPiece { .. } => "can discard a combo piece from hand to the graveyard".to_string(),
+            SpellRule::Mill => "mills toward combo pieces".to_string(),
+            SpellRule::AwakenTheHonoredDead => "no mill spell to cast first".to_string(),
+            SpellRule::ArdynLine => "copies Bringer to return Ardyn for Starscourge".to_string(),
+            SpellRule::DigWithSpiderMan => {
+                "second Spider-Man in hand, dig with a mill creature in the graveyard".to_string()
+            }
+            SpellRule::ManaValue => format!("cheapest castable spell (mana value {})", card.mana_value()),
+        }
+    }
+
+    /// Whether spells under this rule are ordered among themselves by mana value
+    fn ranks_by_mana_value(self) -> bool {
+        matches!(self, SpellRule::Mill | SpellRule::ArdynLine | SpellRule::DigWithSpiderMan | SpellRule::ManaValue)
+    }
+
+    /// Short label with the rule's number in the priority order (for the -vvv castable list)
+    pub fn label(self) -> &'static str {
+        match self {
+            SpellRule::Combo { .. } => "1: combo",
+            SpellRule::TutorForSpiderMan => "1.5: tutor for Spider-Man",
+            SpellRule::DiscardComboPiece { .. } => "2: discard a combo piece",
+            SpellRule::Mill => "3: mill",
+            SpellRule::AwakenTheHonoredDead => "4: Awaken the Honored Dead",
+            SpellRule::ArdynLine => "5: Ardyn line",
+            SpellRule::DigWithSpiderMan => "5: dig with Spider-Man",
+            SpellRule::ManaValue => "5: mana value",
+        }
+    }
+}
+
+/// What the spell priority rules look at, worked out once per decision
+struct SpellContext {
+    bringer_in_graveyard: bool,
+    combo_piece_in_hand: bool,
+    spider_man_in_hand: bool,
+    /// Combo damage if Spider-Man copied Bringer now (0 without a Bringer in the graveyard)
+    combo_damage: u32,
+    combo_is_lethal: bool,
+    fire_combo: bool,
+    /// No Bringer, but Ardyn in the graveyard with another creature for Starscourge
+    ardyn_line: bool,
+}
+
+impl SpellContext {
+    fn new(state: &GameState) -> Self {
+        let in_hand = |name: &str| state.hand.cards().iter().any(|c| c.name() == name);
+        let bringer_in_graveyard = state.graveyard.cards().iter()
+            .any(|c| c.name() == "Bringer of the Last Gift");
+        let combo_damage = if bringer_in_graveyard { cards::calculate_combo_damage(state) } else { 0 };
+        let combo_is_lethal = bringer_in_graveyard && combo_damage >= state.opponent_life as u32;
+        let ardyn_in_graveyard = state.graveyard.cards().iter().any(|c| c.name() == "Ardyn, the Usurper");
+        // Count creatures in GY that aren't Ardyn (for Starscourge targets)
+        let other_creatures_in_gy = state.graveyard.cards().iter()
+            .filter(|card| matches!(card, Card::Creature(_)) && card.name() != "Ardyn, the Usurper")
+            .count();
+        SpellContext {
+            bringer_in_graveyard,
+            combo_piece_in_hand: in_hand("Bringer of the Last Gift") || in_hand("Terror of the Peaks"),
+            spider_man_in_hand: in_hand("Superior Spider-Man"),
+            combo_damage,
+            combo_is_lethal,
+            fire_combo: combo_is_lethal
+                || (bringer_in_graveyard && state.combo_policy == ComboPolicy::FireWhenAssembled),
+            ardyn_line: !bringer_in_graveyard && ardyn_in_graveyard && other_creatures_in_gy >= 1,
+        }
+    }
+
+    /// The priority rule placing a castable spell
+    fn rule(&self, card: &Card) -> SpellRule {
+        match card.name() {
+            // Priority 1: Spider-Man if combo is lethal (or firing when assembled)
+            "Superior Spider-Man" if self.fire_combo => {
+                SpellRule::Combo { damage: self.combo_damage, lethal: self.combo_is_lethal }
+            }
+            "Superior Spider-Man" if self.ardyn_line => SpellRule::ArdynLine,
+            "Superior Spider-Man" => SpellRule::DigWithSpiderMan,
+            // Priority 1.5: Formidable Speaker if Bringer in GY but no Spider-Man
+            // (Speaker can discard something to tutor for Spider-Man!)
+            "Formidable Speaker" if self.bringer_in_graveyard && !self.spider_man_in_hand => {
+                SpellRule::TutorForSpiderMan
+            }
+            // Priority 2: Kiora or Formidable Speaker if Bringer/Terror in hand
+            // (These can discard combo pieces to the graveyard; Speaker also tutors
+            // for Spider-Man, so it goes first)
+            "Formidable Speaker" if self.combo_piece_in_hand => SpellRule::DiscardComboPiece { speaker: true },
+            "Kiora, the Rising Tide" if self.combo_piece_in_hand => SpellRule::DiscardComboPiece { speaker: false },
+            // Priority 3: Mill spells
+            "Cache Grab" | "Dredger's Insight" | "Town Greeter" | "Overlord of the Balemurk" => SpellRule::Mill,
+            // Priority 4: Awaken the Honored Dead
+            "Awaken the Honored Dead" => SpellRule::AwakenTheHonoredDead,
+            // Priority 5: Cheaper spells
+            _ => SpellRule::ManaValue,
+        }
+    }
+}
+
+impl DecisionEngine {
+    /// Whether a graveyard card is something to bring back: a creature card, never a land
+    /// or spell (whatever its mana value)
+    pub fn is_reanimation_target(card: &Card) -> bool {
+        matches!(card, Card::Creature(_))
+    }
+
+    /// Bringer and Terror must stay in the graveyard for the combo: never returned to
+    /// hand or exiled for value
+    pub fn is_combo_piece(card: &Card) -> bool {
+        matches!(card.name(), "Bringer of the Last Gift" | "Terror of the Peaks")
+    }
+
+    /// Graveyard indices that exiling for value (evidence) must leave alone so Spider-Man
+    /// keeps a lethal copy. Bringer and Terror are combo pieces already; with no Bringer
+    /// there, Ardyn and the last other creature its Starscourge needs are kept as well.
+    pub fn spider_man_copy_reserve(graveyard: &[Card]) -> Vec<usize> {
+        if graveyard.iter().any(|c| c.name() == "Bringer of the Last Gift") {
+            return Vec::new();
+        }
+        let Some(ardyn) = graveyard.iter().position(|c| c.name() == "Ardyn, the Usurper") else {
+            return Vec::new();
         };
+        let fodder = graveyard.iter().rposition(|c| Self::is_reanimation_target(c) && c.name() != "Ardyn, the Usurper");
+        std::iter::once(ardyn).chain(fodder).collect()
+    }
 
-        let mut sorted = lands.clone();
-        sorted.sort_by(|a, b| {
-            let (a_land, b_land) = match (a.1, b.1) {
-                (Card::Land(al), Card::Land(bl)) => (al, bl),
-                _ => return std::cmp::Ordering::Equal,
-            };
-            Self::compare_lands(a_land, b_land, &enters_tapped, &provides_missing, &can_cast_this_turn)
-        });
+    /// Choose which land to play - matches TypeScript's sophisticated logic.
+    /// Under `LandPolicy::FixColorsFirst`, lands adding a new color come before everything else.
+    pub fn choose_land_to_play(hand: &[Card], state: &GameState) -> Option<usize> {
+        Self::choose_land_with_rule(&LandContext::new(hand, state), hand).map(|(idx, _)| idx)
+    }
 
-        sorted.first().map(|(idx, _)| *idx)
+    /// `choose_land_to_play`, also saying which rule picked the land (for the -vvv log)
+    pub fn choose_land_to_play_with_reason(hand: &[Card], state: &GameState) -> Option<Decision> {
+        let context = LandContext::new(hand, state);
+        let (index, rule) = Self::choose_land_with_rule(&context, hand)?;
+        let Card::Land(land) = &hand[index] else {
+            return None;
+        };
+        Some(Decision { index, reason: context.reason(land, &rule) })
+    }
+
+    /// The first land in hand by the land-drop rules, with the rule that placed it
+    fn choose_land_with_rule<'a>(context: &LandContext<'a>, hand: &[Card]) -> Option<(usize, LandRule<'a>)> {
+        hand.iter()
+            .enumerate()
+            .filter_map(|(idx, card)| match card {
+                Card::Land(land) => {
+                    let (rule, key) = context.rule_and_key(land);
+                    Some((key, idx, rule))
+                }
+                _ => None,
+            })
+            .min_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, idx, rule)| (idx, rule))
     }
 
     fn add_missing_colors(spell: &Card, available: &HashSet<ManaColor>, missing: &mut HashSet<ManaColor>) {
@@ -120,23 +411,130 @@ impl DecisionEngine {
             && (cost.green == 0 || colors.contains(&ManaColor::Green))
     }
 
-    fn compare_lands<F1, F2, F3>(a: &LandCard, b: &LandCard, enters_tapped: &F1, provides_missing: &F2, can_cast: &F3) -> std::cmp::Ordering
-    where F1: Fn(&LandCard) -> bool, F2: Fn(&LandCard) -> bool, F3: Fn(&LandCard) -> bool,
-    {
-        let (a_cast, b_cast) = (can_cast(a), can_cast(b));
-        if a_cast != b_cast { return if a_cast { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater }; }
+    /// Choose which spell to cast next from hand, or None if nothing should be cast.
+    /// Applies the Spider-Man gating (only cast for a lethal combo, the Ardyn line, or to dig)
+    /// and then the spell priority order.
+    pub fn choose_card_to_play(state: &GameState) -> Option<usize> {
+        Self::castable_in_priority_order(state).first().copied()
+    }
+
+    /// Hand indices of every spell `choose_card_to_play` would cast, best first
+    pub fn castable_in_priority_order(state: &GameState) -> Vec<usize> {
+        Self::castable_with_rules(state).into_iter().map(|(idx, _)| idx).collect()
+    }
+
+    /// Every spell `choose_card_to_play` would cast, best first, with the priority rule
+    /// that places it
+    pub fn castable_with_rules(state: &GameState) -> Vec<(usize, SpellRule)> {
+        let context = SpellContext::new(state);
+
+        // Get castable spells
+        let castable_spells: Vec<(usize, &Card)> = state.hand.castable_spells(state)
+            .into_iter()
+            .filter(|(_, c)| {
+                // Spider-Man casting logic:
+                // 1. If Bringer in graveyard and combo is lethal -> cast (THE COMBO!)
+                //    (or as soon as it's castable under ComboPolicy::FireWhenAssembled)
+                // 2. If Ardyn in graveyard with other creatures -> cast (ARDYN COMBO!)
+                // 3. If no combo available but have 2+ Spider-Man in hand AND
+                //    a mill creature in graveyard -> cast to dig for combo pieces
+                if c.name() == "Superior Spider-Man" {
+                    if context.bringer_in_graveyard {
+                        // Only cast if combo would be lethal
+                        if !context.fire_combo {
+                            return false; // Wait until it would kill
+                        }
+                    } else if !context.ardyn_line {
+                        // No Ardyn combo - check if we should dig
+                        let spider_man_count = state.hand.cards().iter()
+                            .filter(|card| card.name() == "Superior Spider-Man")
+                            .count();
+                        let has_mill_creature_in_gy = state.graveyard.cards().iter()
+                            .any(|card| matches!(card.name(),
+                                "Overlord of the Balemurk" |
+                                "Kiora, the Rising Tide" |
+                                "Town Greeter"));
+
+                        if spider_man_count < 2 || !has_mill_creature_in_gy {
+                            return false; // Can't dig effectively
+                        }
+                        // Otherwise, allow casting to dig for combo pieces
+                    }
+                }
 
-        if !a_cast && !b_cast {
-            let (a_miss, b_miss) = (provides_missing(a), provides_missing(b));
-            if a_miss != b_miss { return if a_miss { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater }; }
-            if a.has_surveil != b.has_surveil { return if a.has_surveil { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater }; }
-            let (a_tap, b_tap) = (enters_tapped(a), enters_tapped(b));
-            if a_tap != b_tap { return if a_tap { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater }; }
-            return std::cmp::Ordering::Equal;
+                true
+            })
+            .collect();
+
+        // Sort by priority: rule first, then cheaper spells. Two spells under the same named
+        // rule (two Kioras, say) compare Less both ways, as they always have; that decides
+        // which copy is cast, so it is kept to keep games unchanged.
+        let mut ranked: Vec<(usize, &Card, SpellRule)> = castable_spells.into_iter()
+            .map(|(idx, card)| (idx, card, context.rule(card)))
+            .collect();
+        ranked.sort_by(|(_, a_card, a_rule), (_, b_card, b_rule)| {
+            match a_rule.rank().cmp(&b_rule.rank()) {
+                Ordering::Equal if a_rule.ranks_by_mana_value() => a_card.mana_value().cmp(&b_card.mana_value()),
+                Ordering::Equal => Ordering::Less,
+                unequal => unequal,
+            }
+        });
+        ranked.into_iter().map(|(idx, _, rule)| (idx, rule)).collect()
+    }
+
+    /// `choose_card_to_play`, also saying which priority rule picked the spell (for the -vvv log)
+    pub fn choose_card_to_play_with_reason(state: &GameState) -> Option<Decision> {
+        let (index, rule) = Self::castable_with_rules(state).into_iter().next()?;
+        Some(Decision { index, reason: rule.reason(&state.hand.cards()[index], state) })
+    }
+
+    /// Choose a flashback-style spell to cast from the graveyard (cheapest first)
+    pub fn choose_flashback_spell(state: &GameState) -> Option<usize> {
+        state.graveyard.castable_spells(state)
+            .into_iter()
+            .min_by_key(|(_, c)| c.mana_value())
+            .map(|(idx, _)| idx)
+    }
+
+    /// Default surveil policy: bin the top card if we'd rather reanimate it than draw it.
+    /// Graveyard: Bringer, Terror, Overlord (want to reanimate these), Kiora if we already
+    /// have one, and Town Greeter (cheap 1/1, better to reanimate than draw).
+    /// Top: Spider-Man (MUST stay in hand!), lands, mill spells
+    pub fn surveil_to_graveyard(card: &Card, state: &GameState) -> bool {
+        match card.name() {
+            "Bringer of the Last Gift" | "Terror of the Peaks" | "Overlord of the Balemurk" | "Town Greeter" => true,
+            "Kiora, the Rising Tide" => state.hand.cards().iter().any(|c| c.name() == "Kiora, the Rising Tide"),
+            _ => false,
         }
+    }
 
-        if a.has_surveil != b.has_surveil { return if a.has_surveil { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater }; }
-        b.colors.len().cmp(&a.colors.len())
+    /// Choose which card to discard from hand (e.g. down to hand size), or None if empty.
+    /// Bringer and Terror go first and lands beyond two next (as with Kiora), then other
+    /// spells, then the remaining lands. Spider-Man and Ardyn are only discarded as a last
+    /// resort, when every other card is gone.
+    pub fn choose_discard(state: &GameState) -> Option<usize> {
+        let cards = state.hand.cards();
+        let protected = |c: &Card| matches!(c.name(), "Superior Spider-Man" | "Ardyn, the Usurper");
+
+        // Priority 1: Bringer, Terror - we want these in the graveyard anyway
+        if let Some(idx) = cards.iter().position(Self::is_combo_piece) {
+            return Some(idx);
+        }
+        // Priority 2: Excess lands (more than 2 in hand)
+        let lands_in_hand = cards.iter().filter(|c| matches!(c, Card::Land(_))).count();
+        if lands_in_hand > 2 {
+            return cards.iter().rposition(|c| matches!(c, Card::Land(_)));
+        }
+        // Priority 3: Non-essential spells
+        if let Some(idx) = cards.iter().rposition(|c| !matches!(c, Card::Land(_)) && !protected(c)) {
+            return Some(idx);
+        }
+        // Priority 4: Our remaining lands
+        if let Some(idx) = cards.iter().rposition(|c| matches!(c, Card::Land(_))) {
+            return Some(idx);
+        }
+        // Last resort: Ardyn (we'd still reanimate it) before Spider-Man
+        cards.iter().position(|c| c.name() == "Ardyn, the Usurper").or(cards.len().checked_sub(1))
     }
 
     /// Select the best card from a milled set based on game state priorities
@@ -171,15 +569,21 @@ impl DecisionEngine {
             if let Some(land) = cards.iter().find(|c| matches!(c, Card::Land(_))) { return Some(land); }
         }
         // Priority 6: Non-combo creature
-        if let Some(c) = cards.iter().find(|c| matches!(c, Card::Creature(_)) && c.name() != "Bringer of the Last Gift" && c.name() != "Terror of the Peaks") {
+        if let Some(c) = cards.iter().find(|c| Self::is_reanimation_target(c) && !Self::is_combo_piece(c)) {
             return Some(c);
         }
         // Priority 7: Any permanent except combo pieces
-        cards.iter().find(|c| !matches!(c, Card::Instant(_) | Card::Sorcery(_)) && c.name() != "Bringer of the Last Gift" && c.name() != "Terror of the Peaks")
+        cards.iter().find(|c| !matches!(c, Card::Instant(_) | Card::Sorcery(_)) && !Self::is_combo_piece(c))
     }
 
     /// Choose which card to return from mill
     pub fn choose_mill_return(milled: &[Card], _card_type: CardType) -> Option<usize> {
+        Self::choose_return_to_hand(milled)
+    }
+
+    /// Choose which card to put into hand from a set of milled or graveyard cards
+    /// NEVER returns Bringer or Terror - they must stay in graveyard for reanimation
+    pub fn choose_return_to_hand(milled: &[Card]) -> Option<usize> {
         // Priority 1: Spider-Man
         if let Some(idx) = milled.iter().position(|c| c.name() == "Superior Spider-Man") { return Some(idx); }
         // Priority 2: Kiora
@@ -200,6 +604,148 @@ impl DecisionEngine {
         // Priority 5: Basic lands
         if let Some(idx) = milled.iter().position(|c| matches!(c, Card::Land(_))) { return Some(idx); }
         // Priority 6: Non-combo creatures
-        milled.iter().position(|c| matches!(c, Card::Creature(_)) && c.name() != "Bringer of the Last Gift" && c.name() != "Terror of the Peaks")
+        milled.iter().position(|c| Self::is_reanimation_target(c) && !Self::is_combo_piece(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardDatabase;
+    use crate::game::zones::Permanent;
+
+    fn state_with(db: &CardDatabase, lands: &[&str], hand: &[&str]) -> GameState {
+        let mut state = GameState::new();
+        for name in lands {
+            state.battlefield.add_permanent(Permanent::new(db.get_card(name).unwrap(), 0));
+        }
+        for name in hand {
+            state.hand.add_card(db.get_card(name).unwrap());
+        }
+        state
+    }
+
+    #[test]
+    fn test_choose_card_to_play_uses_hand_castable_set() {
+        let db = CardDatabase::fixture();
+        let state = state_with(&db, &["Forest", "Island"],
+            &["Forest", "Cache Grab", "Kiora, the Rising Tide", "Superior Spider-Man"]);
+
+        let castable: Vec<usize> = state.hand.castable_spells(&state).iter().map(|(i, _)| *i).collect();
+        assert_eq!(castable, vec![1], "Only Cache Grab is castable with two lands");
+        assert_eq!(DecisionEngine::choose_card_to_play(&state), Some(1));
+    }
+
+    #[test]
+    fn test_reanimation_targets_are_creatures_and_combo_pieces_stay_put() {
+        let db = CardDatabase::fixture();
+        let forest = db.get_card("Forest").unwrap();
+        let greeter = db.get_card("Town Greeter").unwrap();
+        let terror = db.get_card("Terror of the Peaks").unwrap();
+
+        assert!(!DecisionEngine::is_reanimation_target(&forest));
+        assert!(!DecisionEngine::is_reanimation_target(&db.get_card("Cache Grab").unwrap()));
+        assert!(DecisionEngine::is_reanimation_target(&greeter));
+        assert!(DecisionEngine::is_reanimation_target(&terror) && DecisionEngine::is_combo_piece(&terror));
+        assert!(!DecisionEngine::is_combo_piece(&greeter));
+
+        // Returning from a milled pile skips Terror for the plain creature
+        let milled = [terror, forest, greeter];
+        assert_eq!(DecisionEngine::choose_return_to_hand(&milled[..1]), None);
+        let state = state_with(&db, &["Forest", "Island", "Swamp", "Forest"], &["Island"]);
+        assert_eq!(DecisionEngine::select_best_from_mill(&milled, &state).map(|c| c.name()), Some("Town Greeter"));
+    }
+
+    #[test]
+    fn test_choose_discard_keeps_spider_man() {
+        let db = CardDatabase::fixture();
+        let mut state = state_with(&db, &[], &["Superior Spider-Man", "Swamp", "Island", "Forest"]);
+
+        assert_eq!(DecisionEngine::choose_discard(&state), Some(3));
+
+        // Down to Spider-Man and one land: the land still goes first
+        state.hand.remove_card(3);
+        state.hand.remove_card(2);
+        assert_eq!(DecisionEngine::choose_discard(&state), Some(1));
+
+        // Ardyn is kept like Spider-Man: the land goes, then Ardyn before Spider-Man
+        state.hand.add_card(db.get_card("Ardyn, the Usurper").unwrap());
+        assert_eq!(DecisionEngine::choose_discard(&state), Some(1));
+        state.hand.remove_card(1);
+        assert_eq!(DecisionEngine::choose_discard(&state), Some(1));
+
+        // Two lands are both kept over a spell
+        let state = state_with(&db, &[], &["Ardyn, the Usurper", "Swamp", "Cache Grab", "Island"]);
+        assert_eq!(DecisionEngine::choose_discard(&state), Some(2));
+        let state = state_with(&db, &[], &["Ardyn, the Usurper", "Cache Grab", "Kiora, the Rising Tide"]);
+        assert_eq!(DecisionEngine::choose_discard(&state), Some(2));
+
+        let alone = state_with(&db, &[], &["Superior Spider-Man"]);
+        assert_eq!(DecisionEngine::choose_discard(&alone), Some(0));
+        assert_eq!(DecisionEngine::choose_discard(&GameState::new()), None);
+    }
+
+    #[test]
+    fn test_fixing_land_reason_names_missing_color() {
+        let db = CardDatabase::fixture();
+        let state = state_with(&db, &["Swamp"], &["Swamp", "Forest", "Cache Grab"]);
+
+        let decision = DecisionEngine::choose_land_to_play_with_reason(state.hand.cards(), &state).unwrap();
+        assert_eq!(decision.index, 1);
+        assert_eq!(decision.reason, "lets us cast Cache Grab this turn (adds missing Green)");
+
+        let decision = DecisionEngine::choose_card_to_play_with_reason(&state_with(&db, &["Swamp", "Forest"], &["Cache Grab"])).unwrap();
+        assert_eq!(decision, Decision { index: 0, reason: "mills toward combo pieces".to_string() });
+    }
+
+    #[test]
+    fn test_fix_colors_first_prefers_new_color_over_enabling_a_cast() {
+        let db = CardDatabase::fixture();
+        let mut state = state_with(&db, &["Island", "Swamp"], &["Kiora, the Rising Tide", "Island"]);
+        let tapped_forest = LandCard { enters_tapped: true, ..LandCard::basic("Tapped Forest", ManaColor::Green) };
+        state.hand.add_card(Card::Land(tapped_forest));
+
+        // The Island lets us cast Kiora now; the tapped Forest only adds green for later
+        assert_eq!(DecisionEngine::choose_land_to_play(state.hand.cards(), &state), Some(1));
+
+        state.land_policy = LandPolicy::FixColorsFirst;
+        let decision = DecisionEngine::choose_land_to_play_with_reason(state.hand.cards(), &state).unwrap();
+        assert_eq!(decision.index, 2);
+        assert_eq!(decision.reason, "fixing colors first: adds Green");
+    }
+
+    #[test]
+    fn test_choose_card_to_play_holds_spider_man_without_combo() {
+        let db = CardDatabase::fixture();
+        let state = state_with(&db, &["Forest", "Island", "Swamp", "Swamp"], &["Superior Spider-Man"]);
+
+        // Castable by mana, but the decision engine holds it with nothing to copy
+        assert_eq!(state.hand.castable_spells(&state).len(), 1);
+        assert_eq!(DecisionEngine::choose_card_to_play(&state), None);
+    }
+
+    #[test]
+    fn test_choose_card_to_play_between_two_copies() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let state = state_with(&db, &["Forest", "Island", "Swamp", "Swamp", "Island", "Forest"],
+            &["Kiora, the Rising Tide", "Bringer of the Last Gift", "Kiora, the Rising Tide", "Cache Grab", "Cache Grab"]);
+
+        // Same-rule copies keep the cast order the priority comparator has always given
+        assert_eq!(DecisionEngine::castable_in_priority_order(&state), vec![2, 0, 3, 4]);
+        assert_eq!(DecisionEngine::choose_card_to_play(&state), Some(2));
+    }
+
+    #[test]
+    fn test_castable_rule_matches_the_sort_for_ardyn_line() {
+        let db = CardDatabase::fixture();
+        let mut state = state_with(&db, &["Forest", "Island", "Swamp", "Swamp"], &["Superior Spider-Man"]);
+        state.graveyard.add_card(db.get_card("Ardyn, the Usurper").unwrap());
+        state.graveyard.add_card(db.get_card("Town Greeter").unwrap());
+
+        // Spider-Man is cast for the Ardyn line and reported under that rule, not plain mana value
+        assert_eq!(DecisionEngine::castable_with_rules(&state), vec![(0, SpellRule::ArdynLine)]);
+        assert_eq!(SpellRule::ArdynLine.label(), "5: Ardyn line");
+        let decision = DecisionEngine::choose_card_to_play_with_reason(&state).unwrap();
+        assert_eq!(decision.reason, "copies Bringer to return Ardyn for Starscourge");
     }
 }
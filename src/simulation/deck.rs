@@ -1,4 +1,5 @@
-use crate::card::{Card, CardDatabase, CardDatabaseError};
+use crate::card::{Card, CardDatabase, CardDatabaseError, CardType, ManaColor};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -21,6 +22,14 @@ pub fn parse_deck_file(
     database: &CardDatabase,
 ) -> Result<Vec<Card>, DeckError> {
     let content = std::fs::read_to_string(path)?;
+    parse_deck_list(&content, database)
+}
+
+/// Parse a decklist from a string, using the same format as `parse_deck_file`
+pub fn parse_deck_list(
+    content: &str,
+    database: &CardDatabase,
+) -> Result<Vec<Card>, DeckError> {
     let mut deck = Vec::new();
 
     for (line_num, line) in content.lines().enumerate() {
@@ -70,6 +79,76 @@ pub fn parse_deck_file(
     Ok(deck)
 }
 
+/// Format a parsed deck as "count name" lines, sorted by card type then name,
+/// followed by the total card count
+pub fn format_deck_list(deck: &[Card]) -> String {
+    let mut counts: BTreeMap<(CardType, &str), usize> = BTreeMap::new();
+    for card in deck {
+        *counts.entry((card.card_type(), card.name())).or_insert(0) += 1;
+    }
+
+    let mut output = String::new();
+    for ((_, name), count) in &counts {
+        output.push_str(&format!("{} {}\n", count, name));
+    }
+    output.push_str(&format!("Total: {}\n", deck.len()));
+    output
+}
+
+/// Diff two parsed decks by card name, returning "+N Card" lines for cards
+/// `to` has more copies of, then "-N Card" lines for cards it has fewer of
+pub fn diff_decks(from: &[Card], to: &[Card]) -> Vec<String> {
+    let mut delta: BTreeMap<&str, i64> = BTreeMap::new();
+    for card in from {
+        *delta.entry(card.name()).or_insert(0) -= 1;
+    }
+    for card in to {
+        *delta.entry(card.name()).or_insert(0) += 1;
+    }
+
+    let added = delta.iter().filter(|(_, &n)| n > 0).map(|(name, n)| format!("+{} {}", n, name));
+    let removed = delta.iter().filter(|(_, &n)| n < 0).map(|(name, n)| format!("-{} {}", -n, name));
+    added.chain(removed).collect()
+}
+
+/// Colors the combo needs (U, B and G) that no land in the deck can ever
+/// produce. Flexible lands (Verges, Multiversal Passage, Starting Town) count
+/// for every color they list; Cavern of Souls counts for any color a creature
+/// in the deck costs, since it makes any color for creature spells.
+pub fn missing_ubg_colors(deck: &[Card]) -> Vec<ManaColor> {
+    let mut producible: Vec<ManaColor> = deck
+        .iter()
+        .filter_map(|card| match card {
+            Card::Land(land) => Some(&land.colors),
+            _ => None,
+        })
+        .flatten()
+        .copied()
+        .collect();
+
+    if deck.iter().any(|card| card.name() == "Cavern of Souls") {
+        for card in deck {
+            if let Card::Creature(creature) = card {
+                let cost = &creature.base.mana_cost;
+                for (pips, color) in [
+                    (cost.blue, ManaColor::Blue),
+                    (cost.black, ManaColor::Black),
+                    (cost.green, ManaColor::Green),
+                ] {
+                    if pips > 0 {
+                        producible.push(color);
+                    }
+                }
+            }
+        }
+    }
+
+    [ManaColor::Blue, ManaColor::Black, ManaColor::Green]
+        .into_iter()
+        .filter(|color| !producible.contains(color))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,5 +180,90 @@ mod tests {
         // This should succeed since deck.txt has valid cards
         assert!(result.is_ok());
     }
-}
 
+    #[test]
+    fn test_format_deck_list_matches_input() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        // Expected counts straight from the decklist text
+        let content = std::fs::read_to_string("deck.txt").expect("Failed to read deck");
+        let mut expected: BTreeMap<String, usize> = BTreeMap::new();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+            let (count, name) = line.split_once(' ').expect("Malformed deck line");
+            *expected.entry(name.to_string()).or_insert(0) += count.parse::<usize>().unwrap();
+        }
+
+        let printed = format_deck_list(&deck);
+        let mut lines: Vec<&str> = printed.lines().collect();
+        assert_eq!(lines.pop(), Some("Total: 60"));
+
+        let mut actual: BTreeMap<String, usize> = BTreeMap::new();
+        for line in lines {
+            let (count, name) = line.split_once(' ').unwrap();
+            actual.insert(name.to_string(), count.parse().unwrap());
+        }
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_format_deck_list_sorted_by_type_then_name() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = vec![
+            db.get_card("Superior Spider-Man").unwrap(),
+            db.get_card("Swamp").unwrap(),
+            db.get_card("Forest").unwrap(),
+            db.get_card("Swamp").unwrap(),
+        ];
+
+        assert_eq!(format_deck_list(&deck), "1 Forest\n2 Swamp\n1 Superior Spider-Man\nTotal: 4\n");
+    }
+
+    #[test]
+    fn test_diff_decks_two_card_swap() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck1 = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        // Swap both Forests for Swamps
+        let mut deck2: Vec<Card> = deck1.iter().filter(|c| c.name() != "Forest").cloned().collect();
+        deck2.push(db.get_card("Swamp").unwrap());
+        deck2.push(db.get_card("Swamp").unwrap());
+
+        assert_eq!(diff_decks(&deck1, &deck2), vec!["+2 Swamp", "-2 Forest"]);
+        assert!(diff_decks(&deck1, &deck1).is_empty());
+    }
+
+    #[test]
+    fn test_missing_ubg_colors_flags_no_green_sources() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = vec![
+            db.get_card("Watery Grave").unwrap(),
+            db.get_card("Island").unwrap(),
+            db.get_card("Superior Spider-Man").unwrap(),
+        ];
+        assert_eq!(missing_ubg_colors(&deck), vec![ManaColor::Green]);
+
+        let full = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        assert!(missing_ubg_colors(&full).is_empty());
+    }
+
+    #[test]
+    fn test_missing_ubg_colors_counts_cavern_for_creature_colors() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        // Cavern is the only green source; Town Greeter's green pip makes it count
+        let mut deck = vec![
+            db.get_card("Watery Grave").unwrap(),
+            db.get_card("Island").unwrap(),
+            db.get_card("Cavern of Souls").unwrap(),
+            db.get_card("Town Greeter").unwrap(),
+        ];
+        assert!(missing_ubg_colors(&deck).is_empty());
+
+        // Without a green creature, Cavern can't cover green
+        deck.pop();
+        assert_eq!(missing_ubg_colors(&deck), vec![ManaColor::Green]);
+    }
+}
@@ -1,48 +1,128 @@
-use crate::card::{Card, CardDatabase, ColorFlags, LandCard, LandSubtype, ManaColor};
-use crate::game::state::GameState;
-use crate::game::turns::{start_turn, draw_phase, upkeep_phase, end_phase, precombat_main_phase_start};
+use crate::card::{Card, CardDatabase, DeckColorIdentity, LandCard, LandSubtype, ManaColor};
+use crate::game::state::{CombatPolicy, ComboPolicy, GameState, LandPolicy, Phase, SurveilPolicy, GraveyardHate, OpponentCurve, ReplacementEffects, DEFAULT_PLAY_PROBABILITY};
+use crate::game::turns::{start_turn, draw_phase, skips_draw, upkeep_phase, end_phase, precombat_main_phase_start};
 use crate::game::cards;
-use crate::game::mana;
+use crate::game::events::GameEvent;
+use crate::game::mana::{self, get_available_colors};
 use crate::simulation::decisions::DecisionEngine;
 use crate::rng::GameRng;
-use crate::simulation::mulligan::resolve_mulligans;
+use crate::simulation::mulligan::{resolve_mulligans, DEFAULT_OPENING_HAND_SIZE};
+use crate::profile::{self, Section};
+use crate::vlog;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// How a game was won
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WinLine {
+    /// Opponent's life reduced to 0 (combat and Terror triggers)
+    Damage,
+    /// Opponent's library milled to 0
+    MillOpponent,
+}
+
+impl std::fmt::Display for WinLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WinLine::Damage => write!(f, "Damage"),
+            WinLine::MillOpponent => write!(f, "Mill"),
+        }
+    }
+}
+
+/// Why a game was lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LossReason {
+    /// Our life hit 0 from the opponent's attacks (`opponent_curve`)
+    Killed,
+    /// Drew from an empty library
+    DeckedOut,
+    /// Out of turns without winning, including running out the `opponent_clock`
+    OutOfTurns,
+}
+
+impl std::fmt::Display for LossReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LossReason::Killed => write!(f, "Killed"),
+            LossReason::DeckedOut => write!(f, "Decked out"),
+            LossReason::OutOfTurns => write!(f, "Out of turns"),
+        }
+    }
+}
 
 /// Result of a single game simulation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameResult {
-    /// Turn on which the game was won (None if didn't win by turn 20)
+    /// Turn on which the game was won (None if didn't win by turn 20 or the opponent's clock)
     pub win_turn: Option<u32>,
     /// First turn we had access to U, B, and G mana
     pub turn_with_ubg: Option<u32>,
+    /// How the game was won (None if didn't win)
+    pub win_line: Option<WinLine>,
+    /// Average fraction of untapped lands tapped per turn (None if no turn had lands)
+    pub mana_utilization: Option<f64>,
+    /// Names of the cards in exile at game end (only with `GameConfig::record_exiled`)
+    pub exiled_cards: Vec<String>,
+    /// First turn a castable combo was held because it wasn't lethal (WaitForLethal only)
+    pub held_combo_turn: Option<u32>,
+    /// Turn the first non-land spell was cast (None if none was)
+    pub first_spell_turn: Option<u32>,
+    /// Structured event log (only with `GameConfig::record_events`)
+    pub events: Vec<GameEvent>,
+    /// By turn 4, drew at least `FLOOD_MARGIN` more lands than spells
+    pub flooded: bool,
+    /// Missed a land drop on turns 1-3
+    pub screwed: bool,
+    /// Went first, skipping the turn-1 draw
+    pub on_the_play: bool,
+    /// Creatures returned by a Bringer mass-reanimate on the winning turn
+    pub reanimated_count: Option<u32>,
+    /// Damage dealt on the winning turn, and how much of it went past lethal (damage wins only)
+    pub winning_turn_damage: Option<u32>,
+    pub overkill: Option<u32>,
+    /// Why the game was lost (None if won)
+    pub loss_reason: Option<LossReason>,
+}
+
+/// Turn by which a game is classified as flooded
+pub const MANA_CHECK_TURN: u32 = 4;
+
+/// Extra lands over spells drawn that counts as a flood
+pub const FLOOD_MARGIN: u32 = 2;
+
+/// Cards the "[Waiting]" log assumes one more mill spell puts in the graveyard
+const WAITING_MILL_SIZE: usize = 4;
+
+/// Library shuffles sampled for the "[Waiting]" log's P(lethal next turn)
+const WAITING_MILL_SAMPLES: usize = 200;
+
+/// Whether we've drawn at least `FLOOD_MARGIN` more lands than spells so far
+pub fn is_flooded(state: &GameState) -> bool {
+    state.lands_drawn >= state.spells_drawn + FLOOD_MARGIN
 }
 
 /// Check if the game has been won
 pub fn check_win_condition(state: &GameState) -> bool {
-    state.opponent_life <= 0
+    win_line(state).is_some()
 }
 
-/// Get available mana colors from battlefield lands as bitflags (no allocations)
-/// Uses can_tap_for_mana to correctly handle conditional lands like Verge lands
-#[inline]
-fn get_available_colors(state: &GameState) -> ColorFlags {
-    let mut colors = ColorFlags::new();
-
-    for permanent in state.battlefield.permanents() {
-        if matches!(permanent.card, Card::Land(_)) {
-            // Use can_tap_for_mana to correctly evaluate conditional lands (Verge, Cavern, etc.)
-            let land_colors = mana::can_tap_for_mana(permanent, state, None);
-            colors.0 |= land_colors.0;
-        }
+/// Determine how the game has been won, if it has
+pub fn win_line(state: &GameState) -> Option<WinLine> {
+    if state.opponent_life <= 0 {
+        Some(WinLine::Damage)
+    } else if state.opponent_library_size == 0 {
+        Some(WinLine::MillOpponent)
+    } else {
+        None
     }
-
-    colors
 }
 
 /// Check if Ardyn, the Usurper is on the battlefield
 fn has_ardyn_on_battlefield(state: &GameState) -> bool {
     state.battlefield.permanents().iter().any(|p| {
         p.card.name() == "Ardyn, the Usurper"
-            || p.is_copy_of.as_deref() == Some("Ardyn, the Usurper")
+            || p.is_copy_of == Some("Ardyn, the Usurper")
     })
 }
 
@@ -66,105 +146,117 @@ fn is_demon(permanent: &crate::game::zones::Permanent) -> bool {
     card_is_demon || copy_is_demon
 }
 
-/// Resolve Ardyn's Starscourge trigger: exile a creature from graveyard and create a 5/5 Demon token copy
-fn resolve_starscourge(state: &mut GameState, verbose: bool) {
-    // Find the best creature in graveyard to exile
-    // Priority: high power creatures, especially reanimation targets like Bringer
-    let mut best_idx: Option<usize> = None;
-    let mut best_power: u32 = 0;
-
-    for (idx, card) in state.graveyard.cards().iter().enumerate() {
-        if let Card::Creature(c) = card {
-            // Prioritize Bringer of the Last Gift and Terror of the Peaks
-            let priority_boost = if c.base.name == "Bringer of the Last Gift" {
-                100
-            } else if c.base.name == "Terror of the Peaks" {
-                50
-            } else {
-                0
-            };
-            let effective_power = c.power + priority_boost;
+/// Whether a creature permanent can be declared as an attacker this turn
+fn can_attack(permanent: &crate::game::zones::Permanent, state: &GameState, ardyn_on_battlefield: bool) -> bool {
+    // Must be a creature
+    let Card::Creature(creature) = &permanent.card else {
+        return false;
+    };
 
-            if effective_power > best_power {
-                best_power = effective_power;
-                best_idx = Some(idx);
-            }
-        }
+    // Check for impending counters (creature is still an enchantment)
+    if permanent.get_counter(crate::game::zones::CounterType::Time) > 0 {
+        return false;
     }
 
-    if let Some(idx) = best_idx {
-        // Get the creature name before removing
-        let creature_name = state.graveyard.cards()[idx].name().to_string();
-        // Note: creature_power is not used since token is always 5/5, but keeping for reference
-        let _creature_power = if let Card::Creature(c) = &state.graveyard.cards()[idx] {
-            c.power
-        } else {
-            5
-        };
+    // Check summoning sickness (entered before this turn)
+    // Exception: Demons have haste if Ardyn is on battlefield
+    let has_summoning_sickness = permanent.turn_entered >= state.turn;
+    if has_summoning_sickness && !(ardyn_on_battlefield && is_demon(permanent)) {
+        return false;
+    }
 
-        // Remove from graveyard and add to exile
-        if let Some(card) = state.graveyard.remove_card(idx) {
-            if verbose {
-                println!("[Starscourge] Ardyn exiles {} from graveyard", card.name());
-            }
-            state.add_to_exile(card);
-        }
+    // Check if tapped
+    if permanent.tapped {
+        return false;
+    }
 
-        // Create a 5/5 Demon token copy of the exiled creature
-        // The token has Demon creature type added so it benefits from Ardyn's abilities
-        let token = Card::Creature(crate::card::CreatureCard {
-            base: crate::card::types::BaseCard {
-                name: format!("{} (Starscourge Token)", creature_name),
-                mana_cost: Default::default(),
-                mana_value: 0,
-            },
-            power: 5,
-            toughness: 5,
-            is_legendary: false,
-            creature_types: vec!["Demon".to_string()],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+    // Value mode: don't throw creatures into blockers that would kill them
+    !(state.combat_policy == CombatPolicy::Value
+        && state.opponent_blockers.iter().any(|b| b.power >= creature.toughness))
+}
 
-        let mut perm = crate::game::zones::Permanent::new(token, state.turn);
-        perm.is_copy_of = Some(crate::game::cards::intern_card_name(&creature_name));
+/// Whether a creature cast now would be worth more as an immediate attacker than as an
+/// impending enchantment: it has haste (a Demon with Ardyn out) and its power makes
+/// this turn's attack lethal
+fn wants_attacker_now(state: &GameState, creature: &crate::card::CreatureCard) -> bool {
+    let ardyn_on_battlefield = has_ardyn_on_battlefield(state);
+    if !ardyn_on_battlefield || !creature.creature_types.iter().any(|t| t == "Demon") {
+        return false;
+    }
 
-        // Count Terrors BEFORE adding the token - Terror of the Peaks triggers on "another creature"
-        // so the token cannot trigger from itself entering
-        let terror_count = state.battlefield.permanents().iter()
-            .filter(|p| {
-                p.card.name() == "Terror of the Peaks"
-                    || p.is_copy_of.as_deref() == Some("Terror of the Peaks")
-            })
-            .count() as i32;
+    let attack: u32 = state.battlefield.permanents().iter()
+        .filter(|p| can_attack(p, state, ardyn_on_battlefield))
+        .filter_map(|p| match &p.card {
+            Card::Creature(c) => Some(c.power),
+            _ => None,
+        })
+        .sum();
+    (attack + creature.power) as i32 >= state.opponent_life
+}
 
-        state.battlefield.add_permanent(perm);
+/// Resolve Ardyn's Starscourge trigger: exile a creature from graveyard and create a 5/5 Demon token copy
+fn resolve_starscourge(state: &mut GameState, verbose: bool) {
+    let Some(creature_name) = cards::choose_starscourge_target(state) else {
+        return;
+    };
 
+    // Remove from graveyard and add to exile, keeping the copied creature's types
+    let mut creature_types = Vec::new();
+    if let Some(card) = state.graveyard.remove_card_by_name(&creature_name) {
         if verbose {
-            println!("[Starscourge] Created a 5/5 Demon token copy of {} (has haste from Ardyn)", creature_name);
+            vlog!(2, "[Starscourge] Ardyn exiles {} from graveyard", card.name());
         }
+        if let Card::Creature(c) = &card {
+            creature_types = c.creature_types.clone();
+        }
+        state.add_to_exile(card);
+    }
 
-        // Trigger Terror of the Peaks if on battlefield (for the 5/5 token entering)
-        if terror_count > 0 {
-            let terror_damage = 5 * terror_count; // Token is 5/5
-            state.opponent_life -= terror_damage;
-            if verbose {
-                println!("[Terror] {} damage from Starscourge token entering (5 power x {} Terror(s))",
-                    terror_damage, terror_count);
-            }
+    // The token is a Demon in addition to its other types so it benefits from Ardyn's abilities
+    if !creature_types.iter().any(|t| t == "Demon") {
+        creature_types.push("Demon".to_string());
+    }
+    if verbose {
+        vlog!(1, "[Starscourge] Created a 5/5 Demon token copy of {} (has haste from Ardyn)", creature_name);
+    }
+    cards::create_reanimation_token(state, &creature_name, "Starscourge", creature_types, verbose);
+}
+
+/// Power and toughness of the creature at battlefield index `idx` (0/0 for non-creatures)
+fn creature_stats(state: &GameState, idx: usize) -> (u32, u32) {
+    match state.battlefield.permanents().get(idx).map(|p| &p.card) {
+        Some(Card::Creature(c)) => (c.power, c.toughness),
+        _ => (0, 0),
+    }
+}
+
+/// Blocks for attackers ordered highest power first: each known blocker, in order, blocks
+/// the smallest unblocked attacker it kills (so blockers trade with small attackers and
+/// the big ones get through), and doesn't block if it kills none. Returns the blocker
+/// index for each attacker.
+fn assign_blocks(state: &GameState, attackers: &[usize]) -> Vec<Option<usize>> {
+    let mut blocked_by = vec![None; attackers.len()];
+    for (b, blocker) in state.opponent_blockers.iter().enumerate() {
+        let target = (0..attackers.len()).rev().find(|&i| {
+            blocked_by[i].is_none() && creature_stats(state, attackers[i]).1 <= blocker.power
+        });
+        if let Some(i) = target {
+            blocked_by[i] = Some(b);
         }
     }
+    blocked_by
 }
 
 /// Simulate combat phase: declare attackers and deal damage
 pub fn simulate_combat(state: &mut GameState, verbose: bool) -> u32 {
+    let _timer = profile::section(Section::Combat);
     let mut total_damage = 0;
 
     // Check if Ardyn is on the battlefield (for haste and Starscourge)
     let ardyn_on_battlefield = has_ardyn_on_battlefield(state);
 
-    // Resolve Starscourge trigger at beginning of combat (if Ardyn is on battlefield)
+    // Resolve Starscourge trigger at beginning of combat (if Ardyn is on battlefield).
+    // This is Ardyn's real timing - it is not an upkeep trigger.
     if ardyn_on_battlefield {
         resolve_starscourge(state, verbose);
     }
@@ -174,36 +266,19 @@ pub fn simulate_combat(state: &mut GameState, verbose: bool) -> u32 {
     let mut lifelink_damage = 0u32;
 
     for (idx, permanent) in state.battlefield.permanents().iter().enumerate() {
-        // Must be a creature
-        if !matches!(permanent.card, Card::Creature(_)) {
-            continue;
-        }
-
-        // Check for impending counters (creature is still an enchantment)
-        if permanent.get_counter(crate::game::zones::CounterType::Time) > 0 {
-            continue;
-        }
-
-        // Check summoning sickness (entered before this turn)
-        // Exception: Demons have haste if Ardyn is on battlefield
-        let has_summoning_sickness = permanent.turn_entered >= state.turn;
-        if has_summoning_sickness {
-            let demon_with_haste = ardyn_on_battlefield && is_demon(permanent);
-            if !demon_with_haste {
-                continue;
-            }
-        }
-
-        // Check if tapped
-        if permanent.tapped {
-            continue;
+        if can_attack(permanent, state, ardyn_on_battlefield) {
+            attackers.push(idx);
         }
-
-        attackers.push(idx);
     }
 
+    // Order attackers by power, highest first; the sort is stable, so equal powers keep
+    // battlefield order and the same board always blocks the same way
+    attackers.sort_by_key(|&idx| std::cmp::Reverse(creature_stats(state, idx).0));
+    let blocked_by = assign_blocks(state, &attackers);
+
     // Tap all attackers and calculate damage
-    for idx in &attackers {
+    let mut killed = Vec::new();
+    for (idx, blocker) in attackers.iter().zip(&blocked_by) {
         // Check if demon BEFORE taking mutable reference
         let is_demon_attacker = if let Some(perm) = state.battlefield.permanents().get(*idx) {
             ardyn_on_battlefield && is_demon(perm)
@@ -217,7 +292,11 @@ pub fn simulate_combat(state: &mut GameState, verbose: bool) -> u32 {
             // Get creature power (use the base creature's power - copies like Spider-Man keep their own P/T)
             if let Card::Creature(creature) = &permanent.card {
                 let power = creature.power;
-                total_damage += power;
+                match blocker {
+                    // Blocked: the damage goes to the blocker, which kills the attacker
+                    Some(_) => killed.push(*idx),
+                    None => total_damage += power,
+                }
 
                 // Track lifelink damage for Demons when Ardyn is present
                 if is_demon_attacker {
@@ -227,100 +306,239 @@ pub fn simulate_combat(state: &mut GameState, verbose: bool) -> u32 {
         }
     }
 
+    // Blockers that took lethal damage die; killed attackers go to the graveyard
+    let mut dead_blockers: Vec<usize> = attackers.iter().zip(&blocked_by)
+        .filter_map(|(&idx, blocker)| blocker.filter(|&b| creature_stats(state, idx).0 >= state.opponent_blockers[b].toughness))
+        .collect();
+    dead_blockers.sort_unstable();
+    for b in dead_blockers.into_iter().rev() {
+        state.opponent_blockers.remove(b);
+    }
+    killed.sort_unstable();
+    let mut died = Vec::new();
+    for idx in killed.into_iter().rev() {
+        if let Some(perm) = state.battlefield.remove_permanent(idx) {
+            if verbose {
+                vlog!(1, "[Combat] {} is blocked and dies", perm.card.name());
+            }
+            state.add_to_graveyard(perm.card.clone());
+            died.push(perm);
+        }
+    }
+    cards::resolve_creature_deaths(state, &died, verbose);
+
     // Deal damage to opponent
     state.opponent_life -= total_damage as i32;
 
     // Gain life from lifelink
     if lifelink_damage > 0 {
-        state.life += lifelink_damage as i32;
+        state.gain_life(lifelink_damage);
         if verbose {
-            println!("[Combat] Gained {} life from Demon lifelink", lifelink_damage);
+            vlog!(2, "[Combat] Gained {} life from Demon lifelink", lifelink_damage);
         }
     }
 
     if verbose && total_damage > 0 {
-        println!("[Combat] {} damage dealt", total_damage);
+        vlog!(1, "[Combat] {} damage dealt", total_damage);
     }
 
     total_damage
 }
 
-/// Execute a single turn: untap -> draw -> main -> combat -> end
+/// Execute a single turn: untap -> upkeep -> draw -> main -> combat -> main 2 -> end
 pub fn execute_turn(state: &mut GameState, db: &CardDatabase, verbose: bool, rng: &mut crate::rng::GameRng) -> u32 {
+    begin_turn(state, verbose, rng);
+    if state.has_lost() {
+        return 0;
+    }
+    finish_turn(state, db, verbose, rng)
+}
+
+/// Choose a land to play, logging why at -vvv
+fn choose_land_logged(hand: &[Card], state: &GameState, verbose: bool) -> Option<usize> {
+    if !(verbose && crate::log::enabled(3)) {
+        return DecisionEngine::choose_land_to_play(hand, state);
+    }
+    let decision = DecisionEngine::choose_land_to_play_with_reason(hand, state)?;
+    vlog!(3, "  [Choose] {}: {}", hand[decision.index].name(), decision.reason);
+    Some(decision.index)
+}
+
+/// Choose a spell to cast from hand, logging why at -vvv
+fn choose_card_logged(state: &GameState, verbose: bool) -> Option<usize> {
+    if !(verbose && crate::log::enabled(3)) {
+        return DecisionEngine::choose_card_to_play(state);
+    }
+    let ranked: Vec<String> = DecisionEngine::castable_with_rules(state).into_iter()
+        .map(|(idx, rule)| format!("{} ({})", state.hand.cards()[idx].name(), rule.label()))
+        .collect();
+    if !ranked.is_empty() {
+        vlog!(3, "  [Castable] {}", ranked.join(", "));
+    }
+    let decision = DecisionEngine::choose_card_to_play_with_reason(state)?;
+    vlog!(3, "  [Choose] {}: {}", state.hand.cards()[decision.index].name(), decision.reason);
+    Some(decision.index)
+}
+
+/// Move the turn on to `phase` (the engine always steps through phases in order)
+fn enter_phase(state: &mut GameState, phase: Phase) {
+    if let Err(e) = state.advance_phase(phase) {
+        panic!("Turn {}: {}", state.turn, e);
+    }
+}
+
+/// First half of a turn: untap, upkeep, draw and the start of precombat main
+/// (saga chapters), stopping before any land or spell is played
+pub fn begin_turn(state: &mut GameState, verbose: bool, rng: &mut GameRng) {
     // Start turn: increment turn counter, untap, reset land drop
     start_turn(state);
 
     if verbose {
-        println!("\n=== TURN {} ===", state.turn);
+        vlog!(1, "\n=== TURN {} ===", state.turn);
+    }
+
+    // The opponent's attack and graveyard hate resolve before our upkeep
+    state.apply_opponent_attack(verbose);
+    if state.life <= 0 {
+        if verbose {
+            vlog!(1, "[Opponent] We're dead");
+        }
+        return;
     }
+    state.apply_graveyard_hate(verbose);
 
     // Upkeep phase
-    upkeep_phase(state);
+    enter_phase(state, Phase::Upkeep);
+    upkeep_phase(state, verbose);
 
     // Draw phase
-    state.phase = crate::game::state::Phase::Draw;
+    enter_phase(state, Phase::Draw);
     let hand_before = state.hand.size();
     draw_phase(state);
+    if state.decked_out {
+        if verbose {
+            vlog!(1, "[Draw] Library is empty - we lose");
+        }
+        return;
+    }
 
     if verbose {
         if state.hand.size() > hand_before {
             // Get the last card drawn
             if let Some(card) = state.hand.cards().last() {
-                println!("[Draw] Drew: {}", card.name());
+                vlog!(1, "[Draw] Drew: {}", card.name());
             }
-        } else if state.turn == 1 && state.on_the_play {
-            println!("[Draw] Skipped (on the play)");
+        } else if skips_draw(state) {
+            vlog!(1, "[Draw] Skipped (on the play)");
         }
     }
 
     // Main phase 1: Play lands and cast spells
-    state.phase = crate::game::state::Phase::Main1;
+    enter_phase(state, Phase::Main1);
 
     // Precombat main phase start: advance saga counters and resolve chapters
     // Per MTG rules, saga lore counters are added at the beginning of precombat main phase
-    precombat_main_phase_start(state, verbose);
+    precombat_main_phase_start(state, rng, verbose);
     if verbose {
         let hand_names: Vec<&str> = state.hand.cards().iter().map(|c| c.name()).collect();
-        println!("[Main 1] Hand: {}", hand_names.join(", "));
+        vlog!(1, "[Main 1] Hand: {}", hand_names.join(", "));
     }
+}
+
+/// Second half of a turn, after `begin_turn`: main phase, combat and end step.
+/// Returns the combat damage dealt.
+pub fn finish_turn(state: &mut GameState, db: &CardDatabase, verbose: bool, rng: &mut crate::rng::GameRng) -> u32 {
     execute_main_phase(state, db, verbose, rng);
 
     // Combat phase
-    state.phase = crate::game::state::Phase::Combat;
+    enter_phase(state, Phase::Combat);
     let combat_damage = simulate_combat(state, verbose);
 
+    // Compare what the combo actually dealt with what was estimated
+    if let Some((estimate, life_before)) = state.combo_estimate.take() {
+        if state.record_events {
+            state.events.push(GameEvent::ComboResolved {
+                turn: state.turn,
+                estimate,
+                life_before,
+                life_after: state.opponent_life,
+            });
+        }
+        if verbose {
+            if let Some(message) = combo_estimate_discrepancy(estimate, life_before, state.opponent_life) {
+                vlog!(1, "[Combo check] {}", message);
+            }
+        }
+    }
+
     // Main phase 2: Additional spell casting could happen here
-    state.phase = crate::game::state::Phase::Main2;
+    enter_phase(state, Phase::Main2);
     // For now, we don't do anything in main 2
 
     // End phase
-    state.phase = crate::game::state::Phase::End;
+    enter_phase(state, Phase::End);
     end_phase(state);
 
     if verbose {
-        println!("[End of Turn {}]", state.turn);
-        let battlefield_names: Vec<String> = state.battlefield.permanents()
-            .iter()
-            .map(|p| {
-                let mut name = p.card.name().to_string();
-                if let Some(copy_of) = &p.is_copy_of {
-                    name.push_str(&format!(" (copy of {})", copy_of));
-                }
-                if let Some(time_counters) = p.counters.get(&crate::game::zones::CounterType::Time) {
-                    name.push_str(&format!(" ({} time counters)", time_counters));
-                }
-                name
-            })
-            .collect();
-        println!("  Battlefield: {}", if battlefield_names.is_empty() { "(empty)".to_string() } else { battlefield_names.join(", ") });
+        vlog!(1, "{}", format_end_of_turn(state).trim_end());
+    }
 
-        let graveyard_names: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
-        println!("  Graveyard: {}", if graveyard_names.is_empty() { "(empty)".to_string() } else { graveyard_names.join(", ") });
+    combat_damage
+}
 
-        println!("  Opponent life: {}", state.opponent_life);
+/// Compare the combo damage estimate with the life actually lost by the end of combat.
+/// Returns a description if a combo estimated as lethal failed to kill, or if the
+/// opponent lost less life than estimated.
+pub fn combo_estimate_discrepancy(estimate: u32, life_before: i32, life_after: i32) -> Option<String> {
+    let actual = life_before - life_after;
+    let estimated_lethal = estimate as i32 >= life_before;
+    if estimated_lethal && life_after > 0 {
+        Some(format!("estimated lethal ({} damage vs {} life) but opponent survived at {}", estimate, life_before, life_after))
+    } else if actual < estimate as i32 {
+        Some(format!("estimated {} damage but only {} dealt", estimate, actual))
+    } else {
+        None
     }
+}
 
-    combat_damage
+/// Format the verbose end-of-turn state: battlefield (with tapped lands marked),
+/// currently available colors from untapped lands, graveyard and opponent life
+pub fn format_end_of_turn(state: &GameState) -> String {
+    let mut out = format!("[End of Turn {}]\n", state.turn);
+    let battlefield_names: Vec<String> = state.battlefield.permanents()
+        .iter()
+        .map(|p| {
+            let mut name = p.card.name().to_string();
+            if let Some(copy_of) = &p.is_copy_of {
+                name.push_str(&format!(" (copy of {})", copy_of));
+            }
+            if let Some(time_counters) = p.counters.get(&crate::game::zones::CounterType::Time) {
+                name.push_str(&format!(" ({} time counters)", time_counters));
+            }
+            if matches!(p.card, Card::Land(_)) && p.tapped {
+                name.push_str(" (tapped)");
+            }
+            name
+        })
+        .collect();
+    out.push_str(&format!("  Battlefield: {}\n", if battlefield_names.is_empty() { "(empty)".to_string() } else { battlefield_names.join(", ") }));
+
+    let colors = get_available_colors(state, true);
+    let color_chars: String = [
+        ManaColor::White, ManaColor::Blue, ManaColor::Black,
+        ManaColor::Red, ManaColor::Green, ManaColor::Colorless,
+    ]
+        .iter()
+        .filter(|c| colors.contains(**c))
+        .map(|c| c.to_char())
+        .collect();
+    out.push_str(&format!("  Available colors: {}\n", if color_chars.is_empty() { "(none)" } else { &color_chars }));
+
+    let graveyard_names: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
+    out.push_str(&format!("  Graveyard: {}\n", if graveyard_names.is_empty() { "(empty)".to_string() } else { graveyard_names.join(", ") }));
+
+    out.push_str(&format!("  Opponent life: {}\n", state.opponent_life));
+    out
 }
 
 /// Get mana cost from a card
@@ -338,6 +556,14 @@ fn get_mana_cost(card: &Card) -> &crate::card::ManaCost {
 /// Port of TypeScript mainPhase function (lines 2211-2502)
 /// Core game logic that determines what spells to cast and in what order
 pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng: &mut crate::rng::GameRng) {
+    let _timer = profile::section(Section::MainPhase);
+    if !state.can_cast_sorcery_speed() {
+        if verbose {
+            vlog!(3, "[{}] Not a main phase, nothing can be played", state.phase);
+        }
+        return;
+    }
+
     // SPECIAL CASE: Turn 4 combo check
     // If we have Spider-Man in hand, and a valid combo target in GY, and can get to 4 mana by playing a land,
     // play the land FIRST before casting any other spells!
@@ -358,7 +584,7 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
         .filter(|p| matches!(p.card, Card::Land(_)) && !p.tapped)
         .count() as u32;
 
-    if has_spider_man && has_valid_combo_target && current_mana == 3 && !state.land_played_this_turn {
+    if has_spider_man && has_valid_combo_target && current_mana == 3 && state.can_play_land() {
         // Check if we have an untapped land to play
         let hand_cards = state.hand.cards().to_vec();
         if let Some(untapped_land_idx) = hand_cards.iter().position(|c| {
@@ -373,7 +599,7 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
                 let land_name = untapped_land.name().to_string();
                 let _ = cards::play_land(state, &untapped_land, verbose);
                 if verbose {
-                    println!("  [COMBO SETUP] Played {} first to enable turn 4 combo", land_name);
+                    vlog!(1, "  [COMBO SETUP] Played {} first to enable turn 4 combo", land_name);
                 }
             }
         }
@@ -401,8 +627,8 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
             return true;
         }
 
-        // If we've already played a land, no look-ahead needed
-        if state.land_played_this_turn {
+        // If we've already used our land drops, no look-ahead needed
+        if !state.can_play_land() {
             return false;
         }
 
@@ -483,7 +709,7 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
     let speaker_can_tutor_for_combo = has_bringer_in_graveyard_early
         && !has_spider_man_in_hand_early
         && formidable_speaker_in_hand.is_some()
-        && formidable_speaker_in_hand.map_or(false, |s| mana::can_cast_spell(s, state));
+        && formidable_speaker_in_hand.is_some_and(|s| mana::can_cast_spell(s, state));
 
     let should_prioritize_discard_spell = (has_bringer_or_terror_in_hand
         && (kiora_in_hand.is_some() || formidable_speaker_in_hand.is_some())
@@ -492,7 +718,7 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
 
     // SPECIAL CASE: Analyze the Pollen early casting
     // Cast it early (without evidence) if:
-    // 1. We need a land for UBG color fixing, OR
+    // 1. We need a land for color fixing, OR
     // 2. Getting to 4 mana enables combo next turn (have Spider-Man + Bringer in GY)
     let analyze_pollen_idx = state.hand.cards().iter()
         .position(|c| c.name() == "Analyze the Pollen");
@@ -501,11 +727,7 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
         let can_cast_pollen = mana::can_cast_spell(&state.hand.cards()[pollen_idx], state);
 
         if can_cast_pollen {
-            let available_colors = get_available_colors(state);
-            let has_u = available_colors.has_blue();
-            let has_b = available_colors.has_black();
-            let has_g = available_colors.has_green();
-            let has_all_colors = has_u && has_b && has_g;
+            let missing_colors = state.color_identity.missing(get_available_colors(state, false));
 
             // Count untapped lands
             let untapped_lands = state.battlefield.permanents().iter()
@@ -522,17 +744,13 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
             let has_land_in_hand = state.hand.cards().iter()
                 .any(|c| matches!(c, Card::Land(_)));
 
-            // Condition 1: Need a basic land for UBG color fixing
-            // (missing a color and don't have a land that provides it)
-            let needs_color_fixing = !has_all_colors && {
+            // Condition 1: Need a basic land for color fixing
+            // (missing one of the deck's colors and don't have a land that provides it)
+            let needs_color_fixing = !missing_colors.is_empty() && {
                 // Check if we have a land in hand that would fix our colors
                 let hand_would_fix = state.hand.cards().iter()
                     .filter_map(|c| if let Card::Land(l) = c { Some(l) } else { None })
-                    .any(|land| {
-                        (!has_u && land.colors.iter().any(|c| *c == ManaColor::Blue)) ||
-                        (!has_b && land.colors.iter().any(|c| *c == ManaColor::Black)) ||
-                        (!has_g && land.colors.iter().any(|c| *c == ManaColor::Green))
-                    });
+                    .any(|land| land.colors.iter().any(|c| missing_colors.contains(*c)));
                 // If we don't have a land in hand that fixes, we should Analyze
                 !hand_would_fix
             };
@@ -545,7 +763,7 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
                 // If we have 2 lands and a land in hand, pollen would get us to 4
                 // If we have 3 lands and no land in hand, pollen would get us to 4
                 (untapped_lands == 2 && has_land_in_hand) ||
-                (untapped_lands == 3 && !has_land_in_hand && !state.land_played_this_turn)
+                (untapped_lands == 3 && !has_land_in_hand && state.can_play_land())
             };
 
             let should_cast_pollen_early = needs_color_fixing || enables_combo_next_turn;
@@ -557,12 +775,13 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
                     if mana::tap_lands_for_cost(cost, state, None) {
                         if verbose {
                             if needs_color_fixing {
-                                println!("  [Cast] Analyze the Pollen (color fixing - no evidence)");
+                                vlog!(1, "  [Cast] Analyze the Pollen (color fixing - no evidence)");
                             } else {
-                                println!("  [Cast] Analyze the Pollen (enabling combo - no evidence)");
+                                vlog!(1, "  [Cast] Analyze the Pollen (enabling combo - no evidence)");
                             }
                         }
-                        let _ = cards::cast_spell(state, &card, db, verbose, rng);
+                        let _ = cards::cast_spell(state, &card, cards::SpellDestination::Graveyard, db, verbose, rng);
+                        cards::resolve_spell_cast_triggers(state, verbose);
                     } else {
                         // Put it back if we can't pay
                         state.hand.add_card(card);
@@ -572,10 +791,10 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
         }
     }
 
-    if !state.land_played_this_turn && !should_prioritize_discard_spell {
+    if state.can_play_land() && !should_prioritize_discard_spell {
         let mut cast_any = true;
 
-        while cast_any && !state.land_played_this_turn {
+        while cast_any && state.can_play_land() {
             cast_any = false;
 
             // Land-finding spells (from TypeScript LAND_FINDING_SPELLS)
@@ -586,12 +805,9 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
             ];
 
             // Find castable land-finding spells
-            let mut castable_finders: Vec<(usize, &Card)> = state.hand.cards()
-                .iter()
-                .enumerate()
-                .filter(|(_, c)| {
-                    LAND_FINDERS.contains(&c.name()) && mana::can_cast_spell(c, state)
-                })
+            let mut castable_finders: Vec<(usize, &Card)> = state.hand.castable_spells(state)
+                .into_iter()
+                .filter(|(_, c)| LAND_FINDERS.contains(&c.name()))
                 .collect();
 
             if !castable_finders.is_empty() {
@@ -627,18 +843,19 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
                                 }
                             }
                         } else {
-                            let _ = cards::cast_spell(state, &card, db, verbose, rng);
+                            let _ = cards::cast_spell(state, &card, cards::SpellDestination::Graveyard, db, verbose, rng);
                         }
 
                         if verbose {
-                            println!("  [Cast] {}", card_name);
+                            vlog!(1, "  [Cast] {}", card_name);
                         }
+                        cards::resolve_spell_cast_triggers(state, verbose);
                         cast_any = true;
 
                         // Check if we found a land
                         let lands_after = state.hand.cards().iter().filter(|c| matches!(c, Card::Land(_))).count();
                         if lands_after > lands_before && verbose {
-                            println!("  [Land-finder] Found a land");
+                            vlog!(2, "  [Land-finder] Found a land");
                         }
                     } else {
                         // Put it back if we can't pay
@@ -651,34 +868,34 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
         }
     }
 
-    // STEP 2: Now play a land (possibly one we just found from milling)
-    if !state.land_played_this_turn {
+    // STEP 2: Now play lands (possibly ones we just found from milling), one per land drop
+    while state.can_play_land() {
         let hand_cards = state.hand.cards().to_vec();
-        let lands_in_hand: Vec<&Card> = hand_cards.iter()
-            .filter(|c| matches!(c, Card::Land(_)))
-            .collect();
+        if !hand_cards.iter().any(|c| matches!(c, Card::Land(_))) {
+            break;
+        }
 
-        if !lands_in_hand.is_empty() {
-            // Use DecisionEngine to choose the best land
-            if let Some(land_idx) = DecisionEngine::choose_land_to_play(&hand_cards, state) {
-                if let Some(card) = state.hand.remove_card(land_idx) {
-                    let card_name = card.name().to_string();
-                    let _ = cards::play_land(state, &card, verbose);
+        // Use DecisionEngine to choose the best land
+        let Some(land_idx) = choose_land_logged(&hand_cards, state, verbose) else {
+            break;
+        };
+        let Some(card) = state.hand.remove_card(land_idx) else {
+            break;
+        };
+        let card_name = card.name().to_string();
+        let _ = cards::play_land(state, &card, verbose);
 
-                    // DO NOT tap the land here - TypeScript taps lands DURING casting
-                    // This allows can_cast_spell to correctly see the new untapped land
+        // DO NOT tap the land here - TypeScript taps lands DURING casting
+        // This allows can_cast_spell to correctly see the new untapped land
 
-                    if verbose {
-                        let last_perm = state.battlefield.permanents().last();
-                        let tapped_str = if let Some(perm) = last_perm {
-                            if perm.tapped { " (tapped)" } else { "" }
-                        } else {
-                            ""
-                        };
-                        println!("  [Land] {}{}", card_name, tapped_str);
-                    }
-                }
-            }
+        if verbose {
+            let last_perm = state.battlefield.permanents().last();
+            let tapped_str = if let Some(perm) = last_perm {
+                if perm.tapped { " (tapped)" } else { "" }
+            } else {
+                ""
+            };
+            vlog!(1, "  [Land] {}{}", card_name, tapped_str);
         }
     }
 
@@ -687,168 +904,43 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
     while cast_any {
         cast_any = false;
 
-        // Get game state for spell priorities
+        // Get game state for the held-combo log
         let has_bringer_in_graveyard = state.graveyard.cards().iter()
             .any(|c| c.name() == "Bringer of the Last Gift");
-        let has_bringer_in_hand = state.hand.cards().iter()
-            .any(|c| c.name() == "Bringer of the Last Gift");
-        let has_terror_in_hand = state.hand.cards().iter()
-            .any(|c| c.name() == "Terror of the Peaks");
 
         // Check if the combo would be lethal
         let combo_is_lethal = has_bringer_in_graveyard && cards::is_combo_lethal(state);
         let has_spider_man_in_hand = state.hand.cards().iter()
             .any(|c| c.name() == "Superior Spider-Man");
 
-        // Log when we're holding back the combo
-        if verbose && has_bringer_in_graveyard && has_spider_man_in_hand && !combo_is_lethal {
-            let expected_damage = cards::calculate_combo_damage(state);
-            println!(
-                "  [Waiting] Combo not lethal yet (expected: {} damage, need: {})",
-                expected_damage, state.opponent_life
-            );
+        // Remember the first turn we held back a combo we could have cast
+        if has_bringer_in_graveyard && has_spider_man_in_hand && !combo_is_lethal
+            && state.held_combo_turn.is_none()
+            && state.combo_policy == ComboPolicy::WaitForLethal
+            && state.hand.cards().iter().any(|c| c.name() == "Superior Spider-Man" && mana::can_cast_spell(c, state))
+        {
+            state.held_combo_turn = Some(state.turn);
         }
 
-        // Get castable spells
-        let mut castable_spells: Vec<(usize, &Card)> = state.hand.cards()
-            .iter()
-            .enumerate()
-            .filter(|(_, c)| {
-                if matches!(c, Card::Land(_)) {
-                    return false;
-                }
-                if !mana::can_cast_spell(c, state) {
-                    return false;
-                }
-
-                // Spider-Man casting logic:
-                // 1. If Bringer in graveyard and combo is lethal -> cast (THE COMBO!)
-                // 2. If Ardyn in graveyard with other creatures -> cast (ARDYN COMBO!)
-                // 3. If no combo available but have 2+ Spider-Man in hand AND
-                //    a mill creature in graveyard -> cast to dig for combo pieces
-                if c.name() == "Superior Spider-Man" {
-                    if has_bringer_in_graveyard {
-                        // Only cast if combo would be lethal
-                        if !combo_is_lethal {
-                            return false; // Wait until it would kill
-                        }
-                    } else {
-                        // No Bringer in graveyard - check for Ardyn combo path
-                        let has_ardyn_in_gy = state.graveyard.cards().iter()
-                            .any(|card| card.name() == "Ardyn, the Usurper");
-
-                        // Count creatures in GY that aren't Ardyn (for Starscourge targets)
-                        let other_creatures_in_gy = state.graveyard.cards().iter()
-                            .filter(|card| matches!(card, Card::Creature(_)) && card.name() != "Ardyn, the Usurper")
-                            .count();
-
-                        // Allow casting Spider-Man if Ardyn is in GY with targets for Starscourge
-                        if has_ardyn_in_gy && other_creatures_in_gy >= 1 {
-                            // Allow casting to reanimate Ardyn - Starscourge creates 5/5 Demons with haste
-                            // This path is valid even if not immediately lethal
-                        } else {
-                            // No Ardyn combo - check if we should dig
-                            let spider_man_count = state.hand.cards().iter()
-                                .filter(|card| card.name() == "Superior Spider-Man")
-                                .count();
-                            let has_mill_creature_in_gy = state.graveyard.cards().iter()
-                                .any(|card| matches!(card.name(),
-                                    "Overlord of the Balemurk" |
-                                    "Kiora, the Rising Tide" |
-                                    "Town Greeter"));
-
-                            if spider_man_count < 2 || !has_mill_creature_in_gy {
-                                return false; // Can't dig effectively
-                            }
-                            // Otherwise, allow casting to dig for combo pieces
-                        }
-                    }
-                }
-
-                true
-            })
-            .collect();
-
-        if castable_spells.is_empty() {
-            break;
+        // Log when we're holding back the combo
+        if verbose && crate::log::enabled(3) && has_bringer_in_graveyard && has_spider_man_in_hand && !combo_is_lethal {
+            let expected = cards::combo_damage_breakdown(state);
+            // Sampled from a generator derived from the game's seed and the turn, so a logged
+            // game draws the same cards as a quiet one
+            let next_mill = cards::combo_damage_distribution(
+                state, WAITING_MILL_SIZE, WAITING_MILL_SAMPLES, &mut rng.derived(state.turn as u64),
+            );
+            vlog!(3,
+                "  [Waiting] Combo not lethal yet (expected: {} damage = {} Terror + {} combat, need: {}, short by {}; \
+                 each extra creature returned adds its power x {} Terror{}; P(lethal next turn): {:.0}% after a mill {})",
+                expected.total(), expected.terror, expected.combat, state.opponent_life,
+                state.opponent_life - expected.total() as i32,
+                expected.terrors, if expected.terrors == 1 { "" } else { "s" },
+                next_mill.p_lethal(state.opponent_life) * 100.0, WAITING_MILL_SIZE
+            );
         }
 
-        // Sort by priority
-        castable_spells.sort_by(|a, b| {
-            let (_, a_card) = a;
-            let (_, b_card) = b;
-
-            // Priority 1: Spider-Man if combo is lethal
-            if combo_is_lethal {
-                if a_card.name() == "Superior Spider-Man" {
-                    return std::cmp::Ordering::Less;
-                }
-                if b_card.name() == "Superior Spider-Man" {
-                    return std::cmp::Ordering::Greater;
-                }
-            }
-
-            // Priority 1.5: Formidable Speaker if Bringer in GY but no Spider-Man
-            // (Speaker can discard something to tutor for Spider-Man!)
-            if has_bringer_in_graveyard && !has_spider_man_in_hand {
-                if a_card.name() == "Formidable Speaker" {
-                    return std::cmp::Ordering::Less;
-                }
-                if b_card.name() == "Formidable Speaker" {
-                    return std::cmp::Ordering::Greater;
-                }
-            }
-
-            // Priority 2: Kiora or Formidable Speaker if Bringer/Terror in hand
-            // (These can discard combo pieces to the graveyard)
-            if has_bringer_in_hand || has_terror_in_hand {
-                // Prefer Formidable Speaker slightly (cheaper at 3 mana vs Kiora's 3)
-                // and it tutors for Spider-Man
-                if a_card.name() == "Formidable Speaker" {
-                    return std::cmp::Ordering::Less;
-                }
-                if b_card.name() == "Formidable Speaker" {
-                    return std::cmp::Ordering::Greater;
-                }
-                if a_card.name() == "Kiora, the Rising Tide" {
-                    return std::cmp::Ordering::Less;
-                }
-                if b_card.name() == "Kiora, the Rising Tide" {
-                    return std::cmp::Ordering::Greater;
-                }
-            }
-
-            // Priority 3: Mill spells
-            let mill_spells = vec![
-                "Cache Grab",
-                "Dredger's Insight",
-                "Town Greeter",
-                "Overlord of the Balemurk",
-            ];
-            let a_is_mill = mill_spells.contains(&a_card.name());
-            let b_is_mill = mill_spells.contains(&b_card.name());
-            if a_is_mill && !b_is_mill {
-                return std::cmp::Ordering::Less;
-            }
-            if b_is_mill && !a_is_mill {
-                return std::cmp::Ordering::Greater;
-            }
-
-            // Priority 4: Awaken the Honored Dead
-            if a_card.name() == "Awaken the Honored Dead" && !b_is_mill {
-                return std::cmp::Ordering::Less;
-            }
-            if b_card.name() == "Awaken the Honored Dead" && !a_is_mill {
-                return std::cmp::Ordering::Greater;
-            }
-
-            // Priority 5: Cheaper spells
-            a_card.mana_value().cmp(&b_card.mana_value())
-        });
-
-        if !castable_spells.is_empty() {
-            let (spell_idx, _spell) = castable_spells[0];
-
+        if let Some(spell_idx) = choose_card_logged(state, verbose) {
             if let Some(card) = state.hand.remove_card(spell_idx) {
                 let card_name = card.name().to_string();
 
@@ -860,10 +952,12 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
 
                 // Determine if we should use impending cost
                 // For creatures with impending, prefer impending (it's cheaper and triggers immediately)
+                // unless the full cost is affordable and we want the body attacking this turn
                 let (use_impending, cost) = if let Some(creature) = for_creature {
                     if let Some(impending_cost) = &creature.impending_cost {
-                        // Prefer impending when we can afford it
-                        if mana::can_afford_cost(impending_cost, state, for_creature) {
+                        let hardcast = wants_attacker_now(state, creature)
+                            && mana::can_afford_cost(&creature.base.mana_cost, state, for_creature);
+                        if !hardcast && mana::can_afford_cost(impending_cost, state, for_creature) {
                             (true, impending_cost.clone())
                         } else {
                             (false, get_mana_cost(&card).clone())
@@ -875,9 +969,26 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
                     (false, get_mana_cost(&card).clone())
                 };
 
-                if mana::tap_lands_for_cost(&cost, state, for_creature) {
+                if mana::pay_spell_cost(&card, &cost, state, for_creature) {
+                    // The opponent counters Spider-Man while they have counterspells left
+                    // (and it isn't protected from them)
+                    let counterspells = for_creature
+                        .filter(|_| card_name == "Superior Spider-Man")
+                        .and_then(|c| state.counterspells_to_counter(c));
                     match &card {
+                        Card::Creature(_) if let Some(countered) = counterspells => {
+                            state.opponent_counters -= countered;
+                            state.record_spell_cast();
+                            state.add_to_graveyard(card.clone());
+                            if verbose {
+                                vlog!(1, "  [Countered] {} ({} counterspells left)", card_name, state.opponent_counters);
+                            }
+                        }
                         Card::Creature(_) => {
+                            // Casting Spider-Man onto a Bringer graveyard is the combo: remember the estimate
+                            if card_name == "Superior Spider-Man" && has_bringer_in_graveyard {
+                                state.combo_estimate = Some((cards::calculate_combo_damage(state), state.opponent_life));
+                            }
                             let _ = cards::cast_creature(state, &card, use_impending);
 
                             // Process ETB triggers
@@ -898,25 +1009,26 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
 
                             if verbose {
                                 if use_impending {
-                                    println!("  [Cast] {} (impending)", card_name);
+                                    vlog!(1, "  [Cast] {} (impending)", card_name);
                                 } else {
-                                    println!("  [Cast] {}", card_name);
+                                    vlog!(1, "  [Cast] {}", card_name);
                                 }
                             }
                         }
                         Card::Land(_) => {
                             let _ = cards::play_land(state, &card, verbose);
                             if verbose {
-                                println!("  [Land] {}", card_name);
+                                vlog!(1, "  [Land] {}", card_name);
                             }
                         }
                         Card::Instant(_) | Card::Sorcery(_) | Card::Enchantment(_) | Card::Saga(_) => {
-                            let _ = cards::cast_spell(state, &card, db, verbose, rng);
+                            let _ = cards::cast_spell(state, &card, cards::SpellDestination::Graveyard, db, verbose, rng);
                             if verbose {
-                                println!("  [Cast] {}", card_name);
+                                vlog!(1, "  [Cast] {}", card_name);
                             }
                         }
                     }
+                    cards::resolve_spell_cast_triggers(state, verbose);
 
                     cast_any = true;
                 } else {
@@ -924,6 +1036,22 @@ pub fn main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, rng:
                     state.hand.add_card(card);
                 }
             }
+        } else if let Some(gy_idx) = DecisionEngine::choose_flashback_spell(state) {
+            // Nothing left to cast from hand - try flashback-style spells in the graveyard
+            // The spell leaves the graveyard as it's cast, so delve can't exile it to pay for itself
+            if let Some(card) = state.graveyard.remove_card(gy_idx) {
+                if mana::pay_spell_cost(&card, get_mana_cost(&card), state, None) {
+                    let _ = cards::cast_spell(state, &card, cards::SpellDestination::Exile, db, verbose, rng);
+                    if verbose {
+                        vlog!(1, "  [Cast] {} (from graveyard)", card.name());
+                    }
+                    cards::resolve_spell_cast_triggers(state, verbose);
+                    cast_any = true;
+                } else {
+                    // Put it back where it was if we can't pay
+                    state.graveyard.insert_card(gy_idx, card);
+                }
+            }
         }
     }
 }
@@ -935,95 +1063,386 @@ fn execute_main_phase(state: &mut GameState, db: &CardDatabase, verbose: bool, r
     main_phase(state, db, verbose, rng);
 }
 
-/// Run a complete game simulation
-pub fn run_game(
-    deck: &[Card],
-    seed: u64,
-    _db: &CardDatabase,
-    verbose: bool,
-) -> GameResult {
-    let mut rng = GameRng::new(Some(seed));
-
-    // Initialize game state
-    let mut state = GameState::new();
+thread_local! {
+    /// Per-thread game state and library scratch buffer, reused across games to avoid
+    /// reallocating zones for every simulated game
+    static GAME_SCRATCH: RefCell<(GameState, Vec<Card>)> = RefCell::new((GameState::new(), Vec::new()));
+}
 
-    // Determine if on play or draw (50/50) - BEFORE shuffling to match TypeScript RNG sequence
-    state.on_the_play = rng.random() < 0.5;
+/// Shuffle the deck, resolve mulligans and set up the library and opening hand.
+/// `scratch` is a reusable buffer for the shuffled library; `state` should be freshly reset.
+pub fn setup_game(state: &mut GameState, deck: &[Card], rng: &mut GameRng, scratch: &mut Vec<Card>) {
+    // Determine if on play or draw (50/50 by default) - BEFORE shuffling to match TypeScript RNG sequence.
+    // The coin-flip value is drawn even when play_probability forces the result (1.0 or 0.0),
+    // so a seed shuffles and mulligans identically whichever way the start is decided.
+    state.on_the_play = rng.random() < state.play_probability;
+    state.color_identity = DeckColorIdentity::from_deck(deck);
 
-    // Shuffle deck into library
-    let mut shuffled_deck = deck.to_vec();
-    rng.shuffle(&mut shuffled_deck);
-    for card in shuffled_deck {
-        state.library.add_card(card);
-    }
+    // Shuffle deck into library order
+    scratch.clear();
+    scratch.extend_from_slice(deck);
+    rng.shuffle(scratch);
 
     // Mulligan phase: resolve mulligans to get opening hand
-    let mut library_cards = Vec::new();
-    for _ in 0..state.library.size() {
-        if let Some(card) = state.library.draw() {
-            library_cards.push(card);
-        }
-    }
-
-    let opening_hand = resolve_mulligans(&mut library_cards, &mut rng);
+    let opening_hand = {
+        let _timer = profile::section(Section::Mulligan);
+        let events = state.record_events.then_some(&mut state.events);
+        resolve_mulligans(scratch, rng, state.opening_hand_size, events)
+    };
 
-    // Put remaining cards back in library
-    for card in library_cards {
+    // Remaining cards form the library
+    for card in scratch.drain(..) {
         state.library.add_card(card);
     }
 
-    // Add opening hand to hand
-    for card in opening_hand.clone() {
+    for card in opening_hand {
+        state.count_drawn(&card);
         state.hand.add_card(card);
     }
+}
 
-    // Print game start info if verbose
-    if verbose {
-        println!("=== Game Start (seed: {}) ===", seed);
-        println!("{}", if state.on_the_play { "On the play" } else { "On the draw" });
-        println!("Opening hand ({} cards):", opening_hand.len());
-        for card in &opening_hand {
-            println!("  - {}", card.name());
+/// Maximum number of reshuffles when searching for an opening hand that contains required cards
+pub const MAX_OPENING_HAND_RESHUFFLES: u32 = 10_000;
+
+/// Starting from `seed`, find the first seed whose kept opening hand (after mulligans)
+/// contains every card in `required`, drawing `opening_hand_size` cards before mulligans.
+/// Each reshuffle moves to a new, unrelated seed.
+/// Returns the seed to play and how many reshuffles were needed, or None if not found.
+pub fn find_seed_with_opening_hand(deck: &[Card], seed: u64, required: &[String], opening_hand_size: usize) -> Option<(u64, u32)> {
+    let mut state = GameState::new();
+    state.opening_hand_size = opening_hand_size;
+    let mut scratch = Vec::with_capacity(deck.len());
+    let mut candidate = seed;
+
+    for reshuffles in 0..=MAX_OPENING_HAND_RESHUFFLES {
+        state.reset();
+        setup_game(&mut state, deck, &mut GameRng::new(Some(candidate)), &mut scratch);
+        let has_all = required.iter()
+            .all(|name| state.hand.cards().iter().any(|c| c.name() == name));
+        if has_all {
+            return Some((candidate, reshuffles));
         }
+        // LCG step so retries don't collide with neighbouring batch seeds
+        candidate = candidate.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
     }
-    
-    // Game loop
-    let max_turns = 20u32;
-    let mut turn_with_ubg = None;
+    None
+}
 
-    while state.turn < max_turns && !check_win_condition(&state) {
-        // Execute turn
-        execute_turn(&mut state, _db, verbose, &mut rng);
+/// Last turn a game is played to
+pub const MAX_TURNS: u32 = 20;
 
-        // Track when all colors become available
-        if turn_with_ubg.is_none() {
-            let colors = get_available_colors(&state);
-            if colors.has_blue() && colors.has_black() && colors.has_green() {
-                turn_with_ubg = Some(state.turn);
-            }
+/// Settings that change how a game is played, applied to the state before it starts
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    pub combat_policy: CombatPolicy,
+    pub combo_policy: ComboPolicy,
+    pub surveil_policy: SurveilPolicy,
+    pub land_policy: LandPolicy,
+    pub graveyard_hate: Option<GraveyardHate>,
+    /// Chance of being on the play (the coin flip still consumes one RNG value)
+    pub play_probability: f64,
+    /// Cards drawn for the opening hand, before mulligans (an extra-card effect makes it 8)
+    pub opening_hand_size: usize,
+    /// We lose if we haven't won by the end of this turn
+    pub opponent_clock: Option<u32>,
+    /// The opponent attacks each turn, harder as the game goes on
+    pub opponent_curve: Option<OpponentCurve>,
+    /// The opponent counters this many of our Superior Spider-Man casts
+    pub opponent_counters: u32,
+    /// Capture the exile zone into `GameResult::exiled_cards` (off by default to avoid allocating)
+    pub record_exiled: bool,
+    /// Record structured events into `GameResult::events`
+    pub record_events: bool,
+    /// Replacement effects in play from the start of the game (sideboard hate)
+    pub replacement_effects: ReplacementEffects,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            combat_policy: CombatPolicy::default(),
+            combo_policy: ComboPolicy::default(),
+            surveil_policy: DecisionEngine::surveil_to_graveyard,
+            land_policy: LandPolicy::default(),
+            graveyard_hate: None,
+            play_probability: DEFAULT_PLAY_PROBABILITY,
+            opening_hand_size: DEFAULT_OPENING_HAND_SIZE,
+            opponent_clock: None,
+            opponent_curve: None,
+            opponent_counters: 0,
+            record_exiled: false,
+            record_events: false,
+            replacement_effects: ReplacementEffects::default(),
         }
     }
-    
-    GameResult {
-        win_turn: if check_win_condition(&state) { Some(state.turn) } else { None },
-        turn_with_ubg,
-    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::card::types::{BaseCard, CreatureCard, LandCard};
+/// Run a complete game simulation
+pub fn run_game(
+    deck: &[Card],
+    seed: u64,
+    db: &CardDatabase,
+    verbose: bool,
+) -> GameResult {
+    run_game_with_config(deck, seed, db, verbose, &GameConfig::default())
+}
 
-    #[test]
-    fn test_check_win_condition_false() {
-        let state = GameState::new();
-        assert!(!check_win_condition(&state));
-    }
+/// Run a complete game simulation with non-default settings
+pub fn run_game_with_config(
+    deck: &[Card],
+    seed: u64,
+    db: &CardDatabase,
+    verbose: bool,
+    config: &GameConfig,
+) -> GameResult {
+    GAME_SCRATCH.with(|scratch| {
+        let (state, library_scratch) = &mut *scratch.borrow_mut();
+        state.reset();
+        state.combat_policy = config.combat_policy;
+        state.combo_policy = config.combo_policy;
+        state.surveil_policy = config.surveil_policy;
+        state.land_policy = config.land_policy;
+        state.graveyard_hate = config.graveyard_hate;
+        state.play_probability = config.play_probability;
+        state.opening_hand_size = config.opening_hand_size;
+        state.opponent_clock = config.opponent_clock;
+        state.opponent_curve = config.opponent_curve;
+        state.opponent_counters = config.opponent_counters;
+        state.record_events = config.record_events;
+        state.replacement_effects.clone_from(&config.replacement_effects);
+        let mut result = run_game_with_state(state, library_scratch, deck, seed, db, verbose);
+        if config.record_exiled {
+            result.exiled_cards = state.exile.cards().iter().map(|c| c.name().to_string()).collect();
+        }
+        result.events = std::mem::take(&mut state.events);
+        result
+    })
+}
 
-    #[test]
-    fn test_check_win_condition_true() {
-        let mut state = GameState::new();
+/// Run a complete game simulation using caller-provided state and scratch buffer
+pub fn run_game_with_state(
+    state: &mut GameState,
+    scratch: &mut Vec<Card>,
+    deck: &[Card],
+    seed: u64,
+    db: &CardDatabase,
+    verbose: bool,
+) -> GameResult {
+    let _timer = profile::game();
+
+    // Move the state into the runner and back so its buffers and settings are reused
+    let owned = std::mem::take(state);
+    let mut runner = GameRunner::with_state(owned, scratch, deck, seed, db, verbose);
+    while !runner.is_over() {
+        runner.step_turn();
+    }
+
+    if verbose {
+        match win_line(runner.state()) {
+            Some(line) => vlog!(1, "\n=== Won on turn {} ({}) ===", runner.state().turn, line),
+            None => vlog!(1, "\n=== No win by turn {} ===", runner.state().turn),
+        }
+    }
+
+    let result = runner.result();
+    *state = runner.into_state();
+    result
+}
+
+/// What happened during one `GameRunner::step_turn`
+#[derive(Debug, Clone)]
+pub struct TurnOutcome {
+    pub turn: u32,
+    /// Opponent life lost this turn: combat, Terror triggers, death pings and other direct damage
+    pub damage: u32,
+    pub won: bool,
+    /// Out of turns (or the opponent's clock ran out) without winning
+    pub lost: bool,
+    /// Events recorded this turn (empty unless `GameState::record_events` is on)
+    pub events: Vec<GameEvent>,
+}
+
+/// Plays a game one turn at a time, for callers that want control between turns
+pub struct GameRunner<'a> {
+    state: GameState,
+    rng: GameRng,
+    db: &'a CardDatabase,
+    verbose: bool,
+    max_turns: u32,
+    turn_with_ubg: Option<u32>,
+    utilization_sum: f64,
+    utilization_turns: u32,
+    flooded: bool,
+    screwed: bool,
+}
+
+impl<'a> GameRunner<'a> {
+    /// Set up a game with default settings, ready for turn 1
+    pub fn new(deck: &[Card], seed: u64, db: &'a CardDatabase) -> Self {
+        Self::with_state(GameState::new(), &mut Vec::with_capacity(deck.len()), deck, seed, db, false)
+    }
+
+    /// Set up a game on `state`, keeping its settings (combat policy, clock, ...).
+    /// `state` should be freshly reset; `scratch` is a reusable library buffer.
+    pub fn with_state(
+        mut state: GameState,
+        scratch: &mut Vec<Card>,
+        deck: &[Card],
+        seed: u64,
+        db: &'a CardDatabase,
+        verbose: bool,
+    ) -> Self {
+        let mut rng = GameRng::new(Some(seed));
+        setup_game(&mut state, deck, &mut rng, scratch);
+
+        // Print game start info if verbose
+        if verbose {
+            vlog!(1, "=== Game Start (seed: {}) ===", seed);
+            vlog!(1, "{}", if state.on_the_play { "On the play" } else { "On the draw" });
+            vlog!(1, "Opening hand ({} cards):", state.hand.size());
+            for card in state.hand.cards() {
+                vlog!(1, "  - {}", card.name());
+            }
+        }
+
+        Self::start(state, rng, db, verbose)
+    }
+
+    /// Pick up a game already in progress: only the library is shuffled (by `seed`),
+    /// then play continues from the turn after `state.turn`
+    pub fn resume(mut state: GameState, seed: u64, db: &'a CardDatabase) -> Self {
+        let mut rng = GameRng::new(Some(seed));
+        state.library.shuffle(&mut rng);
+        Self::start(state, rng, db, false)
+    }
+
+    fn start(state: GameState, rng: GameRng, db: &'a CardDatabase, verbose: bool) -> Self {
+        let max_turns = state.opponent_clock.map_or(MAX_TURNS, |clock| clock.min(MAX_TURNS));
+        GameRunner {
+            state,
+            rng,
+            db,
+            verbose,
+            max_turns,
+            turn_with_ubg: None,
+            utilization_sum: 0.0,
+            utilization_turns: 0,
+            flooded: false,
+            screwed: false,
+        }
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Give back the game state (e.g. to reuse its buffers)
+    pub fn into_state(self) -> GameState {
+        self.state
+    }
+
+    /// The game has been won or lost, or the last turn has been played
+    pub fn is_over(&self) -> bool {
+        check_win_condition(&self.state) || self.state.turn >= self.max_turns || self.state.has_lost()
+    }
+
+    /// Why the game was lost, once it's over without a win
+    pub fn loss_reason(&self) -> Option<LossReason> {
+        let state = &self.state;
+        if check_win_condition(state) {
+            None
+        } else if state.decked_out {
+            Some(LossReason::DeckedOut)
+        } else if state.life <= 0 {
+            Some(LossReason::Killed)
+        } else if state.turn >= self.max_turns {
+            Some(LossReason::OutOfTurns)
+        } else {
+            None
+        }
+    }
+
+    /// Play the next turn
+    pub fn step_turn(&mut self) -> TurnOutcome {
+        let state = &mut self.state;
+        let events_before = state.events.len();
+        let life_before = state.opponent_life;
+        execute_turn(state, self.db, self.verbose, &mut self.rng);
+        let damage = (life_before - state.opponent_life).max(0) as u32;
+
+        // Mana consistency: the flood check settles on turn 4's value (or the last turn, if sooner)
+        if state.turn < MANA_CHECK_TURN && state.lands_played_this_turn == 0 {
+            self.screwed = true;
+        }
+        if state.turn <= MANA_CHECK_TURN {
+            self.flooded = is_flooded(state);
+        }
+
+        if let Some(utilization) = state.mana_utilization_this_turn() {
+            self.utilization_sum += utilization;
+            self.utilization_turns += 1;
+        }
+
+        // Track when all colors become available
+        if self.turn_with_ubg.is_none() {
+            let colors = get_available_colors(state, false);
+            if colors.has_blue() && colors.has_black() && colors.has_green() {
+                self.turn_with_ubg = Some(state.turn);
+            }
+        }
+
+        let won = check_win_condition(&self.state);
+        TurnOutcome {
+            turn: self.state.turn,
+            damage,
+            won,
+            lost: !won && self.is_over(),
+            events: self.state.events[events_before..].to_vec(),
+        }
+    }
+
+    /// Result of the game so far
+    pub fn result(&self) -> GameResult {
+        let state = &self.state;
+        let damage_win = win_line(state) == Some(WinLine::Damage);
+        GameResult {
+            win_turn: if check_win_condition(state) { Some(state.turn) } else { None },
+            turn_with_ubg: self.turn_with_ubg,
+            win_line: win_line(state),
+            mana_utilization: if self.utilization_turns > 0 {
+                Some(self.utilization_sum / self.utilization_turns as f64)
+            } else {
+                None
+            },
+            exiled_cards: Vec::new(),
+            held_combo_turn: state.held_combo_turn,
+            first_spell_turn: state.first_spell_turn,
+            events: Vec::new(),
+            flooded: self.flooded,
+            screwed: self.screwed,
+            on_the_play: state.on_the_play,
+            reanimated_count: if check_win_condition(state) { state.reanimated_count } else { None },
+            winning_turn_damage: damage_win.then(|| state.damage_dealt_this_turn()),
+            overkill: damage_win.then(|| (-state.opponent_life) as u32),
+            loss_reason: self.loss_reason(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::types::{BaseCard, CreatureCard, LandCard};
+
+    #[test]
+    fn test_check_win_condition_false() {
+        let state = GameState::new();
+        assert!(!check_win_condition(&state));
+    }
+
+    #[test]
+    fn test_check_win_condition_true() {
+        let mut state = GameState::new();
         state.opponent_life = 0;
         assert!(check_win_condition(&state));
     }
@@ -1038,34 +1457,21 @@ mod tests {
     #[test]
     fn test_get_available_colors_empty() {
         let state = GameState::new();
-        let colors = get_available_colors(&state);
+        let colors = get_available_colors(&state, false);
         assert!(colors.is_empty());
     }
 
     #[test]
     fn test_get_available_colors_with_lands() {
-        use crate::card::{LandSubtype, ManaColor};
-
         let mut state = GameState::new();
 
         // Add a forest (green land)
-        let forest = Card::Land(LandCard {
-            base: BaseCard {
-                name: "Forest".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 0,
-            },
-            subtype: LandSubtype::Basic,
-            colors: vec![ManaColor::Green],
-            enters_tapped: false,
-            has_surveil: false,
-            surveil_amount: 0,
-        });
+        let forest = Card::from(LandCard::basic("Forest", ManaColor::Green));
 
         let permanent = crate::game::zones::Permanent::new(forest, 1);
         state.battlefield.add_permanent(permanent);
 
-        let colors = get_available_colors(&state);
+        let colors = get_available_colors(&state, false);
         assert!(colors.has_green());
     }
 
@@ -1083,20 +1489,7 @@ mod tests {
         state.turn = 2; // Avoid summoning sickness
         
         // Add a creature to battlefield
-        let creature = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Test Creature".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 1,
-            },
-            power: 3,
-            toughness: 2,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let creature = Card::from(CreatureCard::test("Test Creature", 3, 2, &[]));
         
         let permanent = crate::game::zones::Permanent::new(creature, 1);
         state.battlefield.add_permanent(permanent);
@@ -1106,26 +1499,51 @@ mod tests {
         assert_eq!(state.opponent_life, 17);
     }
 
+    #[test]
+    fn test_drawing_from_empty_library_is_a_deck_out_loss() {
+        let db = CardDatabase::fixture();
+        let mut state = GameState::new();
+        state.library.add_card(db.get_card("Forest").unwrap());
+        state.hand.add_card(db.get_card("Cache Grab").unwrap());
+
+        let mut runner = GameRunner::resume(state, 1, &db);
+        let first = runner.step_turn();
+        assert!(!first.lost);
+        assert_eq!(runner.result().loss_reason, None);
+
+        let second = runner.step_turn();
+        assert!(second.lost);
+        assert!(runner.is_over());
+        assert_eq!(runner.state().turn, 2);
+        assert_eq!(runner.result().win_turn, None);
+        assert_eq!(runner.result().loss_reason, Some(LossReason::DeckedOut));
+    }
+
+    #[test]
+    fn test_thirty_damage_turn_against_twenty_life_overkills_by_ten() {
+        let mut state = GameState::new();
+        state.turn = 2;
+        state.reset_turn_state();
+        for _ in 0..6 {
+            let creature = Card::from(CreatureCard::test("Test Creature", 5, 5, &[]));
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(creature, 1));
+        }
+        assert_eq!(simulate_combat(&mut state, false), 30);
+
+        let db = CardDatabase::fixture();
+        let result = GameRunner::resume(state, 1, &db).result();
+        assert_eq!(result.win_turn, Some(2));
+        assert_eq!(result.winning_turn_damage, Some(30));
+        assert_eq!(result.overkill, Some(10));
+    }
+
     #[test]
     fn test_simulate_combat_summoning_sickness() {
         let mut state = GameState::new();
         state.turn = 1;
 
         // Add a creature that entered this turn (has summoning sickness)
-        let creature = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Test Creature".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 1,
-            },
-            power: 3,
-            toughness: 2,
-            is_legendary: false,
-            creature_types: vec![],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let creature = Card::from(CreatureCard::test("Test Creature", 3, 2, &[]));
 
         let permanent = crate::game::zones::Permanent::new(creature, 1);
         state.battlefield.add_permanent(permanent);
@@ -1154,26 +1572,16 @@ mod tests {
             abilities: vec!["gives_demons_haste".to_string()],
             impending_cost: None,
             impending_counters: None,
+            hardcast_ok: true,
+            ward: false,
+            hexproof: false,
         });
 
         let ardyn_perm = crate::game::zones::Permanent::new(ardyn, 0); // Entered turn 0
         state.battlefield.add_permanent(ardyn_perm);
 
         // Add a Demon that entered this turn (has summoning sickness normally)
-        let demon = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 8,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec!["Vampire".to_string(), "Demon".to_string()],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let demon = Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &["Vampire", "Demon"]));
 
         let demon_perm = crate::game::zones::Permanent::new(demon, 1); // Entered this turn
         state.battlefield.add_permanent(demon_perm);
@@ -1190,20 +1598,7 @@ mod tests {
         state.turn = 1;
 
         // Add a Demon that entered this turn (has summoning sickness)
-        let demon = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 8,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec!["Vampire".to_string(), "Demon".to_string()],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let demon = Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &["Vampire", "Demon"]));
 
         let demon_perm = crate::game::zones::Permanent::new(demon, 1); // Entered this turn
         state.battlefield.add_permanent(demon_perm);
@@ -1233,26 +1628,16 @@ mod tests {
             abilities: vec!["gives_demons_lifelink".to_string()],
             impending_cost: None,
             impending_counters: None,
+            hardcast_ok: true,
+            ward: false,
+            hexproof: false,
         });
 
         let ardyn_perm = crate::game::zones::Permanent::new(ardyn, 1);
         state.battlefield.add_permanent(ardyn_perm);
 
         // Add a Demon that entered last turn (no summoning sickness)
-        let demon = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 8,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec!["Vampire".to_string(), "Demon".to_string()],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let demon = Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &["Vampire", "Demon"]));
 
         let demon_perm = crate::game::zones::Permanent::new(demon, 1);
         state.battlefield.add_permanent(demon_perm);
@@ -1264,6 +1649,25 @@ mod tests {
         assert_eq!(damage, 10);
         // Demon dealt 6 damage with lifelink
         assert_eq!(state.life, initial_life + 6);
+        assert_eq!(state.total_life_gained, 6);
+    }
+
+    #[test]
+    fn test_gain_land_enters_tapped_and_gains_life() {
+        let mut gain_land = LandCard::basic("Jungle Hollow", ManaColor::Black);
+        gain_land.colors.push(ManaColor::Green);
+        gain_land.etb_gain_life = 1;
+
+        let mut state = GameState::new();
+        state.turn = 1;
+        state.life = 18;
+        cards::play_land(&mut state, &Card::Land(gain_land), false).unwrap();
+
+        assert_eq!(state.life, 19);
+        assert_eq!(state.total_life_gained, 1);
+        let permanent = &state.battlefield.permanents()[0];
+        assert!(permanent.tapped);
+        assert_eq!(state.mana_available_this_turn, 0);
     }
 
     #[test]
@@ -1285,26 +1689,16 @@ mod tests {
             abilities: vec!["starscourge".to_string()],
             impending_cost: None,
             impending_counters: None,
+            hardcast_ok: true,
+            ward: false,
+            hexproof: false,
         });
 
         let ardyn_perm = crate::game::zones::Permanent::new(ardyn, 1);
         state.battlefield.add_permanent(ardyn_perm);
 
         // Add Bringer to graveyard
-        let bringer = Card::Creature(CreatureCard {
-            base: BaseCard {
-                name: "Bringer of the Last Gift".to_string(),
-                mana_cost: Default::default(),
-                mana_value: 8,
-            },
-            power: 6,
-            toughness: 6,
-            is_legendary: false,
-            creature_types: vec!["Vampire".to_string(), "Demon".to_string()],
-            abilities: vec![],
-            impending_cost: None,
-            impending_counters: None,
-        });
+        let bringer = Card::from(CreatureCard::test("Bringer of the Last Gift", 6, 6, &["Vampire", "Demon"]));
 
         state.graveyard.add_card(bringer);
 
@@ -1316,7 +1710,7 @@ mod tests {
 
         // A 5/5 Demon token should be created
         let token_count = state.battlefield.permanents().iter()
-            .filter(|p| p.is_copy_of.as_deref() == Some("Bringer of the Last Gift"))
+            .filter(|p| p.is_copy_of == Some("Bringer of the Last Gift"))
             .count();
         assert_eq!(token_count, 1);
 
@@ -1324,5 +1718,991 @@ mod tests {
         // Ardyn (4) + Starscourge token (5) = 9 damage
         assert_eq!(damage, 9);
     }
-}
 
+    #[test]
+    fn test_flashback_spell_cast_once_then_exiled() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 2;
+        state.phase = Phase::Main1;
+
+        let flashback = Card::Sorcery(SpellCard {
+            base: BaseCard {
+                name: "Test Flashback Mill".to_string(),
+                mana_cost: ManaCost { green: 1, generic: 1, ..Default::default() },
+                mana_value: 2,
+            },
+            abilities: vec![],
+            castable_from_graveyard: true,
+            delve: false,
+        });
+        state.graveyard.add_card(flashback);
+        for _ in 0..4 {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Forest").unwrap(), 1));
+        }
+
+        main_phase(&mut state, &db, false, &mut rng);
+
+        assert!(state.graveyard.cards().iter().all(|c| c.name() != "Test Flashback Mill"));
+        assert_eq!(state.exile.cards().len(), 1);
+        assert_eq!(state.exile.cards()[0].name(), "Test Flashback Mill");
+        let tapped = state.battlefield.permanents().iter().filter(|p| p.tapped).count();
+        assert_eq!(tapped, 2, "Flashback spell should be cast exactly once");
+
+        // Untapped again next turn, it is no longer available to cast
+        state.untap_all();
+        main_phase(&mut state, &db, false, &mut rng);
+        assert!(state.battlefield.permanents().iter().all(|p| !p.tapped));
+        assert_eq!(state.exile.cards().len(), 1);
+    }
+
+    #[test]
+    fn test_flashback_spell_under_rest_in_peace_leaves_graveyard_alone() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 2;
+        state.phase = Phase::Main1;
+        state.replacement_effects.add(crate::game::state::ReplacementEffect::GraveyardExiles);
+
+        // Already in the graveyard before Rest in Peace, alongside the flashback spell
+        state.graveyard.add_card(db.get_card("Town Greeter").unwrap());
+        state.graveyard.add_card(Card::Sorcery(SpellCard {
+            base: BaseCard {
+                name: "Test Flashback Mill".to_string(),
+                mana_cost: ManaCost { green: 1, generic: 1, ..Default::default() },
+                mana_value: 2,
+            },
+            abilities: vec![],
+            castable_from_graveyard: true,
+            delve: false,
+        }));
+        for _ in 0..2 {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Forest").unwrap(), 1));
+        }
+
+        main_phase(&mut state, &db, false, &mut rng);
+
+        let exiled: Vec<&str> = state.exile.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(exiled, vec!["Test Flashback Mill"]);
+        let graveyard: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(graveyard, vec!["Town Greeter"]);
+    }
+
+    #[test]
+    fn test_delve_flashback_spell_cannot_exile_itself() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 2;
+        state.phase = Phase::Main1;
+        state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Island").unwrap(), 1));
+
+        let delve = Card::Sorcery(SpellCard {
+            base: BaseCard {
+                name: "Test Delve Flashback".to_string(),
+                mana_cost: ManaCost { blue: 1, generic: 4, ..Default::default() },
+                mana_value: 5,
+            },
+            abilities: vec![],
+            castable_from_graveyard: true,
+            delve: true,
+        });
+        state.graveyard.add_card(delve);
+        for _ in 0..3 {
+            state.graveyard.add_card(db.get_card("Forest").unwrap());
+        }
+
+        // Three other cards can't pay the four generic, and the spell can't exile itself
+        main_phase(&mut state, &db, false, &mut rng);
+        assert_eq!(state.graveyard.size(), 4);
+        assert_eq!(state.graveyard.cards()[0].name(), "Test Delve Flashback");
+        assert!(state.exile.cards().is_empty());
+
+        // A fourth card pays for it: the Forests and Swamp are exiled, then the spell itself
+        state.graveyard.add_card(db.get_card("Swamp").unwrap());
+        main_phase(&mut state, &db, false, &mut rng);
+        assert!(state.graveyard.cards().is_empty());
+        assert_eq!(state.exile.size(), 5);
+        assert_eq!(state.exile.cards().last().unwrap().name(), "Test Delve Flashback");
+        assert!(state.battlefield.permanents()[0].tapped);
+    }
+
+    #[test]
+    fn test_waiting_log_shows_deficit_to_lethal() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 3;
+        state.phase = Phase::Main1;
+        state.hand.add_card(db.get_card("Superior Spider-Man").unwrap());
+        state.graveyard.add_card(db.get_card("Bringer of the Last Gift").unwrap());
+        state.graveyard.add_card(db.get_card("Bringer of the Last Gift").unwrap());
+        state.graveyard.add_card(db.get_card("Terror of the Peaks").unwrap());
+
+        // Spider-Man exiles the Bringer it copies; the other Bringer returning triggers
+        // the returning Terror once: 6 of the 20 needed
+        let ((), log) = crate::log::capture(|| main_phase(&mut state, &db, true, &mut rng));
+        let waiting = log.lines().find(|line| line.contains("[Waiting]")).expect("no [Waiting] line");
+        assert!(waiting.contains("6 Terror + 0 combat"), "{}", waiting);
+        assert!(waiting.contains("short by 14"), "{}", waiting);
+        assert!(waiting.contains("power x 1 Terror;"), "{}", waiting);
+    }
+
+    #[test]
+    fn test_castable_list_matches_cast_order() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 5;
+        state.phase = Phase::Main1;
+        state.lands_played_this_turn = 1;
+        for _ in 0..9 {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Forest").unwrap(), 1));
+        }
+        for name in ["Ouroboroid", "Frenzied Baloth", "Town Greeter", "Pawpatch Recruit"] {
+            state.hand.add_card(db.get_card(name).unwrap());
+        }
+
+        let ((), log) = crate::log::capture(|| main_phase(&mut state, &db, true, &mut rng));
+        let castable = log.lines().find(|line| line.contains("[Castable]")).expect("no [Castable] line");
+        assert_eq!(
+            castable,
+            "  [Castable] Town Greeter (3: mill), Pawpatch Recruit (5: mana value), \
+             Frenzied Baloth (5: mana value), Ouroboroid (5: mana value)"
+        );
+        let cast: Vec<&str> = log.lines().filter_map(|line| line.strip_prefix("  [Cast] ")).collect();
+        let dumped: Vec<&str> = castable.trim_start_matches("  [Castable] ").split(", ")
+            .map(|entry| entry.split(" (").next().unwrap())
+            .collect();
+        assert_eq!(cast, dumped);
+    }
+
+    #[test]
+    fn test_first_spell_turn_records_a_turn_one_drop() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut deck = Vec::new();
+        for (name, count) in [("Forest", 30), ("Llanowar Elves", 30)] {
+            deck.extend(std::iter::repeat_n(db.get_card(name).unwrap(), count));
+        }
+        let result = run_game_with_config(&deck, 1, &db, false, &GameConfig::default());
+        assert_eq!(result.first_spell_turn, Some(1));
+
+        // Nothing but lands: no spell is ever cast
+        let lands = vec![db.get_card("Forest").unwrap(); 60];
+        let result = run_game_with_config(&lands, 1, &db, false, &GameConfig::default());
+        assert_eq!(result.first_spell_turn, None);
+    }
+
+    #[test]
+    fn test_counterspell_needs_a_second_spider_man() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let combo_turn = |spider_men: usize| {
+            let mut rng = GameRng::new(Some(1));
+            let mut state = GameState::new();
+            state.turn = 5;
+            state.phase = Phase::Main1;
+            state.opponent_life = 10;
+            state.opponent_counters = 1;
+            for _ in 0..8 {
+                state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Watery Grave").unwrap(), 1));
+            }
+            // Spider-Man exiles one Bringer; the other returns to trigger both Terrors
+            for name in ["Bringer of the Last Gift", "Bringer of the Last Gift", "Terror of the Peaks", "Terror of the Peaks"] {
+                state.graveyard.add_card(db.get_card(name).unwrap());
+            }
+            for _ in 0..spider_men {
+                state.hand.add_card(db.get_card("Superior Spider-Man").unwrap());
+            }
+            main_phase(&mut state, &db, false, &mut rng);
+            state
+        };
+
+        // The only Spider-Man is countered: no combo, and it ends up in the graveyard
+        let state = combo_turn(1);
+        assert_eq!(state.opponent_counters, 0);
+        assert_eq!(state.opponent_life, 10);
+        assert!(state.graveyard.cards().iter().any(|c| c.name() == "Superior Spider-Man"));
+        assert!(state.battlefield.permanents().iter().all(|p| !matches!(p.card, Card::Creature(_))));
+
+        // The second one resolves
+        let state = combo_turn(2);
+        assert!(check_win_condition(&state), "opponent at {}", state.opponent_life);
+    }
+
+    #[test]
+    fn test_hexproof_spider_man_is_not_countered() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let combo_turn = |protect: fn(&mut crate::card::CreatureCard), counters: u32| {
+            let mut rng = GameRng::new(Some(1));
+            let mut state = GameState::new();
+            state.turn = 5;
+            state.phase = Phase::Main1;
+            state.opponent_life = 10;
+            state.opponent_counters = counters;
+            for _ in 0..8 {
+                state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Watery Grave").unwrap(), 1));
+            }
+            for name in ["Bringer of the Last Gift", "Terror of the Peaks", "Terror of the Peaks", "Craterhoof Behemoth"] {
+                state.graveyard.add_card(db.get_card(name).unwrap());
+            }
+            let mut spider_man = db.get_card("Superior Spider-Man").unwrap();
+            if let Card::Creature(c) = &mut spider_man {
+                protect(c);
+            }
+            state.hand.add_card(spider_man);
+            main_phase(&mut state, &db, false, &mut rng);
+            state
+        };
+
+        // Hexproof: the counterspell is never spent and the combo wins
+        let state = combo_turn(|c| c.hexproof = true, 1);
+        assert_eq!(state.opponent_counters, 1);
+        assert!(check_win_condition(&state), "opponent at {}", state.opponent_life);
+
+        // Ward: one counterspell can't pay for it, two can
+        let state = combo_turn(|c| c.ward = true, 1);
+        assert!(check_win_condition(&state), "opponent at {}", state.opponent_life);
+        let state = combo_turn(|c| c.ward = true, 2);
+        assert_eq!(state.opponent_counters, 0);
+        assert_eq!(state.opponent_life, 10);
+    }
+
+    #[test]
+    fn test_sorcery_speed_rejected_during_combat() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 3;
+        state.hand.add_card(db.get_card("Swamp").unwrap());
+        state.hand.add_card(db.get_card("Cache Grab").unwrap());
+        for phase in [Phase::Upkeep, Phase::Draw, Phase::Main1, Phase::Combat] {
+            state.advance_phase(phase).unwrap();
+        }
+
+        assert!(!state.can_cast_sorcery_speed());
+        main_phase(&mut state, &db, false, &mut rng);
+        assert_eq!(state.hand.size(), 2);
+        assert!(state.battlefield.permanents().is_empty());
+
+        // Phases only move forward, one at a time
+        assert!(state.advance_phase(Phase::Main1).is_err());
+        assert!(state.advance_phase(Phase::End).is_err());
+
+        state.advance_phase(Phase::Main2).unwrap();
+        main_phase(&mut state, &db, false, &mut rng);
+        assert_eq!(state.lands_played_this_turn, 1);
+    }
+
+    #[test]
+    fn test_extra_land_drop_plays_two_lands() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let exploration = Card::Enchantment(SpellCard {
+            base: BaseCard {
+                name: "Test Exploration".to_string(),
+                mana_cost: ManaCost { green: 1, ..Default::default() },
+                mana_value: 1,
+            },
+            abilities: vec!["extra_land_drop".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        });
+        let mut lands_after_main_phase = |with_exploration: bool| {
+            let mut state = GameState::new();
+            if with_exploration {
+                state.battlefield.add_permanent(crate::game::zones::Permanent::new(exploration.clone(), 1));
+            }
+            start_turn(&mut state);
+            state.phase = Phase::Main1;
+            for name in ["Forest", "Swamp", "Island"] {
+                state.hand.add_card(db.get_card(name).unwrap());
+            }
+            main_phase(&mut state, &db, false, &mut rng);
+            assert!(!state.can_play_land());
+            state.lands_played_this_turn
+        };
+
+        assert_eq!(lands_after_main_phase(false), 1);
+        assert_eq!(lands_after_main_phase(true), 2);
+    }
+
+    #[test]
+    fn test_reanimation_only_creature_not_hardcast() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 8;
+        state.phase = Phase::Main1;
+
+        state.hand.add_card(db.get_card("Ardyn, the Usurper").unwrap());
+        for _ in 0..8 {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Swamp").unwrap(), 1));
+        }
+        assert!(mana::can_cast_spell(&state.hand.cards()[0], &state), "8 Swamps pay for Ardyn");
+
+        main_phase(&mut state, &db, false, &mut rng);
+
+        assert_eq!(state.hand.cards()[0].name(), "Ardyn, the Usurper");
+        assert!(state.battlefield.permanents().iter().all(|p| !p.tapped));
+    }
+
+    #[test]
+    fn test_hardcast_impending_demon_when_attack_is_lethal() {
+        use crate::card::types::ManaCost;
+        use crate::game::zones::CounterType;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut demon = CreatureCard::test("Test Impending Demon", 5, 5, &["Demon"]);
+        demon.base.mana_cost = ManaCost { black: 2, generic: 3, ..Default::default() };
+        demon.impending_cost = Some(ManaCost { black: 1, generic: 1, ..Default::default() });
+        demon.impending_counters = Some(5);
+
+        // Ardyn (4 power) plus a hasty 5/5 Demon is exactly lethal at 9 life, not at 20
+        for (opponent_life, expect_hardcast) in [(9, true), (20, false)] {
+            let mut rng = GameRng::new(Some(1));
+            let mut state = GameState::new();
+            state.turn = 5;
+            state.phase = Phase::Main1;
+            state.opponent_life = opponent_life;
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Ardyn, the Usurper").unwrap(), 3));
+            for _ in 0..5 {
+                state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Swamp").unwrap(), 1));
+            }
+            state.hand.add_card(Card::Creature(demon.clone()));
+
+            main_phase(&mut state, &db, false, &mut rng);
+            let cast = state.battlefield.permanents().iter()
+                .find(|p| p.card.name() == "Test Impending Demon")
+                .expect("the demon should be cast either way");
+            assert_eq!(cast.get_counter(CounterType::Time) == 0, expect_hardcast);
+
+            simulate_combat(&mut state, false);
+            assert_eq!(state.opponent_life <= 0, expect_hardcast);
+        }
+    }
+
+    #[test]
+    fn test_starscourge_token_attacks_turn_it_is_created() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+
+        // Token created before combat (e.g. by an upkeep trigger) or at beginning of combat
+        for created_before_combat in [true, false] {
+            let mut state = GameState::new();
+            state.turn = 3;
+            let ardyn = db.get_card("Ardyn, the Usurper").unwrap();
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(ardyn, 2));
+            state.graveyard.add_card(db.get_card("Town Greeter").unwrap());
+
+            if created_before_combat {
+                upkeep_phase(&mut state, false);
+                resolve_starscourge(&mut state, false);
+            }
+            let damage = simulate_combat(&mut state, false);
+
+            let token = state.battlefield.permanents().iter()
+                .find(|p| p.is_copy_of == Some("Town Greeter"))
+                .expect("Starscourge should create a token");
+            assert_eq!(token.turn_entered, 3);
+            assert!(token.tapped, "Token should have attacked the turn it was created");
+            // Ardyn (4) + 5/5 Demon token
+            assert_eq!(damage, 9);
+        }
+    }
+
+    #[test]
+    fn test_mill_opponent_to_zero_wins_by_mill() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+
+        let mill_spell = Card::Sorcery(SpellCard {
+            base: BaseCard {
+                name: "Test Opponent Mill".to_string(),
+                mana_cost: ManaCost { blue: 1, ..Default::default() },
+                mana_value: 1,
+            },
+            abilities: vec!["mill_opponent_30".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        });
+
+        cards::cast_spell(&mut state, &mill_spell, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+        assert_eq!(state.opponent_library_size, 23);
+        assert!(!check_win_condition(&state));
+
+        cards::cast_spell(&mut state, &mill_spell, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+        assert_eq!(state.opponent_library_size, 0);
+        assert!(check_win_condition(&state));
+        assert_eq!(win_line(&state), Some(WinLine::MillOpponent));
+
+        // Damage takes precedence when both happen
+        state.opponent_life = 0;
+        assert_eq!(win_line(&state), Some(WinLine::Damage));
+    }
+
+    #[test]
+    fn test_value_combat_policy_holds_back_attackers_that_die() {
+        use crate::game::state::Blocker;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+
+        for (policy, expected_damage) in [(CombatPolicy::AlphaStrike, 4), (CombatPolicy::Value, 4)] {
+            let mut state = GameState::new();
+            state.turn = 3;
+            state.combat_policy = policy;
+            state.opponent_blockers.push(Blocker { power: 2, toughness: 2 });
+            // 1/1 and 4/4, both able to attack
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Town Greeter").unwrap(), 1));
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Superior Spider-Man").unwrap(), 1));
+
+            assert_eq!(simulate_combat(&mut state, false), expected_damage, "{:?}", policy);
+            // The blocker eats the Greeter if it attacks
+            let greeter_died = state.graveyard.cards().iter().any(|c| c.name() == "Town Greeter");
+            assert_eq!(greeter_died, policy == CombatPolicy::AlphaStrike);
+            assert!(state.battlefield.permanents().iter().all(|p| p.card.name() != "Superior Spider-Man" || p.tapped));
+        }
+    }
+
+    #[test]
+    fn test_blocker_eats_the_smallest_attacker() {
+        use crate::card::types::CreatureCard;
+        use crate::game::state::Blocker;
+
+        let mut state = GameState::new();
+        state.turn = 3;
+        state.opponent_blockers.push(Blocker { power: 2, toughness: 2 });
+        state.battlefield.add_permanent(crate::game::zones::Permanent::new(Card::from(CreatureCard::test("Squire", 1, 1, &[])), 1));
+        state.battlefield.add_permanent(crate::game::zones::Permanent::new(Card::from(CreatureCard::test("Giant", 5, 5, &[])), 1));
+
+        assert_eq!(simulate_combat(&mut state, false), 5);
+        assert_eq!(state.opponent_life, 15);
+        assert_eq!(state.graveyard.cards().iter().map(|c| c.name()).collect::<Vec<_>>(), vec!["Squire"]);
+        // The 1/1 doesn't kill the 2/2, which stays to block again
+        assert_eq!(state.opponent_blockers.len(), 1);
+    }
+
+    #[test]
+    fn test_mana_utilization_two_drop_with_four_lands() {
+        use crate::game::turns::start_turn;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+        for _ in 0..4 {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Forest").unwrap(), 1));
+        }
+        start_turn(&mut state);
+        assert_eq!(state.mana_available_this_turn, 4);
+
+        // Pay for a {1}{G} two-drop
+        let cost = crate::card::ManaCost { green: 1, generic: 1, ..Default::default() };
+        assert!(mana::tap_lands_for_cost(&cost, &mut state, None));
+        assert_eq!(state.mana_spent_this_turn, 2);
+        assert_eq!(state.mana_utilization_this_turn(), Some(0.5));
+    }
+
+    #[test]
+    fn test_three_turn_game_records_three_mana_snapshots() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let config = GameConfig { opponent_clock: Some(3), record_events: true, ..GameConfig::default() };
+        let result = run_game_with_config(&deck, 42, &db, false, &config);
+
+        let snapshots: Vec<(u32, u32)> = result.events.iter()
+            .filter_map(|event| match event {
+                GameEvent::ManaSnapshot { turn, untapped_sources, .. } => Some((*turn, *untapped_sources)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(snapshots.iter().map(|s| s.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(snapshots.windows(2).all(|w| w[0].1 <= w[1].1), "sources went down: {:?}", snapshots);
+
+        // Recording is off by default
+        assert!(run_game(&deck, 42, &db, false).events.is_empty());
+    }
+
+    #[test]
+    fn test_single_mulligan_is_logged_before_the_kept_hand() {
+        use crate::game::events::MulliganEvent;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let config = GameConfig { opponent_clock: Some(1), record_events: true, ..GameConfig::default() };
+        let mulligan_log = |seed: u64| -> Vec<MulliganEvent> {
+            run_game_with_config(&deck, seed, &db, false, &config).events.iter()
+                .filter_map(|event| match event {
+                    GameEvent::Mulligan(step) => Some(*step),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let mulliganed_once = |log: &[MulliganEvent]| {
+            log.iter().filter(|step| matches!(step, MulliganEvent::Mulliganed { .. })).count() == 1
+        };
+        let log = (0..500).map(mulligan_log).find(|log| mulliganed_once(log))
+            .expect("some game should mulligan once");
+
+        let mulliganed = log.iter().position(|step| matches!(step, MulliganEvent::Mulliganed { .. })).unwrap();
+        assert!(matches!(log[0], MulliganEvent::Drew { cards: 7, .. }), "{:?}", log);
+        let Some(MulliganEvent::Kept { cards: 6, lands }) = log.last().copied() else {
+            panic!("should keep 6 after one mulligan: {:?}", log);
+        };
+        assert!(log[mulliganed + 1..].contains(&MulliganEvent::Drew { cards: 6, lands }), "{:?}", log);
+
+        // A kept 7 is just the two Bo1 hands and the keep
+        let kept_seven = (0..50).map(mulligan_log).find(|log| log.len() == 3).expect("most games keep 7");
+        assert!(matches!(kept_seven[2], MulliganEvent::Kept { cards: 7, .. }));
+    }
+
+    #[test]
+    fn test_linear_opponent_curve_kills_before_a_slow_combo() {
+        let damage: Vec<i32> = (1..=5).map(|turn| OpponentCurve::Linear.damage(turn)).collect();
+        assert_eq!(damage, vec![1, 3, 5, 7, 9]);
+        assert_eq!(OpponentCurve::Flat.damage(5), 3);
+
+        // On the draw, seed 2 goldfishes a turn-6 win
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let config = GameConfig { play_probability: 0.0, ..GameConfig::default() };
+        assert_eq!(run_game_with_config(&deck, 2, &db, false, &config).win_turn, Some(6));
+
+        // 1 + 3 + 5 + 7 + 9 = 25 damage is dealt by their turn 5, which comes before ours
+        let mut state = GameState::new();
+        state.play_probability = 0.0;
+        state.opponent_curve = Some(OpponentCurve::Linear);
+        let mut runner = GameRunner::with_state(state, &mut Vec::new(), &deck, 2, &db, false);
+        let mut lives = Vec::new();
+        while !runner.is_over() {
+            runner.step_turn();
+            lives.push(runner.state().life);
+        }
+        assert_eq!(runner.state().turn, 5);
+        assert!(runner.state().life <= 0);
+        assert!(lives.windows(2).all(|w| w[1] < w[0]), "life didn't keep dropping: {:?}", lives);
+        assert_eq!(runner.result().win_turn, None);
+    }
+
+    #[test]
+    fn test_surveil_policy_decides_what_is_binned() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let graveyard_size_after_surveil = |policy: SurveilPolicy| {
+            let mut state = GameState::new();
+            for name in ["Forest", "Cache Grab", "Superior Spider-Man", "Swamp"] {
+                state.library.add_card(db.get_card(name).unwrap());
+            }
+            cards::resolve_surveil(&mut state, 2, policy, false);
+            state.graveyard.size()
+        };
+
+        assert_eq!(graveyard_size_after_surveil(|_, _| true), 2);
+        assert_eq!(graveyard_size_after_surveil(|_, _| false), 0);
+        // The default keeps lands, spells and Spider-Man on top
+        assert_eq!(graveyard_size_after_surveil(DecisionEngine::surveil_to_graveyard), 0);
+    }
+
+    #[test]
+    fn test_stepping_turns_matches_run_game() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        for seed in 0..20 {
+            let mut runner = GameRunner::new(&deck, seed, &db);
+            let mut last = None;
+            let mut damage = 0;
+            while !runner.is_over() {
+                let outcome = runner.step_turn();
+                assert_eq!(outcome.turn, runner.state().turn);
+                damage += outcome.damage as i32;
+                last = Some(outcome);
+            }
+            let last = last.expect("at least one turn is played");
+            assert_eq!(damage, 20 - runner.state().opponent_life, "seed {}", seed);
+
+            let expected = run_game(&deck, seed, &db, false);
+            assert_eq!(runner.result().win_turn, expected.win_turn, "seed {}", seed);
+            assert_eq!(last.won, expected.win_turn.is_some());
+            assert_eq!(last.lost, expected.win_turn.is_none());
+        }
+    }
+
+    #[test]
+    fn test_turn_one_draw_skipped_only_on_the_play() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        // Half lands, half mill spells: a seven-card hand is always kept
+        let mut deck = vec![db.get_card("Swamp").unwrap(); 30];
+        deck.extend(vec![db.get_card("Cache Grab").unwrap(); 30]);
+
+        for (play_probability, on_the_play, hand_size) in [(1.0, true, 7), (0.0, false, 8)] {
+            let mut rng = GameRng::new(Some(3));
+            let mut state = GameState::new();
+            state.play_probability = play_probability;
+            setup_game(&mut state, &deck, &mut rng, &mut Vec::new());
+            assert_eq!(state.hand.size(), 7);
+
+            begin_turn(&mut state, false, &mut rng);
+            assert_eq!(state.phase, Phase::Main1);
+            assert_eq!(state.on_the_play, on_the_play);
+            assert_eq!(state.hand.size(), hand_size);
+
+            let config = GameConfig { play_probability, ..GameConfig::default() };
+            assert_eq!(run_game_with_config(&deck, 3, &db, false, &config).on_the_play, on_the_play);
+        }
+    }
+
+    #[test]
+    fn test_forced_start_keeps_library_order() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let names = |cards: &[Card]| cards.iter().map(|c| c.name().to_string()).collect::<Vec<_>>();
+
+        let setups: Vec<_> = [DEFAULT_PLAY_PROBABILITY, 1.0, 0.0].into_iter().map(|play_probability| {
+            let mut rng = GameRng::new(Some(11));
+            let mut state = GameState::new();
+            state.play_probability = play_probability;
+            setup_game(&mut state, &deck, &mut rng, &mut Vec::new());
+            (state.on_the_play, names(state.hand.cards()), names(state.library.cards()), rng.random())
+        }).collect();
+
+        assert!(setups[1].0 && !setups[2].0);
+        for forced in &setups[1..] {
+            assert_eq!(forced.1, setups[0].1, "same opening hand");
+            assert_eq!(forced.2, setups[0].2, "same library order");
+            assert_eq!(forced.3, setups[0].3, "RNG left at the same point");
+        }
+    }
+
+    #[test]
+    fn test_all_land_deck_is_flooded_not_screwed() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = vec![db.get_card("Swamp").unwrap(); 60];
+        let result = run_game(&deck, 7, &db, false);
+        assert!(result.flooded);
+        assert!(!result.screwed);
+    }
+
+    #[test]
+    fn test_landless_deck_is_screwed_not_flooded() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = vec![db.get_card("Superior Spider-Man").unwrap(); 60];
+        let result = run_game(&deck, 7, &db, false);
+        assert!(result.screwed);
+        assert!(!result.flooded);
+    }
+
+    #[test]
+    fn test_reanimate_top_creature_returns_last_added() {
+        use crate::card::types::SpellCard;
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 3;
+        for name in ["Bringer of the Last Gift", "Pawpatch Recruit", "Cache Grab"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+        assert_eq!(state.graveyard.peek_last().map(|c| c.name()), Some("Cache Grab"));
+        assert_eq!(state.graveyard.top_creature().map(|c| c.base.name.as_str()), Some("Pawpatch Recruit"));
+
+        let spell = Card::Sorcery(SpellCard {
+            base: BaseCard::test("Test Top Reanimation"),
+            abilities: vec!["reanimate_top_creature".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        });
+        cards::cast_spell(&mut state, &spell, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+
+        // The most recent creature comes back, not the best one
+        let names: Vec<&str> = state.battlefield.permanents().iter().map(|p| p.card.name()).collect();
+        assert_eq!(names, vec!["Pawpatch Recruit"]);
+        assert!(state.graveyard.cards().iter().any(|c| c.name() == "Bringer of the Last Gift"));
+    }
+
+    #[test]
+    fn test_exile_graveyard_reanimate_token_spell() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 3;
+        state.graveyard.add_card(db.get_card("Town Greeter").unwrap());
+        state.graveyard.add_card(db.get_card("Bringer of the Last Gift").unwrap());
+        state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Terror of the Peaks").unwrap(), 1));
+
+        let spell = Card::Sorcery(SpellCard {
+            base: BaseCard {
+                name: "Test Graveyard Token".to_string(),
+                mana_cost: ManaCost { black: 1, ..Default::default() },
+                mana_value: 1,
+            },
+            abilities: vec!["exile_graveyard_reanimate_token_5_5".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        });
+
+        cards::cast_spell(&mut state, &spell, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+
+        // Bringer is the best target: exiled, and a 5/5 copy of it enters
+        assert_eq!(state.exile.cards().iter().map(|c| c.name()).collect::<Vec<_>>(), vec!["Bringer of the Last Gift"]);
+        let token = state.battlefield.permanents().last().unwrap();
+        assert_eq!(token.is_copy_of, Some("Bringer of the Last Gift"));
+        match &token.card {
+            Card::Creature(c) => assert_eq!((c.power, c.toughness), (5, 5)),
+            _ => panic!("token should be a creature"),
+        }
+        // Terror triggers for the token
+        assert_eq!(state.opponent_life, 15);
+        assert!(state.graveyard.cards().iter().any(|c| c.name() == "Town Greeter"));
+    }
+
+    #[test]
+    fn test_rest_in_peace_exiles_milled_creatures() {
+        use crate::game::state::{GraveyardHate, GraveyardHateMode};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        let mut exiled_creatures = 0;
+        for seed in 0..20 {
+            let mut state = GameState::new();
+            state.graveyard_hate = Some(GraveyardHate { turn: 1, mode: GraveyardHateMode::Continuous });
+            let mut scratch = Vec::new();
+            run_game_with_state(&mut state, &mut scratch, &deck, seed, &db, false);
+
+            // Nothing ever reaches the graveyard, so there is never anything to reanimate
+            assert_eq!(state.graveyard.size(), 0, "seed {}", seed);
+            assert!(
+                !state.battlefield.permanents().iter().any(|p| p.is_copy_of == Some("Bringer of the Last Gift")),
+                "seed {}: Spider-Man copied Bringer without a graveyard", seed
+            );
+            exiled_creatures += state.exile.cards().iter().filter(|c| matches!(c, Card::Creature(_))).count();
+        }
+        assert!(exiled_creatures > 0, "milled creatures should have been exiled");
+    }
+
+    #[test]
+    fn test_config_replacement_effects_apply_and_hate_ones_end_with_the_game() {
+        use crate::game::state::{GraveyardHate, GraveyardHateMode, ReplacementEffect};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        // Total turns taken to win, a loss counting one past the last turn
+        let turns = |config: &GameConfig| -> u32 {
+            (0..100)
+                .map(|seed| run_game_with_config(&deck, seed, &db, false, config).win_turn.unwrap_or(MAX_TURNS + 1))
+                .sum()
+        };
+
+        let mut hoser = GameConfig::default();
+        hoser.replacement_effects.add(ReplacementEffect::ReanimationExiles);
+        let goldfish = turns(&GameConfig::default());
+        assert!(turns(&hoser) > goldfish, "reanimation hate should slow the deck down");
+
+        // Rest in Peace from graveyard hate lasts for its game only
+        let mut state = GameState::new();
+        state.turn = 1;
+        state.graveyard_hate = Some(GraveyardHate { turn: 1, mode: GraveyardHateMode::Continuous });
+        state.apply_graveyard_hate(false);
+        assert!(state.replacement_effects.exiles_to_graveyard());
+        state.reset();
+        assert!(!state.replacement_effects.exiles_to_graveyard());
+        assert_eq!(turns(&GameConfig::default()), goldfish);
+    }
+
+    #[test]
+    fn test_combo_estimate_matches_actual_damage_on_known_board() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 3;
+        for _ in 0..4 {
+            state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Watery Grave").unwrap(), 1));
+        }
+        state.hand.add_card(db.get_card("Superior Spider-Man").unwrap());
+        for name in ["Bringer of the Last Gift", "Superior Spider-Man", "Terror of the Peaks", "Pawpatch Recruit"] {
+            state.graveyard.add_card(db.get_card(name).unwrap());
+        }
+
+        // Something to draw, so the turn isn't a deck-out loss
+        state.library.add_card(db.get_card("Island").unwrap());
+
+        // Reanimated Spider-Man copies Terror, so two Terrors trigger for Spider-Man (4) and Pawpatch (2)
+        let estimate = cards::calculate_combo_damage(&state);
+        assert_eq!(estimate, 12);
+        state.opponent_life = 12;
+
+        execute_turn(&mut state, &db, false, &mut rng);
+        assert_eq!(state.opponent_life, 0, "combo should deal exactly the estimated damage");
+        assert_eq!(combo_estimate_discrepancy(estimate, 12, state.opponent_life), None);
+    }
+
+    #[test]
+    fn test_combo_estimate_holds_over_seeded_games() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = crate::simulation::deck::parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let config = GameConfig { record_events: true, ..GameConfig::default() };
+
+        let mut combos = 0;
+        for seed in 0..1000 {
+            let result = run_game_with_config(&deck, seed, &db, false, &config);
+            for event in &result.events {
+                if let GameEvent::ComboResolved { turn, estimate, life_before, life_after } = *event {
+                    combos += 1;
+                    assert_eq!(combo_estimate_discrepancy(estimate, life_before, life_after), None,
+                        "seed {} turn {}", seed, turn);
+                }
+            }
+        }
+        assert!(combos > 500, "only {} combos resolved", combos);
+    }
+
+    #[test]
+    fn test_combo_estimate_discrepancy_reports_shortfall() {
+        assert_eq!(combo_estimate_discrepancy(15, 20, 5), None);
+        assert!(combo_estimate_discrepancy(16, 13, 3).unwrap().contains("survived"));
+        assert!(combo_estimate_discrepancy(10, 20, 15).unwrap().contains("only 5 dealt"));
+    }
+
+    #[test]
+    fn test_surveil_spell_bins_bringer_from_top() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.library.add_card(db.get_card("Bringer of the Last Gift").unwrap());
+        state.library.add_card(db.get_card("Forest").unwrap());
+        state.library.add_card(db.get_card("Superior Spider-Man").unwrap());
+
+        let surveil_spell = Card::Instant(SpellCard {
+            base: BaseCard {
+                name: "Test Surveil Cantrip".to_string(),
+                mana_cost: ManaCost { blue: 1, ..Default::default() },
+                mana_value: 1,
+            },
+            abilities: vec!["surveil_2".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        });
+
+        cards::cast_spell(&mut state, &surveil_spell, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+
+        // Bringer is binned, the Forest stays on top, the spell itself resolves to the graveyard
+        let graveyard: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(graveyard, vec!["Bringer of the Last Gift", "Test Surveil Cantrip"]);
+        assert_eq!(state.library.peek_top().map(|c| c.name()), Some("Forest"));
+        assert_eq!(state.library.size(), 2);
+    }
+
+    #[test]
+    fn test_end_of_turn_log_shows_tapped_lands_and_available_colors() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut state = GameState::new();
+        state.turn = 2;
+
+        let mut island = crate::game::zones::Permanent::new(db.get_card("Island").unwrap(), 1);
+        island.tapped = true;
+        state.battlefield.add_permanent(island);
+        state.battlefield.add_permanent(crate::game::zones::Permanent::new(db.get_card("Swamp").unwrap(), 2));
+
+        let log = format_end_of_turn(&state);
+        assert!(log.contains("Battlefield: Island (tapped), Swamp\n"), "{}", log);
+        assert!(log.contains("Available colors: B\n"), "{}", log);
+
+        state.untap_all();
+        assert!(format_end_of_turn(&state).contains("Available colors: UB\n"));
+    }
+
+    #[test]
+    fn test_starscourge_token_keeps_types_and_adds_demon() {
+        let mut state = GameState::new();
+        state.turn = 3;
+        state.graveyard.add_card(Card::from(CreatureCard::test("Test Villager", 3, 3, &["Human", "Peasant"])));
+
+        resolve_starscourge(&mut state, false);
+
+        let token = state.battlefield.permanents().iter()
+            .find(|p| p.is_copy_of == Some("Test Villager"))
+            .expect("token created");
+        let Card::Creature(c) = &token.card else { panic!("token is a creature") };
+        assert_eq!(c.creature_types, vec!["Human", "Peasant", "Demon"]);
+        assert_eq!(c.impending_counters, None);
+        assert!(token.counters.is_empty());
+    }
+
+    #[test]
+    fn test_legend_rule_after_reanimating_two_ardyns() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.turn = 5;
+        state.graveyard.add_card(db.get_card("Ardyn, the Usurper").unwrap());
+        state.graveyard.add_card(db.get_card("Ardyn, the Usurper").unwrap());
+        let mut spider_man = crate::game::zones::Permanent::new(db.get_card("Superior Spider-Man").unwrap(), 5);
+        spider_man.is_copy_of = Some("Bringer of the Last Gift");
+        state.battlefield.add_permanent(spider_man);
+
+        cards::resolve_bringer_etb(&mut state, &mut rng, false);
+
+        let ardyns = |cards: Vec<&str>| cards.iter().filter(|n| **n == "Ardyn, the Usurper").count();
+        assert_eq!(ardyns(state.battlefield.permanents().iter().map(|p| p.card.name()).collect()), 1);
+        assert_eq!(ardyns(state.graveyard.cards().iter().map(|c| c.name()).collect()), 1);
+        assert!(state.battlefield.permanents().iter().any(|p| p.card.name() == "Superior Spider-Man"));
+
+        // Casting a second copy of a legend keeps the new one
+        let kiora = db.get_card("Kiora, the Rising Tide").unwrap();
+        cards::cast_creature(&mut state, &kiora, false).unwrap();
+        state.turn = 6;
+        cards::cast_creature(&mut state, &kiora, false).unwrap();
+        let kioras: Vec<u32> = state.battlefield.permanents().iter()
+            .filter(|p| p.card.name() == "Kiora, the Rising Tide")
+            .map(|p| p.turn_entered)
+            .collect();
+        assert_eq!(kioras, vec![6]);
+    }
+
+    #[test]
+    fn test_regrowth_returns_spider_man_and_leaves_bringer() {
+        use crate::card::types::{ManaCost, SpellCard};
+
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let mut rng = GameRng::new(Some(1));
+        let mut state = GameState::new();
+        state.graveyard.add_card(db.get_card("Bringer of the Last Gift").unwrap());
+        state.graveyard.add_card(db.get_card("Superior Spider-Man").unwrap());
+
+        let regrowth = Card::Sorcery(SpellCard {
+            base: BaseCard {
+                name: "Test Regrowth".to_string(),
+                mana_cost: ManaCost { green: 1, ..Default::default() },
+                mana_value: 1,
+            },
+            abilities: vec!["return_best_from_graveyard_to_hand".to_string()],
+            castable_from_graveyard: false,
+            delve: false,
+        });
+
+        cards::cast_spell(&mut state, &regrowth, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+
+        let hand: Vec<&str> = state.hand.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(hand, vec!["Superior Spider-Man"]);
+        let graveyard: Vec<&str> = state.graveyard.cards().iter().map(|c| c.name()).collect();
+        assert_eq!(graveyard, vec!["Bringer of the Last Gift", "Test Regrowth"]);
+
+        // With only combo pieces left, nothing comes back
+        cards::cast_spell(&mut state, &regrowth, cards::SpellDestination::Graveyard, &db, false, &mut rng).unwrap();
+        assert_eq!(state.hand.size(), 1);
+    }
+}
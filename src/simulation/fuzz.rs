@@ -0,0 +1,139 @@
+//! Decklist fuzzing
+//!
+//! Swaps random database cards into a deck (keeping its size) and plays a few games
+//! with each mutation. Panics are caught and reported with the deck that caused them,
+//! so odd card combinations surface before an engine change ships.
+
+use crate::card::{Card, CardDatabase};
+use crate::rng::GameRng;
+use crate::simulation::engine::run_game;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Most cards replaced in a single mutation
+pub const MAX_SWAPS: usize = 8;
+
+/// A mutated deck whose games panicked
+#[derive(Debug, Clone)]
+pub struct FuzzFailure {
+    pub iteration: usize,
+    /// Seed of the game that panicked
+    pub seed: u64,
+    pub deck: Vec<Card>,
+    pub message: String,
+}
+
+/// Outcome of a fuzzing run
+#[derive(Debug, Clone, Default)]
+pub struct FuzzReport {
+    pub iterations: usize,
+    /// Games that ran to completion
+    pub games: usize,
+    pub wins: usize,
+    pub failures: Vec<FuzzFailure>,
+}
+
+impl FuzzReport {
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+}
+
+/// Replace `swaps` random cards of `base` with random cards from `pool`
+pub fn mutate_deck(base: &[Card], pool: &[Card], swaps: usize, rng: &mut GameRng) -> Vec<Card> {
+    let mut deck = base.to_vec();
+    if deck.is_empty() || pool.is_empty() {
+        return deck;
+    }
+    for _ in 0..swaps {
+        let slot = rng.random_range(deck.len());
+        deck[slot] = pool[rng.random_range(pool.len())].clone();
+    }
+    deck
+}
+
+/// Play `games_per_deck` games with each of `iterations` mutations of `base`
+pub fn fuzz_decks(base: &[Card], iterations: usize, games_per_deck: usize, base_seed: u64, db: &CardDatabase) -> FuzzReport {
+    use rayon::prelude::*;
+
+    // The database is a HashMap, so sort the pool to keep runs reproducible
+    let mut pool: Vec<Card> = db.cards().cloned().collect();
+    pool.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let outcomes: Vec<Result<usize, FuzzFailure>> = (0..iterations)
+        .into_par_iter()
+        .map(|iteration| {
+            let mut rng = GameRng::new(Some(base_seed.wrapping_add(iteration as u64)));
+            let swaps = 1 + rng.random_range(MAX_SWAPS);
+            let deck = mutate_deck(base, &pool, swaps, &mut rng);
+
+            let mut wins = 0;
+            for game in 0..games_per_deck {
+                let seed = base_seed
+                    .wrapping_add((iteration * games_per_deck) as u64)
+                    .wrapping_add(game as u64);
+                match panic::catch_unwind(AssertUnwindSafe(|| run_game(&deck, seed, db, false))) {
+                    Ok(result) => wins += result.win_turn.is_some() as usize,
+                    Err(payload) => {
+                        return Err(FuzzFailure { iteration, seed, deck, message: panic_message(payload.as_ref()) });
+                    }
+                }
+            }
+            Ok(wins)
+        })
+        .collect();
+
+    let mut report = FuzzReport { iterations, ..FuzzReport::default() };
+    for outcome in outcomes {
+        match outcome {
+            Ok(wins) => {
+                report.games += games_per_deck;
+                report.wins += wins;
+            }
+            Err(failure) => report.failures.push(failure),
+        }
+    }
+    report
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::deck::parse_deck_file;
+
+    #[test]
+    fn test_mutation_keeps_deck_size() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let base = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let pool: Vec<Card> = vec![db.get_card("Forest").unwrap()];
+
+        let mut rng = GameRng::new(Some(3));
+        let deck = mutate_deck(&base, &pool, MAX_SWAPS, &mut rng);
+        assert_eq!(deck.len(), base.len());
+        assert!(deck.iter().any(|c| c.name() == "Forest"));
+    }
+
+    #[test]
+    fn test_fuzz_smoke_without_panics() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let base = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+
+        let report = fuzz_decks(&base, 100, 2, 7, &db);
+        assert!(report.failures.is_empty(), "{:?}", report.failures.iter().map(|f| &f.message).collect::<Vec<_>>());
+        assert_eq!(report.games, 200);
+        assert!((0.0..=1.0).contains(&report.win_rate()));
+    }
+}
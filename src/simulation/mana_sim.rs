@@ -2,10 +2,10 @@ use crate::card::{Card, CardDatabase, LandSubtype};
 use crate::rng::GameRng;
 use crate::simulation::mulligan::bo1_opening_hand;
 use rayon::prelude::*;
+use std::io::{self, Write};
 
 #[derive(Clone, Debug)]
 struct Permanent {
-    name: String,
     is_land: bool,
     is_creature: bool,
     is_earthbent: bool,
@@ -117,7 +117,7 @@ fn has_basic_land(bf: &[Permanent]) -> bool {
     bf.iter().any(|p| p.is_land && p.is_basic)
 }
 
-fn earthbend(bf: &mut Vec<Permanent>, count: usize) {
+fn earthbend(bf: &mut [Permanent], count: usize) {
     let mut rem = count;
     for p in bf.iter_mut() {
         if rem == 0 { break; }
@@ -142,7 +142,7 @@ fn play_land_from_hand(hand: &mut Vec<Card>, bf: &mut Vec<Permanent>) -> bool {
         if let Card::Land(l) = &card {
             let tapped = if l.base.name == "Ba Sing Se" { !has_basic_land(bf) } else { l.enters_tapped };
             bf.push(Permanent {
-                name: l.base.name.clone(), is_land: true, is_creature: false,
+                is_land: true, is_creature: false,
                 is_earthbent: false, has_summoning_sickness: false, is_tapped: tapped,
                 abilities: vec![], is_basic: l.subtype == LandSubtype::Basic,
             });
@@ -171,11 +171,10 @@ fn play_spells(hand: &mut Vec<Card>, bf: &mut Vec<Permanent>, library: &mut Vec<
         if let Some((idx, mv)) = best {
             let card = hand.remove(idx);
             let abilities = card_abilities(&card);
-            let card_name = card.name().to_string();
             pay_mana(bf, mv as usize);
             if let Card::Creature(_) = &card {
                 bf.push(Permanent {
-                    name: card_name.clone(), is_land: false, is_creature: true,
+                    is_land: false, is_creature: true,
                     is_earthbent: false, has_summoning_sickness: true, is_tapped: false,
                     abilities: abilities.clone(), is_basic: false,
                 });
@@ -190,7 +189,7 @@ fn play_spells(hand: &mut Vec<Card>, bf: &mut Vec<Permanent>, library: &mut Vec<
                         }) {
                             library.remove(pos);
                             bf.push(Permanent {
-                                name: "Forest".to_string(), is_land: true, is_creature: false,
+                                is_land: true, is_creature: false,
                                 is_earthbent: false, has_summoning_sickness: false,
                                 is_tapped: true, abilities: vec![], is_basic: true,
                             });
@@ -239,7 +238,7 @@ fn run_mana_game(deck: &[Card], seed: u64, db: &CardDatabase, max_turns: usize)
     let should_mulligan = land_count == 0 || (land_count == 1 && !has_mana_dork);
     if should_mulligan {
         // Put hand back into library, shuffle, draw 6
-        library.extend(hand.drain(..));
+        library.append(&mut hand);
         rng.shuffle(&mut library);
         for _ in 0..6 {
             if let Some(card) = library.pop() {
@@ -320,13 +319,13 @@ fn percentile(sorted: &[usize], pct: f64) -> usize {
     sorted[idx.min(sorted.len() - 1)]
 }
 
-pub fn print_mana_results(results: &ManaSimResults, deck_file: &str, deck_size: usize, land_count: usize) {
-    println!("\n=== Mana Production Simulation ===");
-    println!("Deck: {} ({} cards, {} lands)", deck_file, deck_size, land_count);
-    println!("Games: {} | Turns: {} | Hand: Bo1 smoothing + mull\n", results.num_games, results.max_turns);
-    println!("{:<6} {:>8} {:>8} {:>6} {:>6} {:>6} {:>6} {:>8} {:>8}",
-        "Turn", "AvgMana", "Median", "P25", "P75", "P90", "Max", "AvgLand", "AvgCrt");
-    println!("{}", "-".repeat(76));
+pub fn print_mana_results(out: &mut dyn Write, results: &ManaSimResults, deck_file: &str, deck_size: usize, land_count: usize) -> io::Result<()> {
+    writeln!(out, "\n=== Mana Production Simulation ===")?;
+    writeln!(out, "Deck: {} ({} cards, {} lands)", deck_file, deck_size, land_count)?;
+    writeln!(out, "Games: {} | Turns: {} | Hand: Bo1 smoothing + mull\n", results.num_games, results.max_turns)?;
+    writeln!(out, "{:<6} {:>8} {:>8} {:>6} {:>6} {:>6} {:>6} {:>8} {:>8}",
+        "Turn", "AvgMana", "Median", "P25", "P75", "P90", "Max", "AvgLand", "AvgCrt")?;
+    writeln!(out, "{}", "-".repeat(76))?;
 
     for t in 0..results.max_turns {
         let mut mana = results.turn_mana_values[t].clone();
@@ -340,11 +339,13 @@ pub fn print_mana_results(results: &ManaSimResults, deck_file: &str, deck_size:
         let max_val = mana.last().copied().unwrap_or(0);
         let avg_land: f64 = results.turn_land_values[t].iter().sum::<usize>() as f64 / n;
         let avg_crt: f64 = results.turn_creature_values[t].iter().sum::<usize>() as f64 / n;
-        println!("{:<6} {:>8.2} {:>8} {:>6} {:>6} {:>6} {:>6} {:>8.2} {:>8.2}",
-            t + 1, avg_mana, median, p25, p75, p90, max_val, avg_land, avg_crt);
+        writeln!(out, "{:<6} {:>8.2} {:>8} {:>6} {:>6} {:>6} {:>6} {:>8.2} {:>8.2}",
+            t + 1, avg_mana, median, p25, p75, p90, max_val, avg_land, avg_crt)?;
     }
 
     let dork_pct = results.mana_dork_turn_1_count as f64 / results.num_games as f64 * 100.0;
-    println!("\nTurn-1 mana dork: {:.1}% ({}/{})",
-        dork_pct, results.mana_dork_turn_1_count, results.num_games);
+    writeln!(out, "\nTurn-1 mana dork: {:.1}% ({}/{})",
+        dork_pct, results.mana_dork_turn_1_count, results.num_games)?;
+
+    Ok(())
 }
\ No newline at end of file
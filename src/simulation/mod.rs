@@ -4,4 +4,11 @@ pub mod mana_sim;
 pub mod mulligan;
 pub mod decisions;
 pub mod engine;
+pub mod fuzz;
 pub mod optimize;
+pub mod results;
+pub mod scenario;
+pub mod seeds;
+pub mod solve;
+pub mod stats;
+pub mod tournament;
@@ -1,5 +1,10 @@
 use crate::card::Card;
+use crate::game::events::{GameEvent, MulliganEvent};
 use crate::rng::GameRng;
+use crate::simulation::decisions::DecisionEngine;
+
+/// Cards in an opening hand before any mulligan
+pub const DEFAULT_OPENING_HAND_SIZE: usize = 7;
 
 /// Count the number of lands in a hand
 fn count_lands(hand: &[Card]) -> usize {
@@ -8,6 +13,17 @@ fn count_lands(hand: &[Card]) -> usize {
         .count()
 }
 
+/// Add a mulligan step to the event log, if one is being recorded
+fn record(events: &mut Option<&mut Vec<GameEvent>>, event: MulliganEvent) {
+    if let Some(events) = events {
+        events.push(GameEvent::Mulligan(event));
+    }
+}
+
+fn drew(hand: &[Card]) -> MulliganEvent {
+    MulliganEvent::Drew { cards: hand.len() as u32, lands: count_lands(hand) as u32 }
+}
+
 /// Check if a card is a mill/surveil enabler
 fn is_mill_enabler(card: &Card) -> bool {
     let name = card.name();
@@ -33,6 +49,7 @@ fn is_playable_early_spell(card: &Card) -> bool {
 /// Keep hands with:
 /// - 2-5 lands AND at least one playable early spell
 /// - Mill/surveil enabler
+///
 /// Mulligan aggressive hands that can't fill graveyard
 /// Be more lenient at higher mulligan counts
 pub fn should_mulligan(hand: &[Card], _mulligan_count: u32) -> bool {
@@ -52,7 +69,7 @@ pub fn should_mulligan(hand: &[Card], _mulligan_count: u32) -> bool {
     let has_early_spell = hand.iter().any(is_playable_early_spell);
 
     // Keep if we have 2-5 lands and at least one early spell
-    if lands >= 2 && lands <= 5 && has_early_spell {
+    if (2..=5).contains(&lands) && has_early_spell {
         return false;
     }
 
@@ -60,11 +77,11 @@ pub fn should_mulligan(hand: &[Card], _mulligan_count: u32) -> bool {
     lands < 2 || !has_early_spell
 }
 
-/// Scry after mulligan - decide which cards to put on bottom
-/// Scry decision: bottom lands if hand has enough, bottom expensive spells if hand is missing lands
-fn scry_after_mulligan(library: &mut Vec<Card>, hand: &[Card], scry_count: usize) {
+/// Scry after mulligan and return how many cards went to the bottom: Bringer and Terror,
+/// lands if the hand has enough, and expensive spells if the hand is missing lands
+fn scry_after_mulligan(library: &mut Vec<Card>, hand: &[Card], scry_count: usize) -> usize {
     if scry_count == 0 || library.is_empty() {
-        return;
+        return 0;
     }
 
     let hand_lands = count_lands(hand);
@@ -75,18 +92,14 @@ fn scry_after_mulligan(library: &mut Vec<Card>, hand: &[Card], scry_count: usize
     let scry_cards: Vec<Card> = library.drain(0..scry_count.min(library.len())).collect();
 
     for card in scry_cards {
-        let name = card.name();
-
         // Always bottom Bringer/Terror (want in graveyard, not hand)
-        if name == "Bringer of the Last Gift" || name == "Terror of the Peaks" {
-            to_bottom.push(card);
-        }
+        let is_combo_piece = DecisionEngine::is_combo_piece(&card);
         // Bottom lands if we have enough in hand
-        else if matches!(card, Card::Land(_)) && hand_lands >= 3 {
-            to_bottom.push(card);
-        }
+        let is_excess_land = matches!(card, Card::Land(_)) && hand_lands >= 3;
         // Bottom expensive spells if we're missing lands
-        else if card.mana_value() >= 4 && hand_lands < 2 {
+        let is_uncastable = card.mana_value() >= 4 && hand_lands < 2;
+
+        if is_combo_piece || is_excess_land || is_uncastable {
             to_bottom.push(card);
         } else {
             to_top.push(card);
@@ -94,34 +107,61 @@ fn scry_after_mulligan(library: &mut Vec<Card>, hand: &[Card], scry_count: usize
     }
 
     // Reconstruct library: top cards, then rest, then bottom cards
+    let bottomed = to_bottom.len();
     let mut new_library = to_top;
     new_library.extend(library.drain(0..));
     new_library.extend(to_bottom);
 
     *library = new_library;
+    bottomed
 }
 
 /// Mulligan to a smaller hand size, with scry
-fn mulligan_hand(library: &mut Vec<Card>, hand_size: usize, rng: &mut GameRng) -> Vec<Card> {
+fn mulligan_hand(
+    library: &mut Vec<Card>,
+    hand_size: usize,
+    opening_size: usize,
+    rng: &mut GameRng,
+    events: &mut Option<&mut Vec<GameEvent>>,
+) -> Vec<Card> {
     let hand: Vec<Card> = library.drain(0..hand_size).collect();
+    record(events, drew(&hand));
     
     let lands = count_lands(&hand);
     if lands < 2 && hand_size > 4 {
         // Still bad, mulligan again
+        record(events, MulliganEvent::Mulliganed { cards: hand_size as u32 });
         library.extend(hand);
         rng.shuffle(library);
-        return mulligan_hand(library, hand_size - 1, rng);
+        return mulligan_hand(library, hand_size - 1, opening_size, rng, events);
     }
     
-    // Scry for each card below 7
-    let scry_count = 7 - hand_size;
+    // Scry for each card below the opening hand size
+    let scry_count = opening_size - hand_size;
     if scry_count > 0 {
-        scry_after_mulligan(library, &hand, scry_count);
+        let bottomed = scry_after_mulligan(library, &hand, scry_count);
+        record(events, MulliganEvent::Scried { cards: scry_count as u32, bottomed: bottomed as u32 });
     }
     
     hand
 }
 
+/// Ideal land count for a hand of `hand_size` drawn from the deck
+fn ideal_land_count(deck_land_count: usize, deck_size: usize, hand_size: usize) -> f64 {
+    (deck_land_count as f64 / deck_size as f64) * hand_size as f64
+}
+
+/// Whether the first of two hands is closer to the ideal land count (random on ties)
+fn prefer_first_hand(lands1: usize, lands2: usize, ideal: f64, rng: &mut GameRng) -> bool {
+    let dist1 = (lands1 as f64 - ideal).abs();
+    let dist2 = (lands2 as f64 - ideal).abs();
+    if dist1 != dist2 {
+        dist1 < dist2
+    } else {
+        rng.random() < 0.5
+    }
+}
+
 /// Bo1 opening hand smoothing algorithm.
 ///
 /// Draws two opening hands of `hand_size` cards from the shuffled library,
@@ -137,7 +177,7 @@ pub fn bo1_opening_hand(
     deck_land_count: usize,
     deck_size: usize,
 ) -> Vec<Card> {
-    let hand_size = 7;
+    let hand_size = DEFAULT_OPENING_HAND_SIZE;
     assert!(
         library.len() >= hand_size * 2,
         "Library must have at least {} cards to draw two hands of {}",
@@ -149,26 +189,11 @@ pub fn bo1_opening_hand(
     let hand1: Vec<Card> = library.drain(0..hand_size).collect();
     let hand2: Vec<Card> = library.drain(0..hand_size).collect();
 
-    let lands1 = count_lands(&hand1);
-    let lands2 = count_lands(&hand2);
-
-    // Ideal land count for a hand of this size
-    let ideal = (deck_land_count as f64 / deck_size as f64) * hand_size as f64;
-
-    let dist1 = (lands1 as f64 - ideal).abs();
-    let dist2 = (lands2 as f64 - ideal).abs();
-
-    let (chosen, rejected) = if dist1 < dist2 {
+    let ideal = ideal_land_count(deck_land_count, deck_size, hand_size);
+    let (chosen, rejected) = if prefer_first_hand(count_lands(&hand1), count_lands(&hand2), ideal, rng) {
         (hand1, hand2)
-    } else if dist2 < dist1 {
-        (hand2, hand1)
     } else {
-        // Tie — pick randomly
-        if rng.random() < 0.5 {
-            (hand1, hand2)
-        } else {
-            (hand2, hand1)
-        }
+        (hand2, hand1)
     };
 
     // Shuffle rejected hand back into library
@@ -180,27 +205,44 @@ pub fn bo1_opening_hand(
 
 /// Resolve mulligans starting from opening hand
 /// Returns the final hand after all mulligans and scries
-pub fn resolve_mulligans(library: &mut Vec<Card>, rng: &mut GameRng) -> Vec<Card> {
-    // Draw two hands of 7 using BO1 hand smoother
-    let hand1: Vec<Card> = library.drain(0..7).collect();
-    let hand2: Vec<Card> = library.drain(0..7).collect();
+///
+/// `library` must be the whole shuffled deck: its land ratio sets the Bo1 smoother's ideal.
+/// `hand_size` is the opening hand before mulligans (`DEFAULT_OPENING_HAND_SIZE` normally).
+/// Each step (hands drawn, mulligans, scries, the kept hand) goes to `events` if given.
+pub fn resolve_mulligans(
+    library: &mut Vec<Card>,
+    rng: &mut GameRng,
+    hand_size: usize,
+    mut events: Option<&mut Vec<GameEvent>>,
+) -> Vec<Card> {
+    let hand = choose_hand(library, rng, hand_size, &mut events);
+    record(&mut events, MulliganEvent::Kept { cards: hand.len() as u32, lands: count_lands(&hand) as u32 });
+    hand
+}
+
+fn choose_hand(
+    library: &mut Vec<Card>,
+    rng: &mut GameRng,
+    hand_size: usize,
+    events: &mut Option<&mut Vec<GameEvent>>,
+) -> Vec<Card> {
+    let ideal = ideal_land_count(count_lands(library), library.len(), hand_size);
+
+    // Draw two opening hands using BO1 hand smoother
+    let hand1: Vec<Card> = library.drain(0..hand_size).collect();
+    let hand2: Vec<Card> = library.drain(0..hand_size).collect();
+    record(events, drew(&hand1));
+    record(events, drew(&hand2));
     
     let lands1 = count_lands(&hand1);
     let lands2 = count_lands(&hand2);
     
     let (mut chosen_hand, rejected_hand) = if lands1 >= 2 && lands2 >= 2 {
-        // Both hands have at least 2 lands, pick the one with fewer lands
-        if lands1 < lands2 {
+        // Both hands have at least 2 lands, pick the one closer to the deck's ideal
+        if prefer_first_hand(lands1, lands2, ideal, rng) {
             (hand1, hand2)
-        } else if lands2 < lands1 {
-            (hand2, hand1)
         } else {
-            // Same land count, random pick (matches TypeScript behavior)
-            if rng.random() < 0.5 {
-                (hand1, hand2)
-            } else {
-                (hand2, hand1)
-            }
+            (hand2, hand1)
         }
     } else if lands1 >= 2 {
         (hand1, hand2)
@@ -208,10 +250,11 @@ pub fn resolve_mulligans(library: &mut Vec<Card>, rng: &mut GameRng) -> Vec<Card
         (hand2, hand1)
     } else {
         // Both hands have 0-1 lands, need to mulligan
+        record(events, MulliganEvent::Mulliganed { cards: hand_size as u32 });
         library.extend(hand1);
         library.extend(hand2);
         rng.shuffle(library);
-        return mulligan_hand(library, 6, rng);
+        return mulligan_hand(library, hand_size - 1, hand_size, rng, events);
     };
     
     // Put rejected hand back into library and shuffle
@@ -226,9 +269,10 @@ pub fn resolve_mulligans(library: &mut Vec<Card>, rng: &mut GameRng) -> Vec<Card
         }
 
         let next_hand_size = chosen_hand.len() - 1;
+        record(events, MulliganEvent::Mulliganed { cards: chosen_hand.len() as u32 });
         library.extend(chosen_hand.clone());
         rng.shuffle(library);
-        chosen_hand = mulligan_hand(library, next_hand_size, rng);
+        chosen_hand = mulligan_hand(library, next_hand_size, hand_size, rng, events);
         mulligan_count += 1;
     }
 
@@ -242,7 +286,7 @@ mod tests {
 
     #[test]
     fn test_count_lands() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
 
@@ -252,7 +296,7 @@ mod tests {
 
     #[test]
     fn test_is_mill_enabler() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
 
         // Test known mill enablers
         let town_greeter = db.get_card("Town Greeter").expect("Town Greeter should exist");
@@ -268,7 +312,7 @@ mod tests {
 
     #[test]
     fn test_should_mulligan_bad_hand() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
 
@@ -279,7 +323,7 @@ mod tests {
 
     #[test]
     fn test_should_mulligan_with_enabler() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let town_greeter = db.get_card("Town Greeter").expect("Town Greeter should exist");
 
@@ -290,7 +334,7 @@ mod tests {
 
     #[test]
     fn test_resolve_mulligans_basic() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let mut rng = crate::rng::GameRng::new(Some(42));
 
         // Create a deck with enough cards
@@ -299,16 +343,62 @@ mod tests {
             library.push(db.get_card("Forest").expect("Forest should exist"));
         }
 
-        let hand = resolve_mulligans(&mut library, &mut rng);
+        let hand = resolve_mulligans(&mut library, &mut rng, DEFAULT_OPENING_HAND_SIZE, None);
 
         // Should have a hand of at least 4 cards (minimum after mulligans)
         assert!(hand.len() >= 4, "Hand should have at least 4 cards");
         assert!(hand.len() <= 7, "Hand should have at most 7 cards");
     }
 
+    #[test]
+    fn test_resolve_mulligans_targets_deck_land_ratio() {
+        let db = CardDatabase::fixture();
+        let forest = db.get_card("Forest").expect("Forest should exist");
+        let cache_grab = db.get_card("Cache Grab").expect("Cache Grab should exist");
+
+        // 17 lands in 40 cards: ideal = 17/40 * 7 = 2.975
+        let mut total_lands = 0;
+        let mut sevens = 0;
+        for seed in 0..1000 {
+            let mut library: Vec<Card> = Vec::new();
+            for _ in 0..17 { library.push(forest.clone()); }
+            for _ in 0..23 { library.push(cache_grab.clone()); }
+            let mut rng = crate::rng::GameRng::new(Some(seed));
+            rng.shuffle(&mut library);
+
+            let hand = resolve_mulligans(&mut library, &mut rng, DEFAULT_OPENING_HAND_SIZE, None);
+            if hand.len() == 7 {
+                total_lands += count_lands(&hand);
+                sevens += 1;
+            }
+        }
+        let avg = total_lands as f64 / sevens as f64;
+        // Always keeping the fewer-land hand averaged about 2.76 here
+        assert!((avg - 2.975).abs() < 0.1, "average lands in kept 7s = {}", avg);
+    }
+
+    #[test]
+    fn test_resolve_mulligans_keeps_a_larger_opening_hand() {
+        let db = CardDatabase::fixture();
+        let forest = db.get_card("Forest").expect("Forest should exist");
+        let cache_grab = db.get_card("Cache Grab").expect("Cache Grab should exist");
+
+        // Alternating lands and mill enablers: both hands are keepable, so no mulligan
+        let mut library: Vec<Card> = Vec::new();
+        for _ in 0..20 {
+            library.push(forest.clone());
+            library.push(cache_grab.clone());
+        }
+        let mut rng = crate::rng::GameRng::new(Some(7));
+
+        let hand = resolve_mulligans(&mut library, &mut rng, 8, None);
+        assert_eq!(hand.len(), 8);
+        assert_eq!(library.len(), 32);
+    }
+
     #[test]
     fn test_bo1_opening_hand_returns_seven_cards() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let mut rng = crate::rng::GameRng::new(Some(100));
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
@@ -327,7 +417,7 @@ mod tests {
 
     #[test]
     fn test_bo1_opening_hand_prefers_closer_to_ideal() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
 
@@ -354,7 +444,7 @@ mod tests {
 
     #[test]
     fn test_bo1_opening_hand_picks_fewer_lands_when_both_above_ideal() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
 
@@ -378,7 +468,7 @@ mod tests {
 
     #[test]
     fn test_bo1_opening_hand_tie_still_returns_valid_hand() {
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
 
@@ -404,7 +494,7 @@ mod tests {
     fn test_bo1_opening_hand_statistical_bias() {
         // Run many iterations and verify that average land count is
         // closer to ideal than a single random draw would give.
-        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let db = CardDatabase::fixture();
         let forest = db.get_card("Forest").expect("Forest should exist");
         let terror = db.get_card("Terror of the Peaks").expect("Terror should exist");
 
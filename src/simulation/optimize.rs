@@ -1,10 +1,72 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
 use crate::card::{Card, CardDatabase};
 use crate::rng::GameRng;
 use crate::simulation::deck::parse_deck_file;
 
 /// Land configuration: map of land name to count
-pub type LandConfig = HashMap<String, usize>;
+///
+/// Equality and hashing ignore zero counts and map order, so two configs with the
+/// same lands compare equal however they were generated.
+#[derive(Debug, Clone, Default)]
+pub struct LandConfig(HashMap<String, usize>);
+
+impl LandConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lands with a non-zero count, sorted by name
+    fn normalized(&self) -> Vec<(&str, usize)> {
+        let mut lands: Vec<(&str, usize)> = self.0.iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        lands.sort_unstable();
+        lands
+    }
+}
+
+impl Deref for LandConfig {
+    type Target = HashMap<String, usize>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for LandConfig {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a LandConfig {
+    type Item = (&'a String, &'a usize);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl PartialEq for LandConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for LandConfig {}
+
+impl Hash for LandConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
+}
+
+/// How many draws to try for an unseen config before giving up
+pub const MAX_UNIQUE_CONFIG_ATTEMPTS: usize = 1000;
 
 /// Fixed cards configuration: map of card name to count (extracted from deck file)
 pub type FixedCards = Vec<(String, usize)>;
@@ -126,14 +188,26 @@ pub fn generate_random_land_config_shuffle(rng: &mut GameRng) -> LandConfig {
     rng.shuffle(&mut pool);
 
     // Take from shuffled pool to fill remaining slots
-    for i in 0..remaining.min(pool.len()) {
-        let land_name = pool[i].clone();
-        *config.entry(land_name).or_insert(0) += 1;
+    for land_name in pool.iter().take(remaining) {
+        *config.entry(land_name.clone()).or_insert(0) += 1;
     }
 
     config
 }
 
+/// Draw configs from `generate` until one not already in `seen`, and record it
+///
+/// Returns None if every attempt produced a config that was already evaluated.
+pub fn draw_unique_config(seen: &mut HashSet<LandConfig>, mut generate: impl FnMut() -> LandConfig) -> Option<LandConfig> {
+    for _ in 0..MAX_UNIQUE_CONFIG_ATTEMPTS {
+        let config = generate();
+        if seen.insert(config.clone()) {
+            return Some(config);
+        }
+    }
+    None
+}
+
 /// Build a complete deck from a land configuration and fixed cards
 pub fn build_deck_from_config_with_fixed(config: &LandConfig, fixed_cards: &FixedCards, db: &CardDatabase) -> Result<Vec<Card>, String> {
     let mut cards = Vec::new();
@@ -184,7 +258,6 @@ pub fn config_to_string(config: &LandConfig) -> String {
 /// Calculate a short hash for a deck configuration with custom fixed cards
 pub fn calculate_deck_hash_with_fixed(config: &LandConfig, fixed_cards: &FixedCards) -> String {
     use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
 
     // Create a sorted list of all cards (fixed + lands)
     let mut all_cards: Vec<(String, usize)> = fixed_cards.clone();
@@ -281,3 +354,48 @@ pub fn save_deck_to_file(config: &LandConfig, params: &DeckSaveParams) -> std::i
     Ok(filename)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(lands: &[(&str, usize)]) -> LandConfig {
+        let mut config = LandConfig::new();
+        for (name, count) in lands {
+            config.insert(name.to_string(), *count);
+        }
+        config
+    }
+
+    #[test]
+    fn test_equal_land_configs_hash_the_same() {
+        let a = config(&[("Forest", 4), ("Watery Grave", 4), ("Cavern of Souls", 4)]);
+        let b = config(&[("Cavern of Souls", 4), ("Island", 0), ("Watery Grave", 4), ("Forest", 4)]);
+        assert_eq!(a, b);
+
+        let hash = |c: &LandConfig| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            c.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(a, config(&[("Forest", 3), ("Watery Grave", 5), ("Cavern of Souls", 4)]));
+    }
+
+    #[test]
+    fn test_draw_unique_config_skips_repeats() {
+        let first = config(&[("Forest", 12), ("Swamp", 12)]);
+        let second = config(&[("Forest", 11), ("Swamp", 13)]);
+        let mut seen = HashSet::new();
+        seen.insert(first.clone());
+
+        // A repeat is rejected and a replacement drawn
+        let mut draws = vec![second.clone(), first.clone()];
+        assert_eq!(draw_unique_config(&mut seen, || draws.pop().unwrap()), Some(second.clone()));
+        assert_eq!(seen.len(), 2);
+
+        // Nothing new to draw
+        assert_eq!(draw_unique_config(&mut seen, || first.clone()), None);
+        assert_eq!(seen.len(), 2);
+    }
+}
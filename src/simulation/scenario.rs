@@ -0,0 +1,202 @@
+//! Weighted metagame scenarios
+//!
+//! A scenario file is a JSON list of scenarios, each a weight plus the game settings
+//! to play under:
+//!
+//! ```json
+//! [
+//!   { "name": "On the play", "weight": 0.55, "play_probability": 1.0 },
+//!   { "name": "On the draw", "weight": 0.35, "play_probability": 0.0 },
+//!   { "name": "Rest in Peace", "weight": 0.1,
+//!     "graveyard_hate": { "turn": 3, "mode": "continuous" }, "opponent_clock": 7 },
+//!   { "name": "Aggro", "weight": 0.1, "opponent_curve": "linear" },
+//!   { "name": "Sideboarded hate", "weight": 0.1,
+//!     "replacement_effects": [{ "cant_be_reanimated": "Terror of the Peaks" }] }
+//! ]
+//! ```
+
+use crate::card::{Card, CardDatabase};
+use crate::game::state::{GraveyardHate, OpponentCurve, ReplacementEffect};
+use crate::simulation::engine::{run_game_with_config, GameConfig};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ScenarioError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Invalid scenario '{name}': {reason}")]
+    Invalid { name: String, reason: String },
+}
+
+/// One weighted matchup scenario
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    pub weight: f64,
+    /// Chance of being on the play (the run's setting if absent)
+    #[serde(default)]
+    pub play_probability: Option<f64>,
+    #[serde(default)]
+    pub graveyard_hate: Option<GraveyardHate>,
+    #[serde(default)]
+    pub opponent_clock: Option<u32>,
+    #[serde(default)]
+    pub opponent_curve: Option<OpponentCurve>,
+    /// In play from the start, on top of the run's own
+    #[serde(default)]
+    pub replacement_effects: Vec<ReplacementEffect>,
+}
+
+impl Scenario {
+    /// Game settings for this scenario, on top of `base`
+    pub fn config(&self, base: &GameConfig) -> GameConfig {
+        let mut config = GameConfig {
+            play_probability: self.play_probability.unwrap_or(base.play_probability),
+            graveyard_hate: self.graveyard_hate.or(base.graveyard_hate),
+            opponent_clock: self.opponent_clock.or(base.opponent_clock),
+            opponent_curve: self.opponent_curve.or(base.opponent_curve),
+            ..base.clone()
+        };
+        for effect in &self.replacement_effects {
+            config.replacement_effects.add(effect.clone());
+        }
+        config
+    }
+}
+
+/// Win rate of one scenario
+#[derive(Debug, Clone)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub weight: f64,
+    pub games: usize,
+    pub wins: usize,
+}
+
+impl ScenarioResult {
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+}
+
+/// Load and validate a scenario file
+pub fn parse_scenario_file(path: &str) -> Result<Vec<Scenario>, ScenarioError> {
+    let content = std::fs::read_to_string(path)?;
+    parse_scenarios(&content)
+}
+
+/// Parse and validate scenarios from a JSON string (same format as `parse_scenario_file`)
+pub fn parse_scenarios(content: &str) -> Result<Vec<Scenario>, ScenarioError> {
+    let scenarios: Vec<Scenario> = serde_json::from_str(content)?;
+    for scenario in &scenarios {
+        let invalid = |reason: &str| ScenarioError::Invalid {
+            name: scenario.name.clone(),
+            reason: reason.to_string(),
+        };
+        if !scenario.weight.is_finite() || scenario.weight <= 0.0 {
+            return Err(invalid("weight must be positive"));
+        }
+        if scenario.play_probability.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+            return Err(invalid("play_probability must be between 0 and 1"));
+        }
+    }
+    Ok(scenarios)
+}
+
+/// Play `num_games` games per scenario on the same seeds
+pub fn run_scenarios(
+    scenarios: &[Scenario],
+    deck: &[Card],
+    base_seed: u64,
+    num_games: usize,
+    db: &CardDatabase,
+    base: &GameConfig,
+) -> Vec<ScenarioResult> {
+    use rayon::prelude::*;
+
+    scenarios
+        .iter()
+        .map(|scenario| {
+            let config = scenario.config(base);
+            let wins = (0..num_games)
+                .into_par_iter()
+                .filter(|&i| {
+                    run_game_with_config(deck, base_seed.wrapping_add(i as u64), db, false, &config)
+                        .win_turn
+                        .is_some()
+                })
+                .count();
+            ScenarioResult {
+                name: scenario.name.clone(),
+                weight: scenario.weight,
+                games: num_games,
+                wins,
+            }
+        })
+        .collect()
+}
+
+/// Overall win rate with each scenario's win rate weighted by its (normalized) weight
+pub fn weighted_win_rate(results: &[ScenarioResult]) -> f64 {
+    let total_weight: f64 = results.iter().map(|r| r.weight).sum();
+    if total_weight <= 0.0 {
+        return 0.0;
+    }
+    results.iter().map(|r| r.weight * r.win_rate()).sum::<f64>() / total_weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::deck::parse_deck_file;
+
+    #[test]
+    fn test_equal_weights_average_win_rates() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        let scenarios = parse_scenarios(
+            r#"[
+                { "name": "Goldfish", "weight": 1.0 },
+                { "name": "Fast opponent", "weight": 1.0, "opponent_clock": 6 }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = run_scenarios(&scenarios, &deck, 42, 200, &db, &GameConfig::default());
+        let (goldfish, fast) = (results[0].win_rate(), results[1].win_rate());
+        assert!(fast < goldfish, "a clock should cost games ({} vs {})", fast, goldfish);
+        assert!((weighted_win_rate(&results) - (goldfish + fast) / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_scenarios_rejected() {
+        assert!(parse_scenarios(r#"[{ "name": "Zero", "weight": 0.0 }]"#).is_err());
+        assert!(parse_scenarios(r#"[{ "name": "Bad coin", "weight": 1.0, "play_probability": 1.5 }]"#).is_err());
+
+        let parsed = parse_scenarios(
+            r#"[{ "name": "Bog", "weight": 1.0, "graveyard_hate": { "turn": 2, "mode": "one_shot" } }]"#,
+        )
+        .unwrap();
+        assert_eq!(parsed[0].play_probability, None);
+        assert!(parsed[0].graveyard_hate.is_some());
+
+        let hate = parse_scenarios(
+            r#"[{ "name": "Hate", "weight": 1.0, "replacement_effects": ["graveyard_exiles", { "cant_be_reanimated": "Terror of the Peaks" }] }]"#,
+        )
+        .unwrap();
+        let config = hate[0].config(&GameConfig::default());
+        assert!(config.replacement_effects.exiles_to_graveyard());
+        assert_eq!(config.replacement_effects.0[1], ReplacementEffect::CantBeReanimated("Terror of the Peaks".to_string()));
+
+        // Without its own play_probability a scenario keeps the run's forced start
+        let on_the_play = GameConfig { play_probability: 1.0, ..GameConfig::default() };
+        assert_eq!(parsed[0].config(&on_the_play).play_probability, 1.0);
+    }
+}
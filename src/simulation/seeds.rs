@@ -0,0 +1,186 @@
+//! Seed files
+//!
+//! A seed file lists one game seed (u64) per line, so a curated set of games - hard
+//! cases, regressions - can be replayed exactly instead of a `0..num_games` range.
+//! Blank lines and lines starting with `#` are skipped.
+//!
+//! `curate` picks such a set out of a finished batch: one representative game per
+//! category, written as a seed file with each game's summary as a comment.
+
+use crate::simulation::engine::{GameResult, LossReason};
+use std::fmt::Write;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SeedsError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Line {line}: '{text}' is not a seed")]
+    InvalidSeed { line: usize, text: String },
+}
+
+/// Parse seed file contents
+pub fn parse_seeds(content: &str) -> Result<Vec<u64>, SeedsError> {
+    let mut seeds = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let seed = line.parse().map_err(|_| SeedsError::InvalidSeed { line: index + 1, text: line.to_string() })?;
+        seeds.push(seed);
+    }
+    Ok(seeds)
+}
+
+/// Load a seed file
+pub fn parse_seeds_file(path: &str) -> Result<Vec<u64>, SeedsError> {
+    parse_seeds(&std::fs::read_to_string(path)?)
+}
+
+/// Per-seed results as CSV (`seed,win_turn`, with an empty win turn for games not won)
+pub fn format_seeds_csv(seeds: &[u64], results: &[GameResult]) -> String {
+    let mut csv = String::from("seed,win_turn\n");
+    for (seed, result) in seeds.iter().zip(results) {
+        let win_turn = result.win_turn.map(|turn| turn.to_string()).unwrap_or_default();
+        writeln!(csv, "{},{}", seed, win_turn).expect("writing to a String cannot fail");
+    }
+    csv
+}
+
+/// What a curated game is representative of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CuratedCategory {
+    /// The earliest win
+    FastestWin,
+    /// A win on the median winning turn
+    TypicalWin,
+    /// A damage win with no damage past lethal
+    NarrowWin,
+    Loss(LossReason),
+}
+
+impl std::fmt::Display for CuratedCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CuratedCategory::FastestWin => write!(f, "Fastest win"),
+            CuratedCategory::TypicalWin => write!(f, "Typical win"),
+            CuratedCategory::NarrowWin => write!(f, "Narrow win"),
+            CuratedCategory::Loss(reason) => write!(f, "Loss ({})", reason),
+        }
+    }
+}
+
+/// One representative game of a batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuratedGame {
+    pub category: CuratedCategory,
+    pub seed: u64,
+    /// One-line description of how the game went
+    pub summary: String,
+}
+
+fn summarize(result: &GameResult) -> String {
+    let start = if result.on_the_play { "on the play" } else { "on the draw" };
+    match (result.win_turn, result.win_line) {
+        (Some(turn), Some(line)) => {
+            let mut summary = format!("won turn {} by {}, {}", turn, line, start);
+            if let (Some(damage), Some(overkill)) = (result.winning_turn_damage, result.overkill) {
+                write!(summary, ", {} damage ({} overkill)", damage, overkill).expect("writing to a String cannot fail");
+            }
+            summary
+        }
+        _ => format!("not won, {}", start),
+    }
+}
+
+/// Pick one game per category from `results` (played with the matching `seeds`); categories
+/// no game falls in are left out, and ties go to the earliest game
+pub fn curate(seeds: &[u64], results: &[GameResult]) -> Vec<CuratedGame> {
+    let games: Vec<(u64, &GameResult)> = seeds.iter().copied().zip(results).collect();
+    let mut win_turns: Vec<u32> = results.iter().filter_map(|r| r.win_turn).collect();
+    win_turns.sort_unstable();
+    let median_win_turn = win_turns.get(win_turns.len().saturating_sub(1) / 2).copied();
+
+    let mut picks: Vec<(CuratedCategory, Option<&(u64, &GameResult)>)> = vec![
+        (CuratedCategory::FastestWin, games.iter().filter(|(_, r)| r.win_turn.is_some()).min_by_key(|(_, r)| r.win_turn)),
+        (CuratedCategory::TypicalWin, games.iter().find(|(_, r)| median_win_turn.is_some() && r.win_turn == median_win_turn)),
+        (CuratedCategory::NarrowWin, games.iter().find(|(_, r)| r.overkill == Some(0))),
+    ];
+    for reason in [LossReason::Killed, LossReason::DeckedOut, LossReason::OutOfTurns] {
+        picks.push((CuratedCategory::Loss(reason), games.iter().find(|(_, r)| r.loss_reason == Some(reason))));
+    }
+
+    picks
+        .into_iter()
+        .filter_map(|(category, game)| game.map(|(seed, result)| CuratedGame { category, seed: *seed, summary: summarize(result) }))
+        .collect()
+}
+
+/// Curated games as a seed file, each seed preceded by its category and summary
+pub fn format_curated(games: &[CuratedGame]) -> String {
+    let mut file = String::new();
+    for game in games {
+        writeln!(file, "# {}: {}\n{}", game.category, game.summary, game.seed).expect("writing to a String cannot fail");
+    }
+    file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::engine::WinLine;
+
+    #[test]
+    fn test_parse_seeds_skips_comments_and_rejects_garbage() {
+        assert_eq!(parse_seeds("# hard cases\n42\n\n 7 \n18446744073709551615\n").unwrap(), vec![42, 7, u64::MAX]);
+        assert!(matches!(parse_seeds("1\n-2\n"), Err(SeedsError::InvalidSeed { line: 2, .. })));
+    }
+
+    fn result(win_turn: Option<u32>, overkill: Option<u32>, loss_reason: Option<LossReason>) -> GameResult {
+        GameResult {
+            win_turn,
+            turn_with_ubg: None,
+            win_line: win_turn.map(|_| WinLine::Damage),
+            mana_utilization: None,
+            exiled_cards: Vec::new(),
+            held_combo_turn: None,
+            first_spell_turn: None,
+            events: Vec::new(),
+            flooded: false,
+            screwed: false,
+            on_the_play: true,
+            reanimated_count: None,
+            winning_turn_damage: overkill.map(|o| 20 + o),
+            overkill,
+            loss_reason,
+        }
+    }
+
+    #[test]
+    fn test_curate_picks_fastest_win_and_one_game_per_category() {
+        let seeds = [100, 101, 102, 103, 104, 105];
+        let results = [
+            result(Some(6), Some(4), None),
+            result(Some(5), Some(0), None),
+            result(None, None, Some(LossReason::DeckedOut)),
+            result(Some(4), Some(12), None),
+            result(Some(4), Some(9), None),
+            result(Some(6), Some(1), None),
+        ];
+
+        let curated = curate(&seeds, &results);
+        let picked: Vec<(CuratedCategory, u64)> = curated.iter().map(|g| (g.category, g.seed)).collect();
+        assert_eq!(picked, vec![
+            (CuratedCategory::FastestWin, 103),
+            (CuratedCategory::TypicalWin, 101),
+            (CuratedCategory::NarrowWin, 101),
+            (CuratedCategory::Loss(LossReason::DeckedOut), 102),
+        ]);
+        assert_eq!(curated[0].summary, "won turn 4 by Damage, on the play, 32 damage (12 overkill)");
+
+        let file = format_curated(&curated);
+        assert!(file.starts_with("# Fastest win: won turn 4 by Damage"));
+        assert_eq!(parse_seeds(&file).unwrap(), vec![103, 101, 101, 102]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Solve from a fixed board
+//!
+//! A board file is a JSON description of a game in progress, by card name. Only the
+//! library order is randomized; every game plays on from the turn after `turn`:
+//!
+//! ```json
+//! {
+//!   "turn": 4,
+//!   "opponent_life": 20,
+//!   "hand": ["Superior Spider-Man"],
+//!   "battlefield": ["Watery Grave", "Underground Mortuary", "Swamp", "Island"],
+//!   "graveyard": ["Bringer of the Last Gift", "Town Greeter", "Terror of the Peaks"],
+//!   "library": ["Swamp", "Cache Grab", "Forest"]
+//! }
+//! ```
+//!
+//! Permanents are treated as having entered on an earlier turn, untapped and without counters.
+
+use crate::card::{CardDatabase, CardDatabaseError};
+use crate::game::state::GameState;
+use crate::game::zones::Permanent;
+use crate::simulation::engine::GameRunner;
+use crate::simulation::stats::RunningStats;
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SolveError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Card database error: {0}")]
+    DatabaseError(#[from] CardDatabaseError),
+}
+
+fn default_life() -> i32 {
+    20
+}
+
+/// A game in progress, by card name
+#[derive(Debug, Clone, Deserialize)]
+pub struct Board {
+    /// Last turn played; solving starts with the next one
+    pub turn: u32,
+    #[serde(default = "default_life")]
+    pub life: i32,
+    #[serde(default = "default_life")]
+    pub opponent_life: i32,
+    #[serde(default)]
+    pub on_the_play: bool,
+    #[serde(default)]
+    pub hand: Vec<String>,
+    #[serde(default)]
+    pub battlefield: Vec<String>,
+    #[serde(default)]
+    pub graveyard: Vec<String>,
+    #[serde(default)]
+    pub exile: Vec<String>,
+    #[serde(default)]
+    pub library: Vec<String>,
+}
+
+impl Board {
+    /// Build the game state this board describes (library in the listed order)
+    pub fn to_state(&self, db: &CardDatabase) -> Result<GameState, SolveError> {
+        let mut state = GameState::new();
+        state.turn = self.turn;
+        state.life = self.life;
+        state.opponent_life = self.opponent_life;
+        state.on_the_play = self.on_the_play;
+        for name in &self.hand {
+            state.hand.add_card(db.get_card(name)?);
+        }
+        for name in &self.battlefield {
+            state.battlefield.add_permanent(Permanent::new(db.get_card(name)?, self.turn));
+        }
+        for name in &self.graveyard {
+            state.graveyard.add_card(db.get_card(name)?);
+        }
+        for name in &self.exile {
+            state.exile.add_card(db.get_card(name)?);
+        }
+        for name in &self.library {
+            state.library.add_card(db.get_card(name)?);
+        }
+        Ok(state)
+    }
+}
+
+/// Load a board file
+pub fn parse_board_file(path: &str) -> Result<Board, SolveError> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Outcome of playing a board forward over many library orders
+#[derive(Debug, Clone, Default)]
+pub struct SolveResult {
+    /// Turn the board was taken on
+    pub start_turn: u32,
+    pub stats: RunningStats,
+    /// Fewest turns needed to win in any game (None if none won)
+    pub fastest_win: Option<u32>,
+}
+
+impl SolveResult {
+    /// Average turns played before winning, over won games (0 if none won)
+    pub fn avg_remaining_turns(&self) -> f64 {
+        if self.stats.wins == 0 {
+            0.0
+        } else {
+            self.stats.avg_win_turn() - self.start_turn as f64
+        }
+    }
+}
+
+/// Play `state` forward to completion `num_games` times, reshuffling only the library
+pub fn solve(state: &GameState, num_games: usize, base_seed: u64, db: &CardDatabase) -> SolveResult {
+    use rayon::prelude::*;
+
+    let results: Vec<_> = (0..num_games)
+        .into_par_iter()
+        .map(|i| {
+            let mut runner = GameRunner::resume(state.clone(), base_seed.wrapping_add(i as u64), db);
+            while !runner.is_over() {
+                runner.step_turn();
+            }
+            runner.result()
+        })
+        .collect();
+
+    let mut solved = SolveResult { start_turn: state.turn, ..SolveResult::default() };
+    for result in &results {
+        solved.stats.push(result);
+        if let Some(turn) = result.win_turn {
+            let remaining = turn - state.turn;
+            solved.fastest_win = Some(solved.fastest_win.map_or(remaining, |fastest| fastest.min(remaining)));
+        }
+    }
+    solved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_one_turn_from_lethal() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let board: Board = serde_json::from_str(r#"{
+            "turn": 4,
+            "hand": ["Superior Spider-Man"],
+            "battlefield": ["Watery Grave", "Underground Mortuary", "Swamp", "Island"],
+            "graveyard": ["Bringer of the Last Gift", "Bringer of the Last Gift", "Town Greeter",
+                "Terror of the Peaks", "Terror of the Peaks", "Terror of the Peaks"],
+            "library": ["Swamp", "Forest", "Island", "Swamp", "Forest"]
+        }"#).unwrap();
+        let state = board.to_state(&db).unwrap();
+        assert_eq!(state.battlefield.permanents().len(), 4);
+
+        let solved = solve(&state, 50, 1, &db);
+        assert_eq!(solved.stats.games, 50);
+        assert_eq!(solved.stats.win_rate(), 1.0);
+        assert_eq!(solved.fastest_win, Some(1));
+        assert_eq!(solved.avg_remaining_turns(), 1.0);
+    }
+
+    #[test]
+    fn test_solve_board_past_max_turns() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let board: Board = serde_json::from_str(r#"{ "turn": 25, "opponent_life": 0, "library": ["Swamp"] }"#).unwrap();
+
+        let solved = solve(&board.to_state(&db).unwrap(), 10, 1, &db);
+        assert_eq!((solved.stats.games, solved.stats.wins), (10, 10));
+        assert_eq!(solved.stats.summary().turn_distribution.values().sum::<usize>(), 10);
+    }
+
+    #[test]
+    fn test_board_with_unknown_card_is_rejected() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let board: Board = serde_json::from_str(r#"{ "turn": 2, "hand": ["Black Lotus"] }"#).unwrap();
+        assert!(matches!(board.to_state(&db), Err(SolveError::DatabaseError(_))));
+    }
+}
@@ -0,0 +1,388 @@
+use crate::simulation::engine::{GameResult, MAX_TURNS};
+use std::collections::BTreeMap;
+
+/// z-score for a 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// Wilson score interval for a binomial proportion, as (low, high)
+pub fn wilson_interval(successes: usize, trials: usize, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 0.0);
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denom;
+    let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denom;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Headline numbers for a batch of games
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    pub games: usize,
+    pub wins: usize,
+    /// Average win turn over won games (0 if none won)
+    pub avg_win_turn: f64,
+    /// Average first turn with U, B and G available over games that got there (0 if none)
+    pub avg_ubg_turn: f64,
+    /// Average turn of the first spell cast over games that cast one (0 if none)
+    pub avg_first_spell_turn: f64,
+    /// Number of wins on each turn
+    pub turn_distribution: BTreeMap<u32, usize>,
+}
+
+impl RunSummary {
+    pub fn from_results(results: &[GameResult]) -> Self {
+        let mut stats = RunningStats::default();
+        for result in results {
+            stats.push(result);
+        }
+        stats.summary()
+    }
+
+    /// Fraction of all games won by each turn that saw a win (the kill-turn CDF)
+    pub fn cumulative_win_rates(&self) -> Vec<(u32, f64)> {
+        let mut won = 0;
+        self.turn_distribution
+            .iter()
+            .map(|(&turn, &count)| {
+                won += count;
+                (turn, won as f64 / self.games as f64)
+            })
+            .collect()
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+
+    /// 95% Wilson confidence interval on the win rate
+    pub fn win_rate_ci(&self) -> (f64, f64) {
+        wilson_interval(self.wins, self.games, Z_95)
+    }
+
+    /// Stable one-line-per-number `key: value` output for shell pipelines
+    pub fn format_key_value(&self) -> String {
+        let (low, high) = self.win_rate_ci();
+        format!(
+            "win_rate: {:.1}% (95% CI {:.1}-{:.1}%)\navg_win_turn: {:.2}\navg_ubg_turn: {:.2}\navg_first_spell_turn: {:.2}\n",
+            self.win_rate() * 100.0,
+            low * 100.0,
+            high * 100.0,
+            self.avg_win_turn,
+            self.avg_ubg_turn,
+            self.avg_first_spell_turn
+        )
+    }
+}
+
+/// Wins on each turn: a fixed-size array indexed by turn that parallel folds merge cheaply,
+/// plus an overflow map for wins after `MAX_TURNS` (a solved board can start later than that)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TurnCounts {
+    by_turn: [u32; MAX_TURNS as usize + 1],
+    later: BTreeMap<u32, u32>,
+}
+
+impl TurnCounts {
+    pub fn add(&mut self, turn: u32) {
+        match self.by_turn.get_mut(turn as usize) {
+            Some(count) => *count += 1,
+            None => *self.later.entry(turn).or_insert(0) += 1,
+        }
+    }
+
+    pub fn merge(mut self, other: Self) -> Self {
+        for (count, more) in self.by_turn.iter_mut().zip(other.by_turn) {
+            *count += more;
+        }
+        for (turn, more) in other.later {
+            *self.later.entry(turn).or_insert(0) += more;
+        }
+        self
+    }
+
+    /// Wins on each turn that saw one, as reported in `RunSummary::turn_distribution`
+    pub fn distribution(&self) -> BTreeMap<u32, usize> {
+        (0..)
+            .zip(self.by_turn)
+            .filter(|&(_, count)| count > 0)
+            .chain(self.later.iter().map(|(&turn, &count)| (turn, count)))
+            .map(|(turn, count)| (turn, count as usize))
+            .collect()
+    }
+}
+
+/// Win totals accumulated one game at a time, for runs that don't keep every result.
+/// Parallel runs fold a `RunningStats` per thread and `merge` them.
+#[derive(Debug, Clone, Default)]
+pub struct RunningStats {
+    pub games: usize,
+    pub wins: usize,
+    win_turn_sum: u64,
+    ubg_games: usize,
+    ubg_turn_sum: u64,
+    first_spell_games: usize,
+    first_spell_turn_sum: u64,
+    turn_counts: TurnCounts,
+}
+
+impl RunningStats {
+    pub fn push(&mut self, result: &GameResult) {
+        self.games += 1;
+        if let Some(turn) = result.win_turn {
+            self.wins += 1;
+            self.win_turn_sum += turn as u64;
+            self.turn_counts.add(turn);
+        }
+        if let Some(turn) = result.turn_with_ubg {
+            self.ubg_games += 1;
+            self.ubg_turn_sum += turn as u64;
+        }
+        if let Some(turn) = result.first_spell_turn {
+            self.first_spell_games += 1;
+            self.first_spell_turn_sum += turn as u64;
+        }
+    }
+
+    pub fn merge(self, other: Self) -> Self {
+        RunningStats {
+            games: self.games + other.games,
+            wins: self.wins + other.wins,
+            win_turn_sum: self.win_turn_sum + other.win_turn_sum,
+            ubg_games: self.ubg_games + other.ubg_games,
+            ubg_turn_sum: self.ubg_turn_sum + other.ubg_turn_sum,
+            first_spell_games: self.first_spell_games + other.first_spell_games,
+            first_spell_turn_sum: self.first_spell_turn_sum + other.first_spell_turn_sum,
+            turn_counts: self.turn_counts.merge(other.turn_counts),
+        }
+    }
+
+    /// The headline numbers for the games pushed so far
+    pub fn summary(&self) -> RunSummary {
+        RunSummary {
+            games: self.games,
+            wins: self.wins,
+            avg_win_turn: self.avg_win_turn(),
+            avg_ubg_turn: if self.ubg_games == 0 { 0.0 } else { self.ubg_turn_sum as f64 / self.ubg_games as f64 },
+            avg_first_spell_turn: if self.first_spell_games == 0 {
+                0.0
+            } else {
+                self.first_spell_turn_sum as f64 / self.first_spell_games as f64
+            },
+            turn_distribution: self.turn_counts.distribution(),
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+
+    /// Average win turn over won games (0 if none won)
+    pub fn avg_win_turn(&self) -> f64 {
+        if self.wins == 0 {
+            0.0
+        } else {
+            self.win_turn_sum as f64 / self.wins as f64
+        }
+    }
+
+    /// 95% Wilson confidence interval on the win rate
+    pub fn win_rate_ci(&self) -> (f64, f64) {
+        wilson_interval(self.wins, self.games, Z_95)
+    }
+
+    /// Half the width of the 95% confidence interval
+    pub fn ci_half_width(&self) -> f64 {
+        let (low, high) = self.win_rate_ci();
+        (high - low) / 2.0
+    }
+}
+
+/// When an auto-stopping run ends
+#[derive(Debug, Clone, Copy)]
+pub struct AutoStop {
+    /// Stop once the 95% CI half-width is at or below this (as a fraction, 0.005 = ±0.5%)
+    pub target_half_width: f64,
+    /// Stop after this many games even if the target wasn't reached
+    pub max_games: usize,
+    /// Games played (in parallel) between convergence checks
+    pub batch_size: usize,
+}
+
+impl AutoStop {
+    pub fn converged(&self, stats: &RunningStats) -> bool {
+        stats.games > 0 && stats.ci_half_width() <= self.target_half_width
+    }
+}
+
+/// Play games in batches on seeds `base_seed`, `base_seed + 1`, ... until the win-rate
+/// CI is narrow enough or `max_games` is reached
+pub fn run_until_converged<F>(stop: &AutoStop, base_seed: u64, play: F) -> RunningStats
+where
+    F: Fn(u64) -> GameResult + Sync,
+{
+    use rayon::prelude::*;
+
+    let mut stats = RunningStats::default();
+    while stats.games < stop.max_games && !stop.converged(&stats) {
+        let batch = stop.batch_size.max(1).min(stop.max_games - stats.games);
+        let played = (stats.games..stats.games + batch)
+            .into_par_iter()
+            .fold(RunningStats::default, |mut batch_stats, i| {
+                batch_stats.push(&play(base_seed.wrapping_add(i as u64)));
+                batch_stats
+            })
+            .reduce(RunningStats::default, RunningStats::merge);
+        stats = stats.merge(played);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::engine::WinLine;
+
+    fn result(win_turn: Option<u32>, turn_with_ubg: Option<u32>) -> GameResult {
+        result_with_first_spell(win_turn, turn_with_ubg, None)
+    }
+
+    fn result_with_first_spell(win_turn: Option<u32>, turn_with_ubg: Option<u32>, first_spell_turn: Option<u32>) -> GameResult {
+        GameResult {
+            win_turn,
+            turn_with_ubg,
+            win_line: win_turn.map(|_| WinLine::Damage),
+            mana_utilization: None,
+            exiled_cards: Vec::new(),
+            held_combo_turn: None,
+            first_spell_turn,
+            events: Vec::new(),
+            flooded: false,
+            screwed: false,
+            on_the_play: false,
+            reanimated_count: None,
+            winning_turn_damage: None,
+            overkill: None,
+            loss_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_auto_stop_converges_quickly_when_every_game_wins() {
+        let stop = AutoStop { target_half_width: 0.005, max_games: 100_000, batch_size: 100 };
+
+        let stats = run_until_converged(&stop, 1, |_| result(Some(5), Some(3)));
+        assert_eq!(stats.wins, stats.games);
+        assert!(stats.games <= 500, "took {} games", stats.games);
+        assert!(stats.ci_half_width() <= 0.005);
+        assert_eq!(stats.avg_win_turn(), 5.0);
+
+        // A coin flip can't reach ±0.5% within 1000 games, so the cap applies
+        let capped = AutoStop { max_games: 1000, ..stop };
+        let stats = run_until_converged(&capped, 1, |seed| result((seed % 2 == 0).then_some(5), None));
+        assert_eq!(stats.games, 1000);
+        assert_eq!(stats.wins, 500);
+        assert!(!capped.converged(&stats));
+    }
+
+    #[test]
+    fn test_wilson_interval_brackets_the_rate() {
+        let (low, high) = wilson_interval(50, 100, Z_95);
+        assert!((low - 0.4038).abs() < 1e-3, "low = {}", low);
+        assert!((high - 0.5962).abs() < 1e-3, "high = {}", high);
+
+        assert_eq!(wilson_interval(0, 0, Z_95), (0.0, 0.0));
+        let (low, high) = wilson_interval(10, 10, Z_95);
+        assert!(low > 0.6 && high == 1.0);
+    }
+
+    #[test]
+    fn test_summary_only_output_has_four_lines() {
+        let results = vec![
+            result_with_first_spell(Some(4), Some(2), Some(1)),
+            result_with_first_spell(Some(6), Some(3), Some(2)),
+            result_with_first_spell(None, Some(4), Some(3)),
+            result(None, None),
+        ];
+        let summary = RunSummary::from_results(&results);
+        let output = summary.format_key_value();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("win_rate: 50.0% (95% CI "));
+        assert_eq!(lines[1], "avg_win_turn: 5.00");
+        assert_eq!(lines[2], "avg_ubg_turn: 3.00");
+        assert_eq!(lines[3], "avg_first_spell_turn: 2.00");
+    }
+
+    #[test]
+    fn test_parallel_turn_counts_match_map_distribution() {
+        use rayon::prelude::*;
+
+        let results: Vec<GameResult> = (0..1000u32)
+            .map(|i| result((i % 7 != 0).then_some(3 + i % 9), Some(2 + i % 3)))
+            .collect();
+
+        let mut by_map: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
+        for turn in results.iter().filter_map(|r| r.win_turn) {
+            *by_map.entry(turn).or_insert(0) += 1;
+        }
+        let merged = results.par_iter()
+            .fold(RunningStats::default, |mut stats, result| {
+                stats.push(result);
+                stats
+            })
+            .reduce(RunningStats::default, RunningStats::merge)
+            .summary();
+
+        assert_eq!(merged.turn_distribution, by_map.into_iter().collect::<BTreeMap<_, _>>());
+        let sequential = RunSummary::from_results(&results);
+        assert_eq!(merged.turn_distribution, sequential.turn_distribution);
+        assert_eq!((merged.games, merged.wins), (sequential.games, sequential.wins));
+        assert_eq!(merged.avg_win_turn, sequential.avg_win_turn);
+        assert_eq!(merged.avg_ubg_turn, sequential.avg_ubg_turn);
+    }
+
+    #[test]
+    fn test_wins_after_max_turns_are_counted() {
+        let late = MAX_TURNS + 5;
+        let mut first = RunningStats::default();
+        first.push(&result(Some(late), None));
+        first.push(&result(Some(4), None));
+        let mut second = RunningStats::default();
+        second.push(&result(Some(late), None));
+
+        let summary = first.merge(second).summary();
+        assert_eq!(summary.turn_distribution, BTreeMap::from([(4, 1), (late, 2)]));
+        assert_eq!(summary.wins, 3);
+    }
+
+    #[test]
+    fn test_cumulative_win_rate_ends_at_win_rate() {
+        let results = vec![
+            result(Some(5), None),
+            result(Some(4), None),
+            result(Some(5), None),
+            result(Some(7), None),
+            result(None, None),
+        ];
+        let summary = RunSummary::from_results(&results);
+        let cdf = summary.cumulative_win_rates();
+
+        assert_eq!(cdf.iter().map(|(turn, _)| *turn).collect::<Vec<_>>(), vec![4, 5, 7]);
+        assert!((cdf[0].1 - 0.2).abs() < 1e-9);
+        assert!((cdf[1].1 - 0.6).abs() < 1e-9);
+        assert_eq!(cdf.last().unwrap().1, summary.win_rate());
+    }
+}
@@ -0,0 +1,141 @@
+//! Deck tournaments
+//!
+//! Plays every deck on the same seeds and ranks them by win rate. A small gap between
+//! two decks can be noise, so the per-game results are bootstrapped: each round resamples
+//! the seeds with replacement, scores every deck on that same resample and notes which deck
+//! comes out on top. The share of rounds a deck tops estimates the probability that it is
+//! really the best.
+
+use crate::card::{Card, CardDatabase};
+use crate::rng::GameRng;
+use crate::simulation::engine::{run_game_with_config, GameConfig, GameResult};
+use crate::simulation::stats::RunSummary;
+
+/// Bootstrap rounds used by `run_tournament`
+pub const BOOTSTRAP_ROUNDS: usize = 2000;
+
+/// One deck's standing in a tournament
+#[derive(Debug, Clone)]
+pub struct TournamentEntry {
+    pub name: String,
+    /// Every game the deck played, in seed order
+    pub results: Vec<GameResult>,
+    pub summary: RunSummary,
+    /// Share of bootstrap rounds in which this deck had the best win rate
+    pub p_best: f64,
+}
+
+/// Probability that each sample set has the highest true win rate, estimated from
+/// `rounds` paired bootstrap resamples: the sample sets are games on the same seeds, so
+/// each round draws one set of seed indices and scores every deck on it (decks tied for
+/// the top share the round)
+pub fn bootstrap_p_best(samples: &[Vec<bool>], rounds: usize, rng: &mut GameRng) -> Vec<f64> {
+    let games = samples.iter().map(Vec::len).min().unwrap_or(0);
+    let mut best_share = vec![0.0; samples.len()];
+    let mut rates = vec![0.0; samples.len()];
+    let mut picks = vec![0; games];
+    for _ in 0..rounds {
+        for pick in picks.iter_mut() {
+            *pick = rng.random_range(games);
+        }
+        for (rate, wins) in rates.iter_mut().zip(samples) {
+            let resampled = picks.iter().filter(|&&i| wins[i]).count();
+            *rate = resampled as f64 / games.max(1) as f64;
+        }
+        let top = rates.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let tied = rates.iter().filter(|&&rate| rate == top).count();
+        for (share, &rate) in best_share.iter_mut().zip(&rates) {
+            if rate == top {
+                *share += 1.0 / tied as f64;
+            }
+        }
+    }
+    best_share.iter().map(|share| share / rounds.max(1) as f64).collect()
+}
+
+/// Play `num_games` games (seeds `seed..`) with every deck and rank them by win rate,
+/// best first. Against a goldfish nearly every list wins eventually, so `config` usually
+/// sets an opponent clock.
+pub fn run_tournament(
+    decks: &[(String, Vec<Card>)],
+    num_games: usize,
+    seed: u64,
+    db: &CardDatabase,
+    config: &GameConfig,
+) -> Vec<TournamentEntry> {
+    use rayon::prelude::*;
+
+    let played: Vec<(String, Vec<GameResult>)> = decks
+        .iter()
+        .map(|(name, deck)| {
+            let results = (0..num_games)
+                .into_par_iter()
+                .map(|i| run_game_with_config(deck, seed.wrapping_add(i as u64), db, false, config))
+                .collect();
+            (name.clone(), results)
+        })
+        .collect();
+
+    let samples: Vec<Vec<bool>> = played
+        .iter()
+        .map(|(_, results)| results.iter().map(|r| r.win_turn.is_some()).collect())
+        .collect();
+    let p_best = bootstrap_p_best(&samples, BOOTSTRAP_ROUNDS, &mut GameRng::new(Some(seed)));
+
+    let mut entries: Vec<TournamentEntry> = played
+        .into_iter()
+        .zip(p_best)
+        .map(|((name, results), p_best)| {
+            let summary = RunSummary::from_results(&results);
+            TournamentEntry { name, results, summary, p_best }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.summary.win_rate().total_cmp(&a.summary.win_rate()).then(b.p_best.total_cmp(&a.p_best))
+    });
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::deck::parse_deck_file;
+
+    #[test]
+    fn test_tournament_is_confident_in_a_clearly_stronger_deck() {
+        let db = CardDatabase::from_file("cards.json").expect("Failed to load cards");
+        let deck = parse_deck_file("deck.txt", &db).expect("Failed to parse deck");
+        // Without Bringer and Terror the deck is left beating down with whatever it reanimates
+        let swamp = db.get_card("Swamp").expect("Swamp should exist");
+        let weakened: Vec<Card> = deck
+            .iter()
+            .map(|c| match c.name() {
+                "Bringer of the Last Gift" | "Terror of the Peaks" => swamp.clone(),
+                _ => c.clone(),
+            })
+            .collect();
+
+        let decks = vec![("weakened".to_string(), weakened), ("deck.txt".to_string(), deck)];
+        let config = GameConfig { opponent_clock: Some(6), ..GameConfig::default() };
+        let entries = run_tournament(&decks, 200, 1, &db, &config);
+
+        assert_eq!(entries[0].name, "deck.txt");
+        assert!(entries[0].p_best > 0.9, "P(best) = {}", entries[0].p_best);
+        assert_eq!(entries[0].results.len(), 200);
+        let total: f64 = entries.iter().map(|e| e.p_best).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bootstrap_pairs_games_across_decks() {
+        // The second deck wins every game the first does, plus one more: on any shared
+        // resample the first deck can at best tie, while unpaired draws would let it lead
+        let first: Vec<bool> = (0..100).map(|i| i < 50).collect();
+        let mut second = first.clone();
+        second[99] = true;
+
+        let p_best = bootstrap_p_best(&[first, second], 2000, &mut GameRng::new(Some(1)));
+        assert!(p_best[0] < 0.25, "P(best) = {:?}", p_best);
+        assert!((p_best[0] + p_best[1] - 1.0).abs() < 1e-9);
+    }
+}